@@ -16,9 +16,12 @@ pub mod lib {
     pub const SK_SHAPER: &str = "skshaper";
     pub const SK_PARAGRAPH: &str = "skparagraph";
     pub const SVG: &str = "svg";
+    pub const SKOTTIE: &str = "skottie";
+    pub const SK_SG: &str = "sksg";
     pub const SK_RESOURCES: &str = "skresources";
     pub const SK_UNICODE_CORE: &str = "skunicode_core";
     pub const SK_UNICODE_ICU: &str = "skunicode_icu";
+    pub const SK_UNICODE_CLIENT_ICU: &str = "skunicode_client_icu";
 }
 
 /// The configuration of the resulting binaries.
@@ -62,17 +65,27 @@ impl BinariesConfiguration {
         let mut additional_files = Vec::new();
         let feature_ids = features.ids();
 
+        if features.animation {
+            ninja_built_libraries.push(lib::SKOTTIE.into());
+            ninja_built_libraries.push(lib::SK_SG.into());
+        }
         if features.text_layout {
-            if target.is_windows() {
+            if target.is_windows() && !features.client_unicode {
                 additional_files.push(ICUDTL_DAT.into());
             }
             ninja_built_libraries.push(lib::SK_PARAGRAPH.into());
             ninja_built_libraries.push(lib::SK_SHAPER.into());
             ninja_built_libraries.push(lib::SK_UNICODE_CORE.into());
-            ninja_built_libraries.push(lib::SK_UNICODE_ICU.into());
+            if features.client_unicode {
+                ninja_built_libraries.push(lib::SK_UNICODE_CLIENT_ICU.into());
+            } else {
+                ninja_built_libraries.push(lib::SK_UNICODE_ICU.into());
+            }
         }
         if features.svg {
             ninja_built_libraries.push(lib::SVG.into());
+        }
+        if features.svg || features.animation {
             ninja_built_libraries.push(lib::SK_RESOURCES.into());
         }
 
@@ -26,6 +26,9 @@ pub struct Features {
     /// Features related to text layout. Modules skshaper and skparagraph.
     pub text_layout: bool,
 
+    /// Build text layout without ICU. Text segmentation must be provided by the client.
+    pub client_unicode: bool,
+
     /// Support for rendering SVG.
     pub svg: bool,
 
@@ -38,7 +41,7 @@ pub struct Features {
     /// Build with FreeType embedded.
     pub embed_freetype: bool,
 
-    /// Build with animation support (yet unsupported, no wrappers).
+    /// Build with animation support (Skottie).
     pub animation: bool,
 
     /// Support DNG file format (currently unsupported because of build errors).
@@ -63,11 +66,12 @@ impl Default for Features {
             metal: cfg!(feature = "metal"),
             d3d: cfg!(feature = "d3d"),
             text_layout: cfg!(feature = "textlayout"),
+            client_unicode: cfg!(feature = "textlayout-client-unicode"),
             svg: cfg!(feature = "svg"),
             webp_encode: cfg!(feature = "webp-encode"),
             webp_decode: cfg!(feature = "webp-decode"),
             embed_freetype: cfg!(feature = "embed-freetype"),
-            animation: false,
+            animation: cfg!(feature = "skottie"),
             dng: false,
             particles: false,
             freetype_woff2: cfg!(feature = "freetype-woff2"),
@@ -108,6 +112,9 @@ impl Features {
         if self.text_layout {
             feature_ids.push(feature_id::TEXTLAYOUT);
         }
+        if self.client_unicode {
+            feature_ids.push(feature_id::CLIENT_UNICODE);
+        }
         if self.svg {
             feature_ids.push(feature_id::SVG);
         }
@@ -120,6 +127,9 @@ impl Features {
         if self.embed_freetype {
             feature_ids.push(feature_id::EMBED_FREETYPE);
         }
+        if self.animation {
+            feature_ids.push(feature_id::SKOTTIE);
+        }
 
         feature_ids.into_iter().collect()
     }
@@ -132,6 +142,7 @@ mod feature_id {
     pub const METAL: &str = "metal";
     pub const D3D: &str = "d3d";
     pub const TEXTLAYOUT: &str = "textlayout";
+    pub const CLIENT_UNICODE: &str = "clientunicode";
     pub const SVG: &str = "svg";
     pub const WEBPE: &str = "webpe";
     pub const WEBPD: &str = "webpd";
@@ -139,4 +150,5 @@ mod feature_id {
     pub const X11: &str = "x11";
     pub const WAYLAND: &str = "wayland";
     pub const EMBED_FREETYPE: &str = "freetype";
+    pub const SKOTTIE: &str = "skottie";
 }
@@ -120,7 +120,7 @@ impl FinalBuildConfiguration {
                 .arg("is_debug", yes_if(build.skia_debug))
                 .arg("skia_enable_svg", yes_if(features.svg))
                 .arg("skia_enable_gpu", yes_if(features.gpu()))
-                .arg("skia_enable_skottie", no());
+                .arg("skia_enable_skottie", yes_if(features.animation));
 
             // Always enable PDF document support, because it gets switched off for WASM builds.
             // See <https://github.com/rust-skia/rust-skia/issues/694>
@@ -166,8 +166,14 @@ impl FinalBuildConfiguration {
             if features.text_layout {
                 builder
                     .arg("skia_enable_skshaper", yes())
-                    .arg("skia_use_icu", yes())
-                    .arg("skia_use_system_icu", yes_if(use_system_libraries))
+                    .arg("skia_use_icu", yes_if(!features.client_unicode))
+                    .arg(
+                        "skia_use_system_icu",
+                        yes_if(use_system_libraries && !features.client_unicode),
+                    )
+                    // Text segmentation that is provided by the client, see
+                    // `ParagraphBuilder::new_with_client_unicode()`.
+                    .arg("skia_use_client_icu", yes_if(features.client_unicode))
                     .arg("skia_use_harfbuzz", yes())
                     .arg("skia_pdf_subset_harfbuzz", yes())
                     .arg("skia_use_system_harfbuzz", yes_if(use_system_libraries))
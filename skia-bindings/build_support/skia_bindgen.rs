@@ -58,9 +58,15 @@ impl Configuration {
             if features.text_layout {
                 sources.extend(vec!["src/shaper.cpp".into(), "src/paragraph.cpp".into()]);
             }
+            if features.svg || features.animation {
+                sources.push("src/resources.cpp".into());
+            }
             if features.svg {
                 sources.push("src/svg.cpp".into());
             }
+            if features.animation {
+                sources.push("src/skottie.cpp".into());
+            }
             if features.webp_encode {
                 sources.push("src/webp-encode.cpp".into());
             }
@@ -450,6 +456,10 @@ const OPAQUE_TYPES: &[&str] = &[
     "SkTLazy",             // causes wrong layouts in SkSVGSVG
     "SkTCopyOnFirstWrite", // causes wrong layouts in SkSVGRenderContext
     "skresources::ResourceProvider",
+    "RustSVGRenderContext",
+    "RustSVGNodeIter",
+    // Feature `skottie`:
+    "skottie::Animation",
     // m107 (layout failure)
     "skgpu::VulkanMemoryAllocator",
     // m109 (ParagraphPainter::SkPaintOrID)
@@ -607,6 +617,8 @@ const ENUM_REWRITES: &[EnumEntry] = &[
     // SkImage_*
     ("BitDepth", rewrite::k_xxx),
     ("CachingHint", rewrite::k_xxx_name),
+    ("RescaleGamma", rewrite::k_xxx),
+    ("RescaleMode", rewrite::k_xxx),
     ("SkTextureCompressionType", rewrite::k_xxx),
     // SkImageFilter_MapDirection
     ("MapDirection", rewrite::k_xxx_name),
@@ -740,6 +752,7 @@ const ENUM_REWRITES: &[EnumEntry] = &[
     ("GrMarkFrameBoundary", rewrite::k_xxx),
     // SkResources.h
     ("ImageDecodeStrategy", rewrite::k_xxx),
+    ("SizeFit", rewrite::k_xxx),
 ];
 
 pub(crate) mod rewrite {
@@ -898,16 +911,26 @@ pub(crate) mod definitions {
                 "obj/modules/skshaper/skshaper.ninja".into(),
                 "obj/modules/skparagraph/skparagraph.ninja".into(),
                 "obj/modules/skunicode/skunicode_core.ninja".into(),
-                "obj/modules/skunicode/skunicode_icu.ninja".into(),
             ]);
-            // shaper.cpp includes SkLoadICU.h
-            if !use_system_libraries {
-                files.push("obj/third_party/icu/icu.ninja".into())
+            if features.client_unicode {
+                files.push("obj/modules/skunicode/skunicode_client_icu.ninja".into());
+            } else {
+                files.push("obj/modules/skunicode/skunicode_icu.ninja".into());
+                // shaper.cpp includes SkLoadICU.h
+                if !use_system_libraries {
+                    files.push("obj/third_party/icu/icu.ninja".into())
+                }
             }
         }
         if features.svg {
             files.push("obj/modules/svg/svg.ninja".into());
         }
+        if features.animation {
+            files.extend(vec![
+                "obj/modules/skottie/skottie.ninja".into(),
+                "obj/modules/sksg/sksg.ninja".into(),
+            ]);
+        }
         files
     }
 
@@ -1,3 +1,4 @@
+#include <atomic>
 #include <cassert>
 #include <tuple>
 #include <vector>
@@ -22,6 +23,10 @@
 #include "include/codec/SkWebpDecoder.h"
 #endif
 
+// SkCodec looks up the frames of animated images through a SkFrameHolder, which has no public
+// header.
+#include "src/codec/SkFrameHolder.h"
+
 // core/
 #include "include/core/SkAnnotation.h"
 #include "include/core/SkArc.h"
@@ -50,6 +55,8 @@
 #include "include/core/SkImageInfo.h"
 #include "include/core/SkM44.h"
 #include "include/core/SkMaskFilter.h"
+#include "include/core/SkMesh.h"
+#include "include/core/SkOpenTypeSVGDecoder.h"
 #include "include/core/SkPaint.h"
 #include "include/core/SkPath.h"
 #include "include/core/SkPathBuilder.h"
@@ -66,6 +73,7 @@
 #include "include/core/SkRegion.h"
 #include "include/core/SkRRect.h"
 #include "include/core/SkRSXform.h"
+#include "include/core/SkSerialProcs.h"
 #include "include/core/SkStream.h"
 #include "include/core/SkStrokeRec.h"
 #include "include/core/SkSurface.h"
@@ -94,6 +102,10 @@
 #include "include/effects/SkOverdrawColorFilter.h"
 
 #include "include/effects/SkRuntimeEffect.h"
+#include "include/sksl/SkSLDebugTrace.h"
+// SkSL::DebugTrace only writes the trace as JSON. The trace data is read from the private
+// SkSL::DebugTracePriv instead of parsing it again.
+#include "src/sksl/tracing/SkSLDebugTracePriv.h"
 
 #include "include/effects/SkPerlinNoiseShader.h"
 #include "include/effects/SkShaderMaskFilter.h"
@@ -264,6 +276,195 @@ extern "C" void C_SkWebpDecoder_Decoder(SkCodecs::Decoder* uninitialized) {
 
 #endif
 
+//
+// Codecs and decoders implemented in Rust.
+//
+
+namespace Codec {
+    extern "C" {
+        typedef void (*Drop)(TraitObject);
+        typedef SkCodec::Result (*OnGetPixels)(TraitObject, int frameIndex, void* pixels, size_t rowBytes);
+        typedef int (*OnGetFrameCount)(TraitObject);
+        typedef bool (*OnGetFrameInfo)(TraitObject, int index, SkCodec::FrameInfo*);
+        typedef int (*OnGetRepetitionCount)(TraitObject);
+    }
+}
+
+class RustFrame final : public SkFrame {
+public:
+    RustFrame(int id, SkEncodedInfo::Alpha alpha)
+    : SkFrame(id)
+    , _alpha(alpha)
+    { }
+
+protected:
+    SkEncodedInfo::Alpha onReportedAlpha() const override {
+        return _alpha;
+    }
+
+private:
+    SkEncodedInfo::Alpha _alpha;
+};
+
+// Rust codecs decode every frame independently, so each frame covers the whole image and does not
+// depend on a prior frame.
+class RustFrameHolder final : public SkFrameHolder {
+public:
+    RustFrameHolder(SkISize dimensions, int frameCount, bool opaque) {
+        fScreenWidth = dimensions.width();
+        fScreenHeight = dimensions.height();
+        auto alpha = opaque ? SkEncodedInfo::kOpaque_Alpha : SkEncodedInfo::kUnpremul_Alpha;
+        for (int i = 0; i < frameCount; ++i) {
+            auto frame = std::make_unique<RustFrame>(i, alpha);
+            frame->setXYWH(0, 0, fScreenWidth, fScreenHeight);
+            frame->setHasAlpha(!opaque);
+            frame->setBlend(SkCodecAnimation::Blend::kSrc);
+            this->setAlphaAndRequiredFrame(frame.get());
+            _frames.push_back(std::move(frame));
+        }
+    }
+
+protected:
+    const SkFrame* onGetFrame(int i) const override {
+        return i >= 0 && i < static_cast<int>(_frames.size()) ? _frames[i].get() : nullptr;
+    }
+
+private:
+    std::vector<std::unique_ptr<RustFrame>> _frames;
+};
+
+class RustCodec final : public SkCodec {
+public:
+    struct Param {
+        TraitObject trait;
+        int width;
+        int height;
+        bool opaque;
+        SkEncodedImageFormat encodedFormat;
+        SkEncodedOrigin origin;
+        ::Codec::Drop drop;
+        ::Codec::OnGetPixels onGetPixels;
+        ::Codec::OnGetFrameCount onGetFrameCount;
+        ::Codec::OnGetFrameInfo onGetFrameInfo;
+        ::Codec::OnGetRepetitionCount onGetRepetitionCount;
+    };
+
+    RustCodec(const Param& param, std::unique_ptr<SkStream> stream)
+    : SkCodec(encodedInfo(param), skcms_PixelFormat_RGBA_8888, std::move(stream), param.origin)
+    , _param(param)
+    , _frameHolder(
+        SkISize::Make(param.width, param.height),
+        param.onGetFrameCount(param.trait),
+        param.opaque)
+    { }
+
+    ~RustCodec() override {
+        _param.drop(_param.trait);
+    }
+
+protected:
+    SkEncodedImageFormat onGetEncodedFormat() const override {
+        return _param.encodedFormat;
+    }
+
+    // The Rust codec decodes to unpremultiplied RGBA 8888 in sRGB, which is then converted to the
+    // requested destination.
+    Result onGetPixels(
+        const SkImageInfo& dstInfo, void* dst, size_t rowBytes, const Options& options,
+        int* rowsDecoded) override {
+        auto srcInfo = SkImageInfo::Make(
+            this->dimensions(),
+            kRGBA_8888_SkColorType,
+            _param.opaque ? kOpaque_SkAlphaType : kUnpremul_SkAlphaType,
+            SkColorSpace::MakeSRGB());
+        SkBitmap bitmap;
+        if (!bitmap.tryAllocPixels(srcInfo)) {
+            return kInternalError;
+        }
+        auto result = _param.onGetPixels(
+            _param.trait, options.fFrameIndex, bitmap.getPixels(), bitmap.rowBytes());
+        if (result != kSuccess && result != kIncompleteInput) {
+            return result;
+        }
+        if (!bitmap.pixmap().readPixels(dstInfo, dst, rowBytes)) {
+            return kInvalidConversion;
+        }
+        *rowsDecoded = dstInfo.height();
+        return result;
+    }
+
+    int onGetFrameCount() override {
+        return _param.onGetFrameCount(_param.trait);
+    }
+
+    bool onGetFrameInfo(int index, FrameInfo* info) const override {
+        if (!_param.onGetFrameInfo(_param.trait, index, info)) {
+            return false;
+        }
+        info->fRequiredFrame = kNoFrame;
+        return true;
+    }
+
+    int onGetRepetitionCount() override {
+        return _param.onGetRepetitionCount(_param.trait);
+    }
+
+    const SkFrameHolder* getFrameHolder() const override {
+        return &_frameHolder;
+    }
+
+private:
+    static SkEncodedInfo encodedInfo(const Param& param) {
+        return param.opaque
+            ? SkEncodedInfo::Make(param.width, param.height, SkEncodedInfo::kRGB_Color, SkEncodedInfo::kOpaque_Alpha, 8)
+            : SkEncodedInfo::Make(param.width, param.height, SkEncodedInfo::kRGBA_Color, SkEncodedInfo::kUnpremul_Alpha, 8);
+    }
+
+    Param _param;
+    RustFrameHolder _frameHolder;
+};
+
+extern "C" SkCodec* C_RustCodec_New(const RustCodec::Param* param) {
+    return new RustCodec(*param, std::make_unique<SkMemoryStream>());
+}
+
+namespace Decoder {
+    extern "C" {
+        typedef bool (*IsFormat)(const void* data, size_t length);
+        typedef SkCodec::Result (*MakeCodec)(const void* data, size_t length, RustCodec::Param* param);
+    }
+}
+
+static Decoder::IsFormat rustDecoderIsFormat = nullptr;
+static Decoder::MakeCodec rustDecoderMakeCodec = nullptr;
+
+// All decoders that are implemented in Rust are registered as one decoder. The Rust side picks the
+// decoder by testing the data with the `is_format()` functions in registration order.
+extern "C" void C_SkCodecs_RegisterRust(Decoder::IsFormat isFormat, Decoder::MakeCodec makeCodec) {
+    rustDecoderIsFormat = isFormat;
+    rustDecoderMakeCodec = makeCodec;
+    SkCodecs::Register({
+        "rust",
+        [](const void* data, size_t length) {
+            return rustDecoderIsFormat(data, length);
+        },
+        [](std::unique_ptr<SkStream> stream, SkCodec::Result* result, SkCodecs::DecodeContext) -> std::unique_ptr<SkCodec> {
+            SkDynamicMemoryWStream buffer;
+            char chunk[4096];
+            while (auto read = stream->read(chunk, sizeof(chunk))) {
+                buffer.write(chunk, read);
+            }
+            auto data = buffer.detachAsData();
+            RustCodec::Param param;
+            *result = rustDecoderMakeCodec(data->data(), data->size(), &param);
+            if (*result != SkCodec::kSuccess) {
+                return nullptr;
+            }
+            return std::make_unique<RustCodec>(param, std::make_unique<SkMemoryStream>(data));
+        }
+    });
+}
+
 //
 // codec/SkEncodedOrigin.h
 //
@@ -487,6 +688,120 @@ extern "C" SkImage* C_SkImage_reinterpretColorSpace(const SkImage* self, SkColor
     return self->reinterpretColorSpace(sp(newColorSpace)).release();
 }
 
+extern "C" int C_SkImage_AsyncReadResult_count(const SkImage::AsyncReadResult* self) {
+    return self->count();
+}
+
+extern "C" const void* C_SkImage_AsyncReadResult_data(const SkImage::AsyncReadResult* self, int i) {
+    return self->data(i);
+}
+
+extern "C" size_t C_SkImage_AsyncReadResult_rowBytes(const SkImage::AsyncReadResult* self, int i) {
+    return self->rowBytes(i);
+}
+
+extern "C" void C_SkImage_AsyncReadResult_delete(const SkImage::AsyncReadResult* self) {
+    delete self;
+}
+
+namespace ReadPixels {
+    extern "C" {
+        typedef void (*Callback)(void* context, const SkImage::AsyncReadResult* result);
+    }
+
+    struct Context {
+        Callback callback;
+        void* context;
+    };
+
+    // Forwards the result to Rust, transferring its ownership, and frees the context.
+    void callback(SkImage::ReadPixelsContext context, std::unique_ptr<const SkImage::AsyncReadResult> result) {
+        auto rustContext = static_cast<Context*>(context);
+        rustContext->callback(rustContext->context, result.release());
+        delete rustContext;
+    }
+}
+
+extern "C" void C_SkImage_asyncRescaleAndReadPixels(
+        const SkImage* self,
+        const SkImageInfo* info,
+        const SkIRect* srcRect,
+        SkImage::RescaleGamma rescaleGamma,
+        SkImage::RescaleMode rescaleMode,
+        ReadPixels::Callback callback, void* context) {
+    self->asyncRescaleAndReadPixels(
+            *info, *srcRect, rescaleGamma, rescaleMode,
+            ReadPixels::callback, new ReadPixels::Context{callback, context});
+}
+
+extern "C" void C_SkImage_asyncRescaleAndReadPixelsYUV420(
+        const SkImage* self,
+        SkYUVColorSpace yuvColorSpace,
+        SkColorSpace* dstColorSpace,
+        const SkIRect* srcRect,
+        const SkISize* dstSize,
+        SkImage::RescaleGamma rescaleGamma,
+        SkImage::RescaleMode rescaleMode,
+        ReadPixels::Callback callback, void* context) {
+    self->asyncRescaleAndReadPixelsYUV420(
+            yuvColorSpace, sp(dstColorSpace), *srcRect, *dstSize, rescaleGamma, rescaleMode,
+            ReadPixels::callback, new ReadPixels::Context{callback, context});
+}
+
+extern "C" void C_SkImage_asyncRescaleAndReadPixelsYUVA420(
+        const SkImage* self,
+        SkYUVColorSpace yuvColorSpace,
+        SkColorSpace* dstColorSpace,
+        const SkIRect* srcRect,
+        const SkISize* dstSize,
+        SkImage::RescaleGamma rescaleGamma,
+        SkImage::RescaleMode rescaleMode,
+        ReadPixels::Callback callback, void* context) {
+    self->asyncRescaleAndReadPixelsYUVA420(
+            yuvColorSpace, sp(dstColorSpace), *srcRect, *dstSize, rescaleGamma, rescaleMode,
+            ReadPixels::callback, new ReadPixels::Context{callback, context});
+}
+
+extern "C" void C_SkSurface_asyncRescaleAndReadPixels(
+        SkSurface* self,
+        const SkImageInfo* info,
+        const SkIRect* srcRect,
+        SkImage::RescaleGamma rescaleGamma,
+        SkImage::RescaleMode rescaleMode,
+        ReadPixels::Callback callback, void* context) {
+    self->asyncRescaleAndReadPixels(
+            *info, *srcRect, rescaleGamma, rescaleMode,
+            ReadPixels::callback, new ReadPixels::Context{callback, context});
+}
+
+extern "C" void C_SkSurface_asyncRescaleAndReadPixelsYUV420(
+        SkSurface* self,
+        SkYUVColorSpace yuvColorSpace,
+        SkColorSpace* dstColorSpace,
+        const SkIRect* srcRect,
+        const SkISize* dstSize,
+        SkImage::RescaleGamma rescaleGamma,
+        SkImage::RescaleMode rescaleMode,
+        ReadPixels::Callback callback, void* context) {
+    self->asyncRescaleAndReadPixelsYUV420(
+            yuvColorSpace, sp(dstColorSpace), *srcRect, *dstSize, rescaleGamma, rescaleMode,
+            ReadPixels::callback, new ReadPixels::Context{callback, context});
+}
+
+extern "C" void C_SkSurface_asyncRescaleAndReadPixelsYUVA420(
+        SkSurface* self,
+        SkYUVColorSpace yuvColorSpace,
+        SkColorSpace* dstColorSpace,
+        const SkIRect* srcRect,
+        const SkISize* dstSize,
+        SkImage::RescaleGamma rescaleGamma,
+        SkImage::RescaleMode rescaleMode,
+        ReadPixels::Callback callback, void* context) {
+    self->asyncRescaleAndReadPixelsYUVA420(
+            yuvColorSpace, sp(dstColorSpace), *srcRect, *dstSize, rescaleGamma, rescaleMode,
+            ReadPixels::callback, new ReadPixels::Context{callback, context});
+}
+
 //
 // core/SkData.h
 //
@@ -848,6 +1163,10 @@ extern "C" void C_SkCanvas_discard(SkCanvas* self) {
     self->discard();
 }
 
+extern "C" void C_SkCanvas_drawMesh(SkCanvas* self, const SkMesh* mesh, SkBlender* blender, const SkPaint* paint) {
+    self->drawMesh(*mesh, sp(blender), *paint);
+}
+
 //
 // core/SkAutoCanvasRestore.h
 //
@@ -1145,6 +1464,134 @@ extern "C" SkImage* C_SkBitmap_asImage(const SkBitmap* self) {
     return self->asImage().release();
 }
 
+//
+// core/SkSerialProcs.h
+//
+
+namespace SerialProcs {
+    extern "C" {
+        typedef SkData* (*PictureProc)(void* context, SkPicture* picture);
+        typedef SkData* (*ImageProc)(void* context, SkImage* image);
+        typedef SkData* (*TypefaceProc)(void* context, SkTypeface* typeface);
+    }
+}
+
+struct RustSerialProcs {
+    void* context;
+    ::SerialProcs::PictureProc pictureProc;
+    ::SerialProcs::ImageProc imageProc;
+    ::SerialProcs::TypefaceProc typefaceProc;
+};
+
+namespace DeserialProcs {
+    extern "C" {
+        typedef SkPicture* (*PictureProc)(void* context, const void* data, size_t length);
+        typedef SkImage* (*ImageProc)(void* context, const void* data, size_t length);
+        typedef SkTypeface* (*TypefaceProc)(void* context, const void* data, size_t length);
+    }
+}
+
+struct RustDeserialProcs {
+    void* context;
+    ::DeserialProcs::PictureProc pictureProc;
+    ::DeserialProcs::ImageProc imageProc;
+    ::DeserialProcs::TypefaceProc typefaceProc;
+};
+
+// A picture writes the typefaces returned by a typeface proc into its stream without their length,
+// so they are framed with a marker and their length to be able to read them back:
+//
+//   marker: u8[4] = "RSTF", length: u32, data: u8[length]
+//
+// The typefaces Skia serializes are left unframed. They start with the packed style bits of their
+// font descriptor, whose first byte is either 0xFE, 0xFF, or below 3, and so never matches the
+// marker.
+namespace TypefaceFrame {
+    const uint8_t Marker[4] = {'R', 'S', 'T', 'F'};
+}
+
+static SkSerialProcs serialProcs(const RustSerialProcs* procs) {
+    SkSerialProcs r;
+    auto ctx = const_cast<RustSerialProcs*>(procs);
+    if (procs->pictureProc) {
+        r.fPictureProc = [](SkPicture* picture, void* ctx) -> sk_sp<SkData> {
+            auto procs = static_cast<RustSerialProcs*>(ctx);
+            return sp(procs->pictureProc(procs->context, picture));
+        };
+        r.fPictureCtx = ctx;
+    }
+    if (procs->imageProc) {
+        r.fImageProc = [](SkImage* image, void* ctx) -> sk_sp<SkData> {
+            auto procs = static_cast<RustSerialProcs*>(ctx);
+            return sp(procs->imageProc(procs->context, image));
+        };
+        r.fImageCtx = ctx;
+    }
+    if (procs->typefaceProc) {
+        r.fTypefaceProc = [](SkTypeface* typeface, void* ctx) -> sk_sp<SkData> {
+            auto procs = static_cast<RustSerialProcs*>(ctx);
+            auto data = sp(procs->typefaceProc(procs->context, typeface));
+            if (!data) {
+                return nullptr;
+            }
+            SkDynamicMemoryWStream stream;
+            stream.write(TypefaceFrame::Marker, sizeof(TypefaceFrame::Marker));
+            stream.write32(SkToU32(data->size()));
+            stream.write(data->data(), data->size());
+            return stream.detachAsData();
+        };
+        r.fTypefaceCtx = ctx;
+    }
+    return r;
+}
+
+static sk_sp<SkTypeface> readTypeface(SkStream& stream, const RustDeserialProcs* procs) {
+    uint8_t marker[sizeof(TypefaceFrame::Marker)];
+    if (stream.peek(marker, sizeof(marker)) != sizeof(marker)
+        || memcmp(marker, TypefaceFrame::Marker, sizeof(marker)) != 0) {
+        return SkTypeface::MakeDeserialize(&stream, nullptr);
+    }
+    uint32_t size;
+    if (!stream.skip(sizeof(marker)) || !stream.readU32(&size)) {
+        return nullptr;
+    }
+    auto data = SkData::MakeUninitialized(size);
+    if (stream.read(data->writable_data(), size) != size) {
+        return nullptr;
+    }
+    return sp(procs->typefaceProc(procs->context, data->data(), data->size()));
+}
+
+static SkDeserialProcs deserialProcs(const RustDeserialProcs* procs) {
+    SkDeserialProcs r;
+    auto ctx = const_cast<RustDeserialProcs*>(procs);
+    if (procs->pictureProc) {
+        r.fPictureProc = [](const void* data, size_t length, void* ctx) -> sk_sp<SkPicture> {
+            auto procs = static_cast<RustDeserialProcs*>(ctx);
+            return sp(procs->pictureProc(procs->context, data, length));
+        };
+        r.fPictureCtx = ctx;
+    }
+    if (procs->imageProc) {
+        r.fImageProc = [](const void* data, size_t length, void* ctx) -> sk_sp<SkImage> {
+            auto procs = static_cast<RustDeserialProcs*>(ctx);
+            return sp(procs->imageProc(procs->context, data, length));
+        };
+        r.fImageCtx = ctx;
+    }
+    if (procs->typefaceProc) {
+        r.fTypefaceProc = [](const void* data, size_t length, void* ctx) -> sk_sp<SkTypeface> {
+            SkMemoryStream stream(data, length, false);
+            return readTypeface(stream, static_cast<RustDeserialProcs*>(ctx));
+        };
+        r.fTypefaceStreamProc = [](SkStream& stream, void* ctx) -> sk_sp<SkTypeface> {
+            return readTypeface(stream, static_cast<RustDeserialProcs*>(ctx));
+        };
+        r.fTypefaceCtx = ctx;
+    }
+    return r;
+}
+
 //
 // core/SkPicture.h
 //
@@ -1157,10 +1604,20 @@ extern "C" SkPicture* C_SkPicture_MakeFromData2(const void* data, size_t size) {
     return SkPicture::MakeFromData(data, size).release();
 }
 
+extern "C" SkPicture* C_SkPicture_MakeFromDataWithProcs(const SkData* data, const RustDeserialProcs* procs) {
+    auto deserial = deserialProcs(procs);
+    return SkPicture::MakeFromData(data, &deserial).release();
+}
+
 extern "C" SkData* C_SkPicture_serialize(const SkPicture* self) {
     return self->serialize().release();
 }
 
+extern "C" SkData* C_SkPicture_serializeWithProcs(const SkPicture* self, const RustSerialProcs* procs) {
+    auto serial = serialProcs(procs);
+    return self->serialize(&serial).release();
+}
+
 extern "C" SkPicture* C_SkPicture_MakePlaceholder(const SkRect& cull) {
     return SkPicture::MakePlaceholder(cull).release();
 }
@@ -1560,6 +2017,152 @@ extern "C" SkTypeface* C_SkFontMgr_legacyMakeTypeface(const SkFontMgr* self, con
     return self->legacyMakeTypeface(familyName, style).release();
 }
 
+//
+// Font style sets and font managers implemented in Rust.
+//
+
+// A style set of a fixed list of typefaces.
+class TypefaceStyleSet final : public SkFontStyleSet {
+public:
+    explicit TypefaceStyleSet(std::vector<sk_sp<SkTypeface>> typefaces)
+    : _typefaces(std::move(typefaces))
+    { }
+
+    int count() override {
+        return static_cast<int>(_typefaces.size());
+    }
+
+    void getStyle(int index, SkFontStyle* style, SkString* name) override {
+        if (style) {
+            *style = _typefaces[index]->fontStyle();
+        }
+        if (name) {
+            name->reset();
+        }
+    }
+
+    sk_sp<SkTypeface> createTypeface(int index) override {
+        return _typefaces[index];
+    }
+
+    sk_sp<SkTypeface> matchStyle(const SkFontStyle& pattern) override {
+        return this->matchStyleCSS3(pattern);
+    }
+
+private:
+    std::vector<sk_sp<SkTypeface>> _typefaces;
+};
+
+// Takes ownership of the typefaces.
+extern "C" SkFontStyleSet* C_SkFontStyleSet_MakeFromTypefaces(SkTypeface** typefaces, size_t count) {
+    std::vector<sk_sp<SkTypeface>> set;
+    for (size_t i = 0; i < count; ++i) {
+        set.push_back(sp(typefaces[i]));
+    }
+    return new TypefaceStyleSet(std::move(set));
+}
+
+namespace FontMgr {
+    extern "C" {
+        typedef void (*Drop)(TraitObject);
+        typedef int (*CountFamilies)(TraitObject);
+        typedef void (*GetFamilyName)(TraitObject, int index, SkString* familyName);
+        typedef SkFontStyleSet* (*CreateStyleSet)(TraitObject, int index);
+        typedef SkFontStyleSet* (*MatchFamily)(TraitObject, const char familyName[]);
+        typedef SkTypeface* (*MatchFamilyStyle)(TraitObject, const char familyName[], const SkFontStyle*);
+        typedef SkTypeface* (*MatchFamilyStyleCharacter)(
+            TraitObject, const char familyName[], const SkFontStyle*,
+            const char* bcp47[], int bcp47Count, SkUnichar character);
+        typedef SkTypeface* (*MakeFromData)(TraitObject, SkData* data, int ttcIndex);
+        typedef SkTypeface* (*LegacyMakeTypeface)(TraitObject, const char familyName[], const SkFontStyle*);
+    }
+}
+
+class RustFontMgr final : public SkFontMgr {
+public:
+    struct Param {
+        TraitObject trait;
+        ::FontMgr::Drop drop;
+        ::FontMgr::CountFamilies countFamilies;
+        ::FontMgr::GetFamilyName getFamilyName;
+        ::FontMgr::CreateStyleSet createStyleSet;
+        ::FontMgr::MatchFamily matchFamily;
+        ::FontMgr::MatchFamilyStyle matchFamilyStyle;
+        ::FontMgr::MatchFamilyStyleCharacter matchFamilyStyleCharacter;
+        ::FontMgr::MakeFromData makeFromData;
+        ::FontMgr::LegacyMakeTypeface legacyMakeTypeface;
+    };
+
+    explicit RustFontMgr(const Param& param)
+    : _param(param)
+    { }
+
+    ~RustFontMgr() override {
+        _param.drop(_param.trait);
+    }
+
+protected:
+    int onCountFamilies() const override {
+        return _param.countFamilies(_param.trait);
+    }
+
+    void onGetFamilyName(int index, SkString* familyName) const override {
+        _param.getFamilyName(_param.trait, index, familyName);
+    }
+
+    sk_sp<SkFontStyleSet> onCreateStyleSet(int index) const override {
+        return sp(_param.createStyleSet(_param.trait, index));
+    }
+
+    sk_sp<SkFontStyleSet> onMatchFamily(const char familyName[]) const override {
+        return sp(_param.matchFamily(_param.trait, familyName));
+    }
+
+    sk_sp<SkTypeface> onMatchFamilyStyle(const char familyName[], const SkFontStyle& style) const override {
+        return sp(_param.matchFamilyStyle(_param.trait, familyName, &style));
+    }
+
+    sk_sp<SkTypeface> onMatchFamilyStyleCharacter(
+        const char familyName[], const SkFontStyle& style,
+        const char* bcp47[], int bcp47Count,
+        SkUnichar character) const override {
+        return sp(_param.matchFamilyStyleCharacter(
+            _param.trait, familyName, &style, bcp47, bcp47Count, character));
+    }
+
+    sk_sp<SkTypeface> onMakeFromData(sk_sp<SkData> data, int ttcIndex) const override {
+        return sp(_param.makeFromData(_param.trait, data.release(), ttcIndex));
+    }
+
+    // Streams and files are read into memory and passed to `makeFromData()`.
+
+    sk_sp<SkTypeface> onMakeFromStreamIndex(std::unique_ptr<SkStreamAsset> stream, int ttcIndex) const override {
+        auto data = SkData::MakeFromStream(stream.get(), stream->getLength());
+        return data ? this->onMakeFromData(std::move(data), ttcIndex) : nullptr;
+    }
+
+    // Variation and palette arguments are ignored.
+    sk_sp<SkTypeface> onMakeFromStreamArgs(std::unique_ptr<SkStreamAsset> stream, const SkFontArguments& args) const override {
+        return this->onMakeFromStreamIndex(std::move(stream), args.getCollectionIndex());
+    }
+
+    sk_sp<SkTypeface> onMakeFromFile(const char path[], int ttcIndex) const override {
+        auto data = SkData::MakeFromFileName(path);
+        return data ? this->onMakeFromData(std::move(data), ttcIndex) : nullptr;
+    }
+
+    sk_sp<SkTypeface> onLegacyMakeTypeface(const char familyName[], SkFontStyle style) const override {
+        return sp(_param.legacyMakeTypeface(_param.trait, familyName, &style));
+    }
+
+private:
+    Param _param;
+};
+
+extern "C" SkFontMgr* C_RustFontMgr_New(const RustFontMgr::Param* param) {
+    return new RustFontMgr(*param);
+}
+
 // From skia/tools/FontToolUtils.cpp
 
 #if defined(SK_BUILD_FOR_WIN) && (defined(SK_FONTMGR_GDI_AVAILABLE) || defined(SK_FONTMGR_DIRECTWRITE_AVAILABLE))
@@ -1586,6 +2189,10 @@ extern "C" SkTypeface* C_SkFontMgr_legacyMakeTypeface(const SkFontMgr* self, con
 #include "include/ports/SkFontMgr_empty.h"
 #endif
 
+#if defined(SK_FONTMGR_FREETYPE_EMBEDDED_AVAILABLE)
+#include "include/ports/SkFontMgr_data.h"
+#endif
+
 #ifndef SK_FONT_FILE_PREFIX
 #  if defined(SK_BUILD_FOR_MAC) || defined(SK_BUILD_FOR_IOS)
 #    define SK_FONT_FILE_PREFIX "/System/Library/Fonts/"
@@ -1624,6 +2231,37 @@ extern "C" SkFontMgr* C_SkFontMgr_RefEmpty() {
     return SkFontMgr::RefEmpty().release();
 }
 
+// The custom font managers return `nullptr` if they are not available on the target platform.
+
+extern "C" SkFontMgr* C_SkFontMgr_NewCustomDirectory(const char* dir) {
+#if defined(SK_FONTMGR_FREETYPE_DIRECTORY_AVAILABLE)
+    return SkFontMgr_New_Custom_Directory(dir).release();
+#else
+    return nullptr;
+#endif
+}
+
+// Takes ownership of the data.
+extern "C" SkFontMgr* C_SkFontMgr_NewCustomData(SkData** data, size_t count) {
+    std::vector<sk_sp<SkData>> fonts;
+    for (size_t i = 0; i < count; ++i) {
+        fonts.push_back(sp(data[i]));
+    }
+#if defined(SK_FONTMGR_FREETYPE_EMBEDDED_AVAILABLE)
+    return SkFontMgr_New_Custom_Data(SkSpan<sk_sp<SkData>>(fonts.data(), fonts.size())).release();
+#else
+    return nullptr;
+#endif
+}
+
+extern "C" SkFontMgr* C_SkFontMgr_NewCustomEmpty() {
+#if defined(SK_FONTMGR_FREETYPE_EMPTY_AVAILABLE)
+    return SkFontMgr_New_Custom_Empty().release();
+#else
+    return nullptr;
+#endif
+}
+
 //
 // core/SkFontParameters.h
 //
@@ -1662,6 +2300,228 @@ extern "C" SkVertices* C_SkVertices_MakeCopy(
     return SkVertices::MakeCopy(mode, vertexCount, positions, texs, colors, indexCount, indices).release();
 }
 
+//
+// core/SkMesh.h
+//
+
+extern "C" void C_SkMeshSpecification_Attribute_Construct(
+        SkMeshSpecification::Attribute* uninitialized,
+        SkMeshSpecification::Attribute::Type type, size_t offset, const SkString* name) {
+    new (uninitialized) SkMeshSpecification::Attribute{type, offset, *name};
+}
+
+extern "C" void C_SkMeshSpecification_Attribute_destruct(SkMeshSpecification::Attribute* self) {
+    self->~Attribute();
+}
+
+extern "C" void C_SkMeshSpecification_Varying_Construct(
+        SkMeshSpecification::Varying* uninitialized,
+        SkMeshSpecification::Varying::Type type, const SkString* name) {
+    new (uninitialized) SkMeshSpecification::Varying{type, *name};
+}
+
+extern "C" void C_SkMeshSpecification_Varying_destruct(SkMeshSpecification::Varying* self) {
+    self->~Varying();
+}
+
+extern "C" void C_SkMeshSpecification_ref(const SkMeshSpecification* self) {
+    self->ref();
+}
+
+extern "C" void C_SkMeshSpecification_unref(const SkMeshSpecification* self) {
+    self->unref();
+}
+
+extern "C" bool C_SkMeshSpecification_unique(const SkMeshSpecification* self) {
+    return self->unique();
+}
+
+extern "C" SkMeshSpecification* C_SkMeshSpecification_Make(
+        const SkMeshSpecification::Attribute* attributes, size_t attributeCount,
+        size_t vertexStride,
+        const SkMeshSpecification::Varying* varyings, size_t varyingCount,
+        const SkString* vs, const SkString* fs,
+        SkColorSpace* cs, SkAlphaType at,
+        SkString* error) {
+    auto r = SkMeshSpecification::Make(
+            SkSpan(attributes, attributeCount),
+            vertexStride,
+            SkSpan(varyings, varyingCount),
+            *vs, *fs, sp(cs), at);
+    *error = r.error;
+    return r.specification.release();
+}
+
+extern "C" const SkMeshSpecification::Attribute* C_SkMeshSpecification_attributes(const SkMeshSpecification* self, size_t* count) {
+    auto attributes = self->attributes();
+    *count = attributes.size();
+    return attributes.begin();
+}
+
+extern "C" size_t C_SkMeshSpecification_uniformSize(const SkMeshSpecification* self) {
+    return self->uniformSize();
+}
+
+extern "C" const SkRuntimeEffect::Uniform* C_SkMeshSpecification_uniforms(const SkMeshSpecification* self, size_t* count) {
+    auto uniforms = self->uniforms();
+    *count = uniforms.size();
+    return uniforms.begin();
+}
+
+extern "C" const SkRuntimeEffect::Child* C_SkMeshSpecification_children(const SkMeshSpecification* self, size_t* count) {
+    auto children = self->children();
+    *count = children.size();
+    return children.begin();
+}
+
+extern "C" const SkRuntimeEffect::Uniform* C_SkMeshSpecification_findUniform(
+        const SkMeshSpecification* self, const char* name, size_t count) {
+    return self->findUniform(std::string_view(name, count));
+}
+
+extern "C" const SkRuntimeEffect::Child* C_SkMeshSpecification_findChild(
+        const SkMeshSpecification* self, const char* name, size_t count) {
+    return self->findChild(std::string_view(name, count));
+}
+
+extern "C" const SkMeshSpecification::Attribute* C_SkMeshSpecification_findAttribute(
+        const SkMeshSpecification* self, const char* name, size_t count) {
+    return self->findAttribute(std::string_view(name, count));
+}
+
+extern "C" const SkMeshSpecification::Varying* C_SkMeshSpecification_findVarying(
+        const SkMeshSpecification* self, const char* name, size_t count) {
+    return self->findVarying(std::string_view(name, count));
+}
+
+extern "C" size_t C_SkMeshSpecification_stride(const SkMeshSpecification* self) {
+    return self->stride();
+}
+
+extern "C" SkColorSpace* C_SkMeshSpecification_colorSpace(const SkMeshSpecification* self) {
+    return self->colorSpace();
+}
+
+extern "C" size_t C_SkMesh_VertexBuffer_size(const SkMesh::VertexBuffer* self) {
+    return self->size();
+}
+
+extern "C" size_t C_SkMesh_IndexBuffer_size(const SkMesh::IndexBuffer* self) {
+    return self->size();
+}
+
+extern "C" SkMesh::VertexBuffer* C_SkMeshes_MakeVertexBuffer(const void* data, size_t size) {
+    return SkMeshes::MakeVertexBuffer(data, size).release();
+}
+
+extern "C" SkMesh::VertexBuffer* C_SkMeshes_CopyVertexBuffer(SkMesh::VertexBuffer* buffer) {
+    return SkMeshes::CopyVertexBuffer(sk_ref_sp(buffer)).release();
+}
+
+extern "C" SkMesh::IndexBuffer* C_SkMeshes_MakeIndexBuffer(const void* data, size_t size) {
+    return SkMeshes::MakeIndexBuffer(data, size).release();
+}
+
+extern "C" SkMesh::IndexBuffer* C_SkMeshes_CopyIndexBuffer(SkMesh::IndexBuffer* buffer) {
+    return SkMeshes::CopyIndexBuffer(sk_ref_sp(buffer)).release();
+}
+
+static SkMesh* meshOrError(SkMesh::Result&& r, SkString* error) {
+    if (!r.mesh.isValid()) {
+        *error = r.error;
+        return nullptr;
+    }
+    return new SkMesh(std::move(r.mesh));
+}
+
+extern "C" SkMesh* C_SkMesh_Make(
+        SkMeshSpecification* spec, SkMesh::Mode mode,
+        SkMesh::VertexBuffer* vertexBuffer, size_t vertexCount, size_t vertexOffset,
+        SkData* uniforms,
+        SkRuntimeEffect::ChildPtr* children, size_t childCount,
+        const SkRect* bounds,
+        SkString* error) {
+    return meshOrError(
+            SkMesh::Make(
+                    sp(spec), mode,
+                    sp(vertexBuffer), vertexCount, vertexOffset,
+                    sp(uniforms),
+                    SkSpan(children, childCount),
+                    *bounds),
+            error);
+}
+
+extern "C" SkMesh* C_SkMesh_MakeIndexed(
+        SkMeshSpecification* spec, SkMesh::Mode mode,
+        SkMesh::VertexBuffer* vertexBuffer, size_t vertexCount, size_t vertexOffset,
+        SkMesh::IndexBuffer* indexBuffer, size_t indexCount, size_t indexOffset,
+        SkData* uniforms,
+        SkRuntimeEffect::ChildPtr* children, size_t childCount,
+        const SkRect* bounds,
+        SkString* error) {
+    return meshOrError(
+            SkMesh::MakeIndexed(
+                    sp(spec), mode,
+                    sp(vertexBuffer), vertexCount, vertexOffset,
+                    sp(indexBuffer), indexCount, indexOffset,
+                    sp(uniforms),
+                    SkSpan(children, childCount),
+                    *bounds),
+            error);
+}
+
+extern "C" SkMesh* C_SkMesh_clone(const SkMesh* self) {
+    return new SkMesh(*self);
+}
+
+extern "C" void C_SkMesh_delete(SkMesh* self) {
+    delete self;
+}
+
+extern "C" SkMeshSpecification* C_SkMesh_refSpec(const SkMesh* self) {
+    return self->refSpec().release();
+}
+
+extern "C" SkMesh::Mode C_SkMesh_mode(const SkMesh* self) {
+    return self->mode();
+}
+
+extern "C" SkMesh::VertexBuffer* C_SkMesh_refVertexBuffer(const SkMesh* self) {
+    return self->refVertexBuffer().release();
+}
+
+extern "C" size_t C_SkMesh_vertexOffset(const SkMesh* self) {
+    return self->vertexOffset();
+}
+
+extern "C" size_t C_SkMesh_vertexCount(const SkMesh* self) {
+    return self->vertexCount();
+}
+
+extern "C" SkMesh::IndexBuffer* C_SkMesh_refIndexBuffer(const SkMesh* self) {
+    return self->refIndexBuffer().release();
+}
+
+extern "C" size_t C_SkMesh_indexOffset(const SkMesh* self) {
+    return self->indexOffset();
+}
+
+extern "C" size_t C_SkMesh_indexCount(const SkMesh* self) {
+    return self->indexCount();
+}
+
+extern "C" SkData* C_SkMesh_refUniforms(const SkMesh* self) {
+    return const_cast<SkData*>(self->refUniforms().release());
+}
+
+extern "C" void C_SkMesh_bounds(const SkMesh* self, SkRect* bounds) {
+    *bounds = self->bounds();
+}
+
+extern "C" bool C_SkMesh_isValid(const SkMesh* self) {
+    return self->isValid();
+}
+
 //
 // SkVertices::Builder
 //
@@ -1938,6 +2798,146 @@ extern "C" void C_SkDrawable_getBounds(SkDrawable* self, SkRect* result) {
     *result = self->getBounds();
 }
 
+namespace Drawable {
+    extern "C" {
+        typedef void (*Drop)(TraitObject);
+        typedef void (*OnDraw)(TraitObject, SkCanvas*);
+        typedef void (*OnGetBounds)(TraitObject, SkRect*);
+        typedef size_t (*OnApproximateBytesUsed)(TraitObject);
+        typedef SkPicture* (*OnMakePictureSnapshot)(TraitObject);
+    }
+}
+
+class RustDrawable final : public SkDrawable {
+public:
+    struct Param {
+        TraitObject trait;
+        ::Drawable::Drop drop;
+        ::Drawable::OnDraw onDraw;
+        ::Drawable::OnGetBounds onGetBounds;
+        ::Drawable::OnApproximateBytesUsed onApproximateBytesUsed;
+        ::Drawable::OnMakePictureSnapshot onMakePictureSnapshot;
+    };
+
+    explicit RustDrawable(const Param& param)
+    : _param(param)
+    { }
+
+    ~RustDrawable() override {
+        _param.drop(_param.trait);
+    }
+
+protected:
+    void onDraw(SkCanvas* canvas) override {
+        _param.onDraw(_param.trait, canvas);
+    }
+
+    SkRect onGetBounds() override {
+        SkRect bounds;
+        _param.onGetBounds(_param.trait, &bounds);
+        return bounds;
+    }
+
+    size_t onApproximateBytesUsed() override {
+        return _param.onApproximateBytesUsed(_param.trait);
+    }
+
+    // A Rust implementation may return `nullptr` to fall back to recording `onDraw()`.
+    sk_sp<SkPicture> onMakePictureSnapshot() override {
+        auto picture = sp(_param.onMakePictureSnapshot(_param.trait));
+        if (picture) {
+            return picture;
+        }
+        return SkDrawable::onMakePictureSnapshot();
+    }
+
+private:
+    Param _param;
+};
+
+extern "C" RustDrawable* C_RustDrawable_New(const RustDrawable::Param* param) {
+    return new RustDrawable(*param);
+}
+
+//
+// core/SkOpenTypeSVGDecoder.h, core/SkGraphics.h
+//
+
+namespace OpenTypeSVGDecoder {
+    extern "C" {
+        typedef void (*Drop)(TraitObject);
+        typedef size_t (*ApproximateSize)(TraitObject);
+        typedef bool (*Render)(TraitObject, SkCanvas*, int upem, SkGlyphID glyphId, SkColor foregroundColor, const SkColor* palette, size_t paletteCount);
+        // Returns nullptr if the SVG document can not be decoded.
+        typedef SkOpenTypeSVGDecoder* (*Factory)(const uint8_t* svg, size_t length);
+    }
+}
+
+class RustOpenTypeSVGDecoder final : public SkOpenTypeSVGDecoder {
+public:
+    struct Param {
+        TraitObject trait;
+        ::OpenTypeSVGDecoder::Drop drop;
+        ::OpenTypeSVGDecoder::ApproximateSize approximateSize;
+        ::OpenTypeSVGDecoder::Render render;
+    };
+
+    explicit RustOpenTypeSVGDecoder(const Param& param)
+    : _param(param)
+    { }
+
+    ~RustOpenTypeSVGDecoder() override {
+        _param.drop(_param.trait);
+    }
+
+    size_t approximateSize() override {
+        return _param.approximateSize(_param.trait);
+    }
+
+    bool render(SkCanvas& canvas, int upem, SkGlyphID glyphId, SkColor foregroundColor, SkSpan<SkColor> palette) override {
+        return _param.render(_param.trait, &canvas, upem, glyphId, foregroundColor, palette.data(), palette.size());
+    }
+
+private:
+    Param _param;
+};
+
+extern "C" SkOpenTypeSVGDecoder* C_RustOpenTypeSVGDecoder_New(const RustOpenTypeSVGDecoder::Param* param) {
+    return new RustOpenTypeSVGDecoder(*param);
+}
+
+extern "C" void C_SkOpenTypeSVGDecoder_delete(SkOpenTypeSVGDecoder* self) {
+    delete self;
+}
+
+extern "C" size_t C_SkOpenTypeSVGDecoder_approximateSize(SkOpenTypeSVGDecoder* self) {
+    return self->approximateSize();
+}
+
+extern "C" bool C_SkOpenTypeSVGDecoder_render(SkOpenTypeSVGDecoder* self, SkCanvas* canvas, int upem, SkGlyphID glyphId, SkColor foregroundColor, const SkColor* palette, size_t paletteCount) {
+    return self->render(*canvas, upem, glyphId, foregroundColor, SkSpan<SkColor>(const_cast<SkColor*>(palette), paletteCount));
+}
+
+namespace {
+    // SkGraphics takes a plain function pointer, so the factory from Rust is forwarded from here.
+    std::atomic<OpenTypeSVGDecoder::Factory> openTypeSVGDecoderFactory(nullptr);
+
+    std::unique_ptr<SkOpenTypeSVGDecoder> makeOpenTypeSVGDecoder(const uint8_t* svg, size_t length) {
+        auto factory = openTypeSVGDecoderFactory.load();
+        return std::unique_ptr<SkOpenTypeSVGDecoder>(factory ? factory(svg, length) : nullptr);
+    }
+}
+
+// Removes the decoder if factory is nullptr.
+extern "C" void C_SkGraphics_SetOpenTypeSVGDecoderFactory(OpenTypeSVGDecoder::Factory factory) {
+    openTypeSVGDecoderFactory.store(factory);
+    SkGraphics::SetOpenTypeSVGDecoderFactory(factory ? makeOpenTypeSVGDecoder : nullptr);
+}
+
+extern "C" bool C_SkGraphics_HasOpenTypeSVGDecoderFactory() {
+    return SkGraphics::GetOpenTypeSVGDecoderFactory() != nullptr;
+}
+
 //
 // SkImageFilter
 //
@@ -1996,6 +2996,63 @@ extern "C" bool C_SkImageGenerator_isTextureGenerator(const SkImageGenerator *se
     return self->isTextureGenerator();
 }
 
+namespace ImageGenerator {
+    extern "C" {
+        typedef void (*Drop)(TraitObject);
+        typedef SkData* (*OnRefEncodedData)(TraitObject);
+        typedef bool (*OnGetPixels)(TraitObject, const SkImageInfo*, void*, size_t);
+        typedef bool (*OnQueryYUVAInfo)(TraitObject, const SkYUVAPixmapInfo::SupportedDataTypes*, SkYUVAPixmapInfo*);
+        typedef bool (*OnGetYUVAPlanes)(TraitObject, const SkYUVAPixmaps*);
+    }
+}
+
+class RustImageGenerator final : public SkImageGenerator {
+public:
+    struct Param {
+        TraitObject trait;
+        ::ImageGenerator::Drop drop;
+        ::ImageGenerator::OnRefEncodedData onRefEncodedData;
+        ::ImageGenerator::OnGetPixels onGetPixels;
+        ::ImageGenerator::OnQueryYUVAInfo onQueryYUVAInfo;
+        ::ImageGenerator::OnGetYUVAPlanes onGetYUVAPlanes;
+    };
+
+    RustImageGenerator(const SkImageInfo& info, const Param& param)
+    : SkImageGenerator(info)
+    , _param(param)
+    { }
+
+    ~RustImageGenerator() override {
+        _param.drop(_param.trait);
+    }
+
+protected:
+    sk_sp<SkData> onRefEncodedData() override {
+        return sp(_param.onRefEncodedData(_param.trait));
+    }
+
+    bool onGetPixels(const SkImageInfo& info, void* pixels, size_t rowBytes, const Options&) override {
+        return _param.onGetPixels(_param.trait, &info, pixels, rowBytes);
+    }
+
+    bool onQueryYUVAInfo(
+        const SkYUVAPixmapInfo::SupportedDataTypes& supportedDataTypes,
+        SkYUVAPixmapInfo* yuvaPixmapInfo) const override {
+        return _param.onQueryYUVAInfo(_param.trait, &supportedDataTypes, yuvaPixmapInfo);
+    }
+
+    bool onGetYUVAPlanes(const SkYUVAPixmaps& yuvaPixmaps) override {
+        return _param.onGetYUVAPlanes(_param.trait, &yuvaPixmaps);
+    }
+
+private:
+    Param _param;
+};
+
+extern "C" SkImageGenerator* C_RustImageGenerator_New(const SkImageInfo* info, const RustImageGenerator::Param* param) {
+    return new RustImageGenerator(*info, *param);
+}
+
 //
 // core/SkString.h
 //
@@ -2216,6 +3273,10 @@ extern "C" SkMemoryStream* C_SkMemoryStream_MakeDirect(const void* data, size_t
     return SkMemoryStream::MakeDirect(data, length).release();
 }
 
+extern "C" SkStreamAsset* C_SkMemoryStream_MakeFromData(SkData* data) {
+    return SkMemoryStream::Make(sp(data)).release();
+}
+
 extern "C" void C_SkDynamicMemoryWStream_Construct(SkDynamicMemoryWStream* uninitialized) {
     new(uninitialized) SkDynamicMemoryWStream();
 }
@@ -2652,6 +3713,112 @@ bool C_SkRuntimeEffect_allowBlender(const SkRuntimeEffect* self) {
     return self->allowBlender();
 }
 
+SkShader* C_SkRuntimeEffect_MakeTraced(SkShader* shader, const SkIPoint* traceCoord, SkSL::DebugTrace** debugTrace) {
+    auto traced = SkRuntimeEffect::MakeTraced(sp(shader), *traceCoord);
+    *debugTrace = traced.debugTrace.release();
+    return traced.shader.release();
+}
+
+//
+// sksl/SkSLDebugTrace.h
+//
+
+static const SkSL::DebugTracePriv* debugTracePriv(const SkSL::DebugTrace* self) {
+    return static_cast<const SkSL::DebugTracePriv*>(self);
+}
+
+SkData* C_SkSL_DebugTrace_writeTrace(const SkSL::DebugTrace* self) {
+    SkDynamicMemoryWStream stream;
+    self->writeTrace(&stream);
+    return stream.detachAsData().release();
+}
+
+SkData* C_SkSL_DebugTrace_dump(const SkSL::DebugTrace* self) {
+    SkDynamicMemoryWStream stream;
+    self->dump(&stream);
+    return stream.detachAsData().release();
+}
+
+void C_SkSL_DebugTrace_traceCoord(const SkSL::DebugTrace* self, SkIPoint* coord) {
+    *coord = debugTracePriv(self)->fTraceCoord;
+}
+
+size_t C_SkSL_DebugTrace_sourceCount(const SkSL::DebugTrace* self) {
+    return debugTracePriv(self)->fSource.size();
+}
+
+void C_SkSL_DebugTrace_source(const SkSL::DebugTrace* self, size_t i, SkString* line) {
+    auto& source = debugTracePriv(self)->fSource[i];
+    line->set(source.data(), source.size());
+}
+
+size_t C_SkSL_DebugTrace_slotCount(const SkSL::DebugTrace* self) {
+    return debugTracePriv(self)->fSlotInfo.size();
+}
+
+enum class DebugTraceNumberKind {
+    Float,
+    Signed,
+    Unsigned,
+    Boolean,
+    Nonnumeric,
+};
+
+void C_SkSL_DebugTrace_slot(
+    const SkSL::DebugTrace* self, size_t i,
+    SkString* name, int* componentIndex, DebugTraceNumberKind* numberKind, int* line) {
+    auto& slot = debugTracePriv(self)->fSlotInfo[i];
+    name->set(slot.name.data(), slot.name.size());
+    *componentIndex = slot.componentIndex;
+    switch (slot.numberKind) {
+        case SkSL::Type::NumberKind::kFloat: *numberKind = DebugTraceNumberKind::Float; break;
+        case SkSL::Type::NumberKind::kSigned: *numberKind = DebugTraceNumberKind::Signed; break;
+        case SkSL::Type::NumberKind::kUnsigned: *numberKind = DebugTraceNumberKind::Unsigned; break;
+        case SkSL::Type::NumberKind::kBoolean: *numberKind = DebugTraceNumberKind::Boolean; break;
+        default: *numberKind = DebugTraceNumberKind::Nonnumeric; break;
+    }
+    *line = slot.line;
+}
+
+void C_SkSL_DebugTrace_slotSuffix(const SkSL::DebugTrace* self, size_t i, SkString* suffix) {
+    auto str = debugTracePriv(self)->getSlotComponentSuffix(i);
+    suffix->set(str.data(), str.size());
+}
+
+size_t C_SkSL_DebugTrace_functionCount(const SkSL::DebugTrace* self) {
+    return debugTracePriv(self)->fFuncInfo.size();
+}
+
+void C_SkSL_DebugTrace_function(const SkSL::DebugTrace* self, size_t i, SkString* name) {
+    auto& function = debugTracePriv(self)->fFuncInfo[i].name;
+    name->set(function.data(), function.size());
+}
+
+enum class DebugTraceOp {
+    Line,
+    Var,
+    Enter,
+    Exit,
+    Scope,
+};
+
+size_t C_SkSL_DebugTrace_traceCount(const SkSL::DebugTrace* self) {
+    return debugTracePriv(self)->fTraceInfo.size();
+}
+
+void C_SkSL_DebugTrace_trace(const SkSL::DebugTrace* self, size_t i, DebugTraceOp* op, int32_t data[2]) {
+    auto& trace = debugTracePriv(self)->fTraceInfo[i];
+    switch (trace.op) {
+        case SkSL::TraceInfo::Op::kLine: *op = DebugTraceOp::Line; break;
+        case SkSL::TraceInfo::Op::kVar: *op = DebugTraceOp::Var; break;
+        case SkSL::TraceInfo::Op::kEnter: *op = DebugTraceOp::Enter; break;
+        case SkSL::TraceInfo::Op::kExit: *op = DebugTraceOp::Exit; break;
+        case SkSL::TraceInfo::Op::kScope: *op = DebugTraceOp::Scope; break;
+    }
+    data[0] = trace.data[0];
+    data[1] = trace.data[1];
+}
+
 void C_SkRuntimeShaderBuilder_Construct(SkRuntimeShaderBuilder *uninitialized,
                                         SkRuntimeEffect *effect) {
   new (uninitialized) SkRuntimeShaderBuilder(sp(effect));
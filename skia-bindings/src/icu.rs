@@ -1,4 +1,4 @@
-#[cfg(windows)]
+#[cfg(all(windows, not(feature = "textlayout-client-unicode")))]
 pub fn init() {
     use std::env;
     static icudtl: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/skia/icudtl.dat"));
@@ -36,5 +36,5 @@ pub fn init() {
     }
 }
 
-#[cfg(not(windows))]
+#[cfg(not(all(windows, not(feature = "textlayout-client-unicode"))))]
 pub fn init() {}
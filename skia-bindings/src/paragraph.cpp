@@ -13,6 +13,13 @@
 #include "modules/skparagraph/include/TextStyle.h"
 #include "modules/skparagraph/include/TypefaceFontProvider.h"
 
+#if defined(SK_UNICODE_CLIENT_IMPLEMENTATION)
+// ParagraphBuilder does not tell whether a builder has a SkUnicode, which is read from
+// ParagraphBuilderImpl.
+#include "modules/skparagraph/src/ParagraphBuilderImpl.h"
+#include "modules/skunicode/include/SkUnicode_client.h"
+#endif
+
 // m84: needs definition of SkFontData
 #include "src/core/SkFontDescriptor.h"
 
@@ -393,6 +400,16 @@ extern "C" {
 // ParagraphBuilder.h
 //
 
+#if defined(SK_UNICODE_CLIENT_IMPLEMENTATION)
+// Hacky way to access the protected ParagraphBuilderImpl::fUnicode property.
+class ParagraphBuilderAccessor : public ParagraphBuilderImpl {
+public:
+    bool hasUnicode() const {
+        return fUnicode != nullptr;
+    }
+};
+#endif
+
 extern "C" {
     void C_ParagraphBuilder_delete(ParagraphBuilder* self) {
         delete self;
@@ -418,7 +435,19 @@ extern "C" {
         self->addPlaceholder(*placeholderStyle);
     }
 
+    // Builders created by `C_ParagraphBuilder_makeWithClientUnicode()` have no `SkUnicode`. Each
+    // paragraph they build gets a client `SkUnicode` with the text segmentation set by the client.
     Paragraph* C_ParagraphBuilder_Build(ParagraphBuilder* self) {
+#if defined(SK_UNICODE_CLIENT_IMPLEMENTATION)
+        if (!static_cast<ParagraphBuilderAccessor*>(self)->hasUnicode()) {
+            auto [words, graphemeBreaks, lineBreaks] = self->getClientICUData();
+            self->SetUnicode(SkUnicodes::Client::Make(
+                self->getText(), std::move(words), std::move(graphemeBreaks), std::move(lineBreaks)));
+            auto paragraph = self->Build();
+            self->SetUnicode(nullptr);
+            return paragraph.release();
+        }
+#endif
         return self->Build().release();
     }
 
@@ -436,8 +465,113 @@ extern "C" {
         return self->Reset();
     }
 
+    void C_ParagraphBuilder_SetWordsUtf8(ParagraphBuilder* self, const size_t* words, size_t count) {
+        self->SetWordsUtf8(std::vector<SkUnicode::Position>(words, words + count));
+    }
+
+    void C_ParagraphBuilder_getWordsUtf8(ParagraphBuilder* self, VecSink<size_t>* words) {
+        auto v = self->getWordsUtf8();
+        words->set(v);
+    }
+
+    void C_ParagraphBuilder_SetWordsUtf16(ParagraphBuilder* self, const size_t* words, size_t count) {
+        self->SetWordsUtf16(std::vector<SkUnicode::Position>(words, words + count));
+    }
+
+    void C_ParagraphBuilder_getWordsUtf16(ParagraphBuilder* self, VecSink<size_t>* words) {
+        auto v = self->getWordsUtf16();
+        words->set(v);
+    }
+
+    void C_ParagraphBuilder_SetGraphemeBreaksUtf8(ParagraphBuilder* self, const size_t* graphemeBreaks, size_t count) {
+        self->SetGraphemeBreaksUtf8(std::vector<SkUnicode::Position>(graphemeBreaks, graphemeBreaks + count));
+    }
+
+    void C_ParagraphBuilder_getGraphemeBreaksUtf8(ParagraphBuilder* self, VecSink<size_t>* graphemeBreaks) {
+        auto v = self->getGraphemeBreaksUtf8();
+        graphemeBreaks->set(v);
+    }
+
+    void C_ParagraphBuilder_SetGraphemeBreaksUtf16(ParagraphBuilder* self, const size_t* graphemeBreaks, size_t count) {
+        self->SetGraphemeBreaksUtf16(std::vector<SkUnicode::Position>(graphemeBreaks, graphemeBreaks + count));
+    }
+
+    void C_ParagraphBuilder_getGraphemeBreaksUtf16(ParagraphBuilder* self, VecSink<size_t>* graphemeBreaks) {
+        auto v = self->getGraphemeBreaksUtf16();
+        graphemeBreaks->set(v);
+    }
+}
+
+// `SkUnicode::LineBreakBefore`, which is not available in Rust, because `SkUnicode` is blocklisted.
+struct LineBreakBefore {
+    size_t pos;
+    bool hard;
+};
+
+namespace {
+    std::vector<SkUnicode::LineBreakBefore> fromLineBreaks(const LineBreakBefore* breaks, size_t count) {
+        std::vector<SkUnicode::LineBreakBefore> r;
+        for (size_t i = 0; i < count; ++i) {
+            r.emplace_back(breaks[i].pos, breaks[i].hard
+                ? SkUnicode::LineBreakType::kHardLineBreak
+                : SkUnicode::LineBreakType::kSoftLineBreak);
+        }
+        return r;
+    }
+
+    void setLineBreaks(const std::vector<SkUnicode::LineBreakBefore>& breaks, VecSink<LineBreakBefore>* result) {
+        std::vector<LineBreakBefore> r;
+        for (auto& lineBreak : breaks) {
+            r.push_back({lineBreak.pos, lineBreak.breakType == SkUnicode::LineBreakType::kHardLineBreak});
+        }
+        result->set(r);
+    }
+}
+
+extern "C" {
+    void C_ParagraphBuilder_SetLineBreaksUtf8(ParagraphBuilder* self, const LineBreakBefore* lineBreaks, size_t count) {
+        self->SetLineBreaksUtf8(fromLineBreaks(lineBreaks, count));
+    }
+
+    void C_ParagraphBuilder_getLineBreaksUtf8(ParagraphBuilder* self, VecSink<LineBreakBefore>* lineBreaks) {
+        setLineBreaks(self->getLineBreaksUtf8(), lineBreaks);
+    }
+
+    void C_ParagraphBuilder_SetLineBreaksUtf16(ParagraphBuilder* self, const LineBreakBefore* lineBreaks, size_t count) {
+        self->SetLineBreaksUtf16(fromLineBreaks(lineBreaks, count));
+    }
+
+    void C_ParagraphBuilder_getLineBreaksUtf16(ParagraphBuilder* self, VecSink<LineBreakBefore>* lineBreaks) {
+        setLineBreaks(self->getLineBreaksUtf16(), lineBreaks);
+    }
+
+    void C_ParagraphBuilder_getClientICUData(
+        const ParagraphBuilder* self,
+        VecSink<size_t>* wordsUtf16, VecSink<size_t>* graphemeBreaksUtf8,
+        VecSink<LineBreakBefore>* lineBreaksUtf8) {
+        auto [words, graphemeBreaks, lineBreaks] = self->getClientICUData();
+        wordsUtf16->set(words);
+        graphemeBreaksUtf8->set(graphemeBreaks);
+        setLineBreaks(lineBreaks, lineBreaksUtf8);
+    }
+
+    // Returns nullptr if Skia was built without ICU.
     ParagraphBuilder* C_ParagraphBuilder_make(const ParagraphStyle* style, const FontCollection* fontCollection) {
+#if defined(SK_UNICODE_ICU_IMPLEMENTATION)
         return ParagraphBuilder::make(*style, spFromConst(fontCollection)).release();
+#else
+        return nullptr;
+#endif
+    }
+
+    // Returns nullptr if Skia was built without the client `SkUnicode` implementation.
+    ParagraphBuilder* C_ParagraphBuilder_makeWithClientUnicode(const ParagraphStyle* style, const FontCollection* fontCollection) {
+#if defined(SK_UNICODE_CLIENT_IMPLEMENTATION)
+        // The `SkUnicode` is created by `C_ParagraphBuilder_Build()`.
+        return ParagraphBuilder::make(*style, spFromConst(fontCollection), nullptr).release();
+#else
+        return nullptr;
+#endif
     }
 }
 
@@ -0,0 +1,32 @@
+#include "bindings.h"
+#include "resources.h"
+
+extern "C" bool C_ImageAsset_isMultiFrame(skresources::ImageAsset* self) {
+    return self->isMultiFrame();
+}
+
+extern "C" void C_ImageAsset_getFrameData(
+    skresources::ImageAsset* self, float t,
+    SkImage** image, SkSamplingOptions* sampling, SkMatrix* matrix,
+    skresources::ImageAsset::SizeFit* scaling)
+{
+    auto frameData = self->getFrameData(t);
+    *image = frameData.image.release();
+    *sampling = frameData.sampling;
+    *matrix = frameData.matrix;
+    *scaling = frameData.scaling;
+}
+
+extern "C" skresources::ImageAsset* C_MultiFrameImageAsset_Make(
+    SkData* data, skresources::ImageDecodeStrategy decodeStrategy)
+{
+    return skresources::MultiFrameImageAsset::Make(sp(data), decodeStrategy).release();
+}
+
+extern "C" RustResourceProvider* C_RustResourceProvider_New(const RustResourceProvider::Param* param) {
+    return new RustResourceProvider(*param);
+}
+
+extern "C" skresources::ImageAsset* C_RustImageAsset_New(const RustImageAsset::Param* param) {
+    return new RustImageAsset(*param);
+}
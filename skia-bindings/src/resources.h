@@ -0,0 +1,113 @@
+#ifndef SKIA_BINDINGS_RESOURCES_H
+#define SKIA_BINDINGS_RESOURCES_H
+
+#include "bindings.h"
+
+#include "include/core/SkData.h"
+#include "include/core/SkFontMgr.h"
+#include "include/core/SkImage.h"
+#include "include/core/SkMatrix.h"
+#include "include/core/SkSamplingOptions.h"
+#include "include/core/SkTypeface.h"
+#include "modules/skresources/include/SkResources.h"
+
+namespace ResourceProvider { 
+    extern "C" {
+        typedef void (*Drop)(TraitObject);
+
+        typedef SkData *(*Load)(TraitObject, const char resource_path[], const char resource_name[]);
+        typedef skresources::ImageAsset* (*LoadImageAsset)(TraitObject, const char resource_path[], const char resource_name[], const char resource_id[]);
+        typedef SkTypeface *(*LoadTypeface)(TraitObject, const char name[], const char url[]);
+        typedef SkFontMgr *(*FontMgr)(TraitObject);
+    }
+}
+
+class RustResourceProvider final : public skresources::ResourceProvider {
+public:
+    struct Param {
+        TraitObject trait;
+        ::ResourceProvider::Drop drop;
+        ::ResourceProvider::Load load;
+        ::ResourceProvider::LoadImageAsset loadImageAsset;
+        ::ResourceProvider::LoadTypeface loadTypeface;
+        ::ResourceProvider::FontMgr fontMgr;
+    };
+
+    explicit RustResourceProvider(const Param& param) 
+    : _param(param) 
+    { }
+
+    virtual ~RustResourceProvider() {
+        _param.drop(_param.trait);
+    }
+
+    sk_sp<SkData> load(const char resource_path[], const char resource_name[]) const override {
+        return sp(_param.load(_param.trait, resource_path, resource_name));
+    }
+
+    sk_sp<skresources::ImageAsset> loadImageAsset(
+        const char resource_path[],
+        const char resource_name[],
+        const char resource_id[]) const override {
+        return sp(_param.loadImageAsset(_param.trait, resource_path, resource_name, resource_id));
+    }
+
+    sk_sp<SkTypeface> loadTypeface(const char name[], const char url[]) const override {
+        return sp(_param.loadTypeface(_param.trait, name, url));
+    }
+ 
+    // This is here to provide access to the FontMgr to the Dom and Skottie.
+    sk_sp<SkFontMgr> fontMgr() const {
+        return sp(_param.fontMgr(_param.trait));
+    }
+
+private:
+    Param _param;
+};
+
+namespace ImageAsset {
+    extern "C" {
+        typedef void (*Drop)(TraitObject);
+        typedef bool (*IsMultiFrame)(TraitObject);
+        typedef void (*GetFrameData)(
+            TraitObject, float t,
+            SkImage** image, SkSamplingOptions* sampling, SkMatrix* matrix,
+            skresources::ImageAsset::SizeFit* scaling);
+    }
+}
+
+class RustImageAsset final : public skresources::ImageAsset {
+public:
+    struct Param {
+        TraitObject trait;
+        ::ImageAsset::Drop drop;
+        ::ImageAsset::IsMultiFrame isMultiFrame;
+        ::ImageAsset::GetFrameData getFrameData;
+    };
+
+    explicit RustImageAsset(const Param& param)
+    : _param(param)
+    { }
+
+    ~RustImageAsset() override {
+        _param.drop(_param.trait);
+    }
+
+    bool isMultiFrame() override {
+        return _param.isMultiFrame(_param.trait);
+    }
+
+    FrameData getFrameData(float t) override {
+        FrameData frameData;
+        SkImage* image = nullptr;
+        _param.getFrameData(
+            _param.trait, t, &image, &frameData.sampling, &frameData.matrix, &frameData.scaling);
+        frameData.image = sp(image);
+        return frameData;
+    }
+
+private:
+    Param _param;
+};
+
+#endif //SKIA_BINDINGS_RESOURCES_H
@@ -4,14 +4,26 @@
 #include "modules/skshaper/include/SkShaper.h"
 #include "modules/skshaper/include/SkShaper_harfbuzz.h"
 #include "modules/skshaper/include/SkShaper_skunicode.h"
-#include "modules/skunicode/include/SkUnicode_icu.h"
+#include "modules/skunicode/include/SkUnicode.h"
 #ifdef SK_SHAPER_CORETEXT_AVAILABLE
 #include "modules/skshaper/include/SkShaper_coretext.h"
 #endif
 
+#if defined(SK_UNICODE_ICU_IMPLEMENTATION)
+#include "modules/skunicode/include/SkUnicode_icu.h"
 #if defined(_WIN32)
 #include "third_party/icu/SkLoadICU.h"
 #endif
+#endif
+
+// Returns nullptr if Skia was built without ICU.
+static sk_sp<SkUnicode> makeUnicode() {
+#if defined(SK_UNICODE_ICU_IMPLEMENTATION)
+    return SkUnicodes::ICU::Make();
+#else
+    return nullptr;
+#endif
+}
 
 extern "C" SkShaper* C_SkShaper_MakeCoreText() {
 #ifdef SK_SHAPER_CORETEXT_AVAILABLE
@@ -219,7 +231,7 @@ extern "C" SkShaper::ScriptRunIterator* C_SkShapers_Primitive_TrivialScriptRunIt
 // SkShapers::HB
 
 extern "C" SkShaper* C_SkShapers_HB_ShaperDrivenWrapper(SkFontMgr* fontMgr) {
-    auto unicode = SkUnicodes::ICU::Make();
+    auto unicode = makeUnicode();
     if (!unicode) {
         return nullptr;
     }
@@ -227,7 +239,7 @@ extern "C" SkShaper* C_SkShapers_HB_ShaperDrivenWrapper(SkFontMgr* fontMgr) {
 }
 
 extern "C" SkShaper* C_SkShapers_HB_ShapeThenWrap(SkFontMgr* fontMgr) {
-    auto unicode = SkUnicodes::ICU::Make();
+    auto unicode = makeUnicode();
     if (!unicode) {
         return nullptr;
     }
@@ -235,7 +247,7 @@ extern "C" SkShaper* C_SkShapers_HB_ShapeThenWrap(SkFontMgr* fontMgr) {
 }
 
 extern "C" SkShaper* C_SkShapers_HB_ShapeDontWrapOrReorder(SkFontMgr* fontMgr) {
-    auto unicode = SkUnicodes::ICU::Make();
+    auto unicode = makeUnicode();
     if (!unicode) {
         return nullptr;
     }
@@ -245,7 +257,7 @@ extern "C" SkShaper* C_SkShapers_HB_ShapeDontWrapOrReorder(SkFontMgr* fontMgr) {
 // SkShapers::unicode
 
 extern "C" SkShaper::BiDiRunIterator* C_SkShapers_unicode_BidiRunIterator(const char* utf8, size_t utf8Bytes, uint8_t bidiLevel) {
-    auto unicode = SkUnicodes::ICU::Make();
+    auto unicode = makeUnicode();
     if (!unicode) {
         return nullptr;
     }
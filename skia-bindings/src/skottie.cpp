@@ -0,0 +1,75 @@
+#include "bindings.h"
+#include "resources.h"
+
+#include "include/core/SkCanvas.h"
+#include "include/core/SkStream.h"
+#include "modules/skottie/include/Skottie.h"
+#include "modules/skshaper/utils/FactoryHelpers.h"
+
+extern "C" void C_skottie_Animation_ref(const skottie::Animation* self) {
+    self->ref();
+}
+
+extern "C" void C_skottie_Animation_unref(const skottie::Animation* self) {
+    self->unref();
+}
+
+extern "C" bool C_skottie_Animation_unique(const skottie::Animation* self) {
+    return self->unique();
+}
+
+extern "C" skottie::Animation* C_skottie_Animation_MakeFromStream(
+    SkStream& stream,
+    RustResourceProvider* provider)
+{
+    auto builder = skottie::Animation::Builder();
+    builder.setFontManager(provider->fontMgr());
+    builder.setResourceProvider(sp(provider));
+    builder.setTextShapingFactory(SkShapers::BestAvailable());
+    return builder.make(&stream).release();
+}
+
+extern "C" void C_skottie_Animation_version(const skottie::Animation* self, SkString* version) {
+    *version = self->version();
+}
+
+extern "C" void C_skottie_Animation_size(const skottie::Animation* self, SkSize* size) {
+    *size = self->size();
+}
+
+extern "C" double C_skottie_Animation_duration(const skottie::Animation* self) {
+    return self->duration();
+}
+
+extern "C" double C_skottie_Animation_fps(const skottie::Animation* self) {
+    return self->fps();
+}
+
+extern "C" double C_skottie_Animation_inPoint(const skottie::Animation* self) {
+    return self->inPoint();
+}
+
+extern "C" double C_skottie_Animation_outPoint(const skottie::Animation* self) {
+    return self->outPoint();
+}
+
+extern "C" void C_skottie_Animation_render(
+    const skottie::Animation* self,
+    SkCanvas* canvas,
+    const SkRect* dst,
+    uint32_t flags)
+{
+    self->render(canvas, dst, flags);
+}
+
+extern "C" void C_skottie_Animation_seek(skottie::Animation* self, SkScalar t) {
+    self->seek(t);
+}
+
+extern "C" void C_skottie_Animation_seekFrame(skottie::Animation* self, double t) {
+    self->seekFrame(t);
+}
+
+extern "C" void C_skottie_Animation_seekFrameTime(skottie::Animation* self, double t) {
+    self->seekFrameTime(t);
+}
@@ -1,6 +1,8 @@
 #include "bindings.h"
+#include "resources.h"
 
 #include "include/core/SkCanvas.h"
+#include "include/core/SkImageFilter.h"
 #include "include/svg/SkSVGCanvas.h"
 
 #include "modules/svg/include/SkSVGCircle.h"
@@ -32,6 +34,7 @@
 #include "modules/svg/include/SkSVGLinearGradient.h"
 #include "modules/svg/include/SkSVGMask.h"
 #include "modules/svg/include/SkSVGNode.h"
+#include "modules/svg/include/SkSVGOpenTypeSVGDecoder.h"
 #include "modules/svg/include/SkSVGPath.h"
 #include "modules/svg/include/SkSVGPattern.h"
 #include "modules/svg/include/SkSVGPoly.h"
@@ -45,86 +48,82 @@
 #include "modules/svg/include/SkSVGTypes.h"
 #include "modules/svg/include/SkSVGUse.h"
 #include "modules/svg/include/SkSVGValue.h"
-#include "modules/skresources/include/SkResources.h"
 
-#include "include/core/SkStream.h"
-
-extern "C" bool C_ImageAsset_isMultiFrame(skresources::ImageAsset* self) {
-    return self->isMultiFrame();
-}
-
-extern "C" skresources::ImageAsset* C_MultiFrameImageAsset_Make(
-    SkData* data, skresources::ImageDecodeStrategy decodeStrategy)
-{
-    return skresources::MultiFrameImageAsset::Make(sp(data), decodeStrategy).release();
-}
-
-namespace ResourceProvider { 
-    extern "C" {
-        typedef void (*Drop)(TraitObject);
-
-        typedef SkData *(*Load)(TraitObject, const char resource_path[], const char resource_name[]);
-        typedef skresources::ImageAsset* (*LoadImageAsset)(TraitObject, const char resource_path[], const char resource_name[], const char resource_id[]);
-        typedef SkTypeface *(*LoadTypeface)(TraitObject, const char name[], const char url[]);
-        typedef SkFontMgr *(*FontMgr)(TraitObject);
-    }
-}
-
-class RustResourceProvider final : public skresources::ResourceProvider {
-public:
-    struct Param {
-        TraitObject trait;
-        ::ResourceProvider::Drop drop;
-        ::ResourceProvider::Load load;
-        ::ResourceProvider::LoadImageAsset loadImageAsset;
-        ::ResourceProvider::LoadTypeface loadTypeface;
-        ::ResourceProvider::FontMgr fontMgr;
-    };
+#include "modules/skshaper/include/SkShaper_factory.h"
 
-    explicit RustResourceProvider(const Param& param) 
-    : _param(param) 
-    { }
+#include "include/core/SkStream.h"
+#include "include/utils/SkNoDrawCanvas.h"
+// SkTextBlob::Iter does not expose the RSXforms SVG text positions its glyphs with.
+#include "src/core/SkTextBlobPriv.h"
+// SkSVGDOM drops the attributes it does not support, so the XML is parsed again with the parser it
+// uses, for which Skia has no public header.
+#include "src/xml/SkDOM.h"
+
+#include <mutex>
+#include <unordered_map>
+
+// SkSVGDOM does not expose its resource provider, so the DOMs created here get a proxy that
+// registers the provider under the DOM. The DOM owns the proxy, which unregisters it again when
+// the DOM is destroyed.
+class RustSVGDOMResourceProvider final : public skresources::ResourceProviderProxyBase {
+    public:
+        explicit RustSVGDOMResourceProvider(sk_sp<RustResourceProvider> provider)
+            : ResourceProviderProxyBase(provider)
+            , fProvider(std::move(provider)) {}
+
+        ~RustSVGDOMResourceProvider() override {
+            if (fDom) {
+                std::lock_guard<std::mutex> lock(Mutex());
+                Registry().erase(fDom);
+            }
+        }
 
-    virtual ~RustResourceProvider() {
-        _param.drop(_param.trait);
-    }
+        void registerDom(const SkSVGDOM* dom) {
+            std::lock_guard<std::mutex> lock(Mutex());
+            fDom = dom;
+            Registry()[dom] = this;
+        }
 
-    sk_sp<SkData> load(const char resource_path[], const char resource_name[]) const override {
-        return sp(_param.load(_param.trait, resource_path, resource_name));
-    }
+        // The provider the DOM was created with, or null if it was not created here.
+        static sk_sp<RustResourceProvider> Find(const SkSVGDOM* dom) {
+            std::lock_guard<std::mutex> lock(Mutex());
+            auto entry = Registry().find(dom);
+            return entry != Registry().end() ? entry->second->fProvider : nullptr;
+        }
 
-    sk_sp<skresources::ImageAsset> loadImageAsset(
-        const char resource_path[],
-        const char resource_name[],
-        const char resource_id[]) const override {
-        return sp(_param.loadImageAsset(_param.trait, resource_path, resource_name, resource_id));
-    }
+    private:
+        static std::mutex& Mutex() {
+            static std::mutex mutex;
+            return mutex;
+        }
 
-    sk_sp<SkTypeface> loadTypeface(const char name[], const char url[]) const override {
-        return sp(_param.loadTypeface(_param.trait, name, url));
-    }
- 
-    // This is here to provide access to the FontMgr to the Dom.
-    sk_sp<SkFontMgr> fontMgr() const {
-        return sp(_param.fontMgr(_param.trait));
-    }
+        static std::unordered_map<const SkSVGDOM*, RustSVGDOMResourceProvider*>& Registry() {
+            static std::unordered_map<const SkSVGDOM*, RustSVGDOMResourceProvider*> registry;
+            return registry;
+        }
 
-private:
-    Param _param;
+        sk_sp<RustResourceProvider> fProvider;
+        const SkSVGDOM* fDom = nullptr;
 };
 
-extern "C" RustResourceProvider* C_RustResourceProvider_New(const RustResourceProvider::Param* param) {
-    return new RustResourceProvider(*param);
-}
-
 extern "C" SkSVGDOM* C_SkSVGDOM_MakeFromStream(
     SkStream& stream,
     RustResourceProvider* provider) 
 {
+    auto proxy = sk_make_sp<RustSVGDOMResourceProvider>(sp(provider));
     auto builder = SkSVGDOM::Builder();
     builder.setFontManager(provider->fontMgr());
-    builder.setResourceProvider(sp(provider));
-    return builder.make(stream).release();
+    builder.setResourceProvider(proxy);
+    auto dom = builder.make(stream);
+    if (dom) {
+        proxy->registerDom(dom.get());
+    }
+    return dom.release();
+}
+
+extern "C" SkSVGNode* C_SkSVGDOM_findNodeById(const SkSVGDOM* self, const char* id) {
+    auto node = const_cast<SkSVGDOM*>(self)->findNodeById(id);
+    return node ? SkRef(node->get()) : nullptr;
 }
 
 extern "C" void C_SkSVGDOM_setContainerSize(SkSVGDOM* self, const SkSize& size){
@@ -135,6 +134,19 @@ extern "C" SkSVGSVG* C_SkSVGDOM_getRoot(const SkSVGDOM* self){
     return self->getRoot();
 }
 
+extern "C" bool C_SkSVGDOM_renderNode(const SkSVGDOM* self, SkCanvas* canvas, const char* id) {
+    if (!const_cast<SkSVGDOM*>(self)->findNodeById(id)) {
+        return false;
+    }
+    SkSVGPresentationContext pctx;
+    self->renderNode(canvas, pctx, id);
+    return true;
+}
+
+extern "C" SkOpenTypeSVGDecoder* C_SkSVGOpenTypeSVGDecoder_Make(const uint8_t* svg, size_t length) {
+    return SkSVGOpenTypeSVGDecoder::Make(svg, length).release();
+}
+
 extern "C" void C_SkSVGTypes(
     SkSVGFeComponentTransfer*,
     SkSVGFeFlood*,
@@ -419,12 +431,44 @@ class SkSVGContainerAccessor : public SkSVGContainer {
         const sk_sp<SkSVGNode>* children() const {
             return fChildren.data();
         }
+
+        void insertChild(int index, sk_sp<SkSVGNode> node) {
+            fChildren.push_back(std::move(node));
+            for (int i = fChildren.size() - 1; i > index; --i) {
+                std::swap(fChildren[i], fChildren[i - 1]);
+            }
+        }
+
+        sk_sp<SkSVGNode> removeChild(int index) {
+            auto node = std::move(fChildren[index]);
+            for (int i = index; i + 1 < fChildren.size(); ++i) {
+                fChildren[i] = std::move(fChildren[i + 1]);
+            }
+            fChildren.pop_back();
+            return node;
+        }
+
+        void clearChildren() {
+            fChildren.clear();
+        }
 };
 
 extern "C" void C_SkSVGContainer_appendChild(SkSVGContainer* self, SkSVGNode* node) {
     self->appendChild(sk_sp<SkSVGNode>(node));
 }
 
+extern "C" void C_SkSVGContainer_insertChild(SkSVGContainer* self, int index, SkSVGNode* node) {
+    static_cast<SkSVGContainerAccessor*>(self)->insertChild(index, sp(node));
+}
+
+extern "C" SkSVGNode* C_SkSVGContainer_removeChild(SkSVGContainer* self, int index) {
+    return static_cast<SkSVGContainerAccessor*>(self)->removeChild(index).release();
+}
+
+extern "C" void C_SkSVGContainer_clearChildren(SkSVGContainer* self) {
+    static_cast<SkSVGContainerAccessor*>(self)->clearChildren();
+}
+
 extern "C" int C_SkSVGContainer_childrenCount(const SkSVGContainer& self) {
     return static_cast<const SkSVGContainerAccessor&>(self).childrenCount();
 }
@@ -442,6 +486,678 @@ extern "C" SkSVGTag C_SkSVGNode_tag(const SkSVGNode& self) {
     return self.tag();
 }
 
+extern "C" void C_SkSVGNode_appendChild(SkSVGNode* self, SkSVGNode* node) {
+    self->appendChild(sp(node));
+}
+
+extern "C" bool C_SkSVGNode_parseAndSetAttribute(SkSVGNode* self, const char* name, const char* value) {
+    return self->parseAndSetAttribute(name, value);
+}
+
+//
+// Render contexts and geometry queries
+//
+
+class SkSVGNodeAccessor : public SkSVGNode {
+    public:
+        bool prepareToRender(SkSVGRenderContext* ctx) const {
+            return this->onPrepareToRender(ctx);
+        }
+};
+
+namespace {
+    // Nodes that derive from SkSVGContainer.
+    bool isContainer(const SkSVGNode* node) {
+        switch (node->tag()) {
+            case SkSVGTag::kCircle:
+            case SkSVGTag::kEllipse:
+            case SkSVGTag::kImage:
+            case SkSVGTag::kLine:
+            case SkSVGTag::kPath:
+            case SkSVGTag::kPolygon:
+            case SkSVGTag::kPolyline:
+            case SkSVGTag::kRect:
+            case SkSVGTag::kText:
+            case SkSVGTag::kTextLiteral:
+            case SkSVGTag::kTextPath:
+            case SkSVGTag::kTSpan:
+            case SkSVGTag::kUse:
+                return false;
+            default:
+                return true;
+        }
+    }
+
+    // SkSVGTextContainer does not expose its children, so the children of text nodes are not
+    // visited.
+    template <typename F>
+    void forEachChild(const SkSVGNode* node, F&& f) {
+        if (isContainer(node)) {
+            auto container = static_cast<const SkSVGContainerAccessor*>(node);
+            for (int i = 0; i < container->childrenCount(); ++i) {
+                f(container->children()[i].get());
+            }
+        }
+    }
+
+    // Adds the nodes `node` and its descendants refer to by id to `ids`.
+    void collectReferences(SkSVGDOM* dom, const SkSVGNode* node, SkSVGIDMapper* ids) {
+        auto add = [&](const SkSVGIRI& iri) {
+            if (iri.type() != SkSVGIRI::Type::kLocal || ids->find(iri.iri())) {
+                return;
+            }
+            if (auto found = dom->findNodeById(iri.iri().c_str())) {
+                ids->set(iri.iri(), *found);
+            }
+        };
+        auto addPaint = [&](const SkSVGProperty<SkSVGPaint, true>& paint) {
+            if (paint.isValue() && paint->type() == SkSVGPaint::Type::kIRI) {
+                add(paint->iri());
+            }
+        };
+        auto addFunc = [&](const SkSVGProperty<SkSVGFuncIRI, false>& func) {
+            if (func.isValue() && func->type() == SkSVGFuncIRI::Type::kIRI) {
+                add(func->iri());
+            }
+        };
+
+        addPaint(node->getFill());
+        addPaint(node->getStroke());
+        addFunc(node->getClipPath());
+        addFunc(node->getMask());
+        addFunc(node->getFilter());
+        switch (node->tag()) {
+            case SkSVGTag::kFeImage:
+                add(static_cast<const SkSVGFeImage*>(node)->getHref());
+                break;
+            case SkSVGTag::kLinearGradient:
+            case SkSVGTag::kRadialGradient:
+                add(static_cast<const SkSVGGradient*>(node)->getHref());
+                break;
+            case SkSVGTag::kPattern:
+                add(static_cast<const SkSVGPattern*>(node)->getHref());
+                break;
+            case SkSVGTag::kTextPath:
+                add(static_cast<const SkSVGTextPath*>(node)->getHref());
+                break;
+            case SkSVGTag::kUse:
+                add(static_cast<const SkSVGUse*>(node)->getHref());
+                break;
+            default:
+                break;
+        }
+
+        forEachChild(node, [&](const SkSVGNode* child) {
+            collectReferences(dom, child, ids);
+        });
+    }
+}
+
+// The context the root of a DOM is rendered in.
+//
+// SkSVGDOM does not expose its ids, so the nodes that are referenced in the document are looked up
+// by id when the context is created.
+class RustSVGRenderContext {
+    public:
+        explicit RustSVGRenderContext(const SkSVGDOM* dom)
+            : fDom(sk_ref_sp(dom))
+            , fResourceProvider(RustSVGDOMResourceProvider::Find(dom))
+            , fFontMgr(fResourceProvider ? fResourceProvider->fontMgr() : SkFontMgr::RefEmpty())
+            , fTextShapingFactory(SkShapers::Primitive::Factory())
+            , fCanvas(SkScalarCeilToInt(dom->containerSize().width()),
+                      SkScalarCeilToInt(dom->containerSize().height()))
+            , fLengthContext(dom->containerSize())
+            , fContext(&fCanvas,
+                       fFontMgr,
+                       fResourceProvider,
+                       fIDMapper,
+                       fLengthContext,
+                       fPresentationContext,
+                       {nullptr, nullptr},
+                       fTextShapingFactory) {
+            collectReferences(const_cast<SkSVGDOM*>(dom), dom->getRoot(), &fIDMapper);
+        }
+
+        const SkSVGNode* root() const {
+            return fDom->getRoot();
+        }
+
+        const SkSVGRenderContext& context() const {
+            return fContext;
+        }
+
+    private:
+        sk_sp<const SkSVGDOM> fDom;
+        sk_sp<RustResourceProvider> fResourceProvider;
+        sk_sp<SkFontMgr> fFontMgr;
+        sk_sp<SkShapers::Factory> fTextShapingFactory;
+        SkSVGIDMapper fIDMapper;
+        SkNoDrawCanvas fCanvas;
+        SkSVGLengthContext fLengthContext;
+        SkSVGPresentationContext fPresentationContext;
+        SkSVGRenderContext fContext;
+};
+
+extern "C" RustSVGRenderContext* C_RustSVGRenderContext_New(const SkSVGDOM* dom) {
+    return new RustSVGRenderContext(dom);
+}
+
+extern "C" void C_RustSVGRenderContext_delete(RustSVGRenderContext* self) {
+    delete self;
+}
+
+extern "C" void C_SkSVGNode_asPath(const SkSVGNode* self, const RustSVGRenderContext* ctx, SkPath* path) {
+    *path = self->asPath(ctx->context());
+}
+
+extern "C" void C_SkSVGNode_objectBoundingBox(const SkSVGNode* self, const RustSVGRenderContext* ctx, SkRect* rect) {
+    *rect = self->objectBoundingBox(ctx->context());
+}
+
+extern "C" SkImageFilter* C_SkSVGFilter_buildFilterDAG(const SkSVGFilter* self, const RustSVGRenderContext* ctx, const SkRect* bounds) {
+    if (!bounds) {
+        return self->buildFilterDAG(ctx->context()).release();
+    }
+    // Object bounding box units are resolved against the node of the OBB scope, so a rect with
+    // the bounds stands in for the filtered content.
+    auto node = SkSVGRect::Make();
+    node->setX(SkSVGLength(bounds->x()));
+    node->setY(SkSVGLength(bounds->y()));
+    node->setWidth(SkSVGLength(bounds->width()));
+    node->setHeight(SkSVGLength(bounds->height()));
+    SkSVGRenderContext boundsCtx(ctx->context(), node.get());
+    return self->buildFilterDAG(boundsCtx).release();
+}
+
+namespace {
+    // Containers that render their children.
+    bool isRenderedContainer(const SkSVGNode* node) {
+        return node->tag() == SkSVGTag::kSvg || node->tag() == SkSVGTag::kG;
+    }
+
+    // Nodes that render themselves. Others, like gradients or clip paths, are only rendered when
+    // referenced.
+    bool isRenderedLeaf(const SkSVGNode* node) {
+        switch (node->tag()) {
+            case SkSVGTag::kCircle:
+            case SkSVGTag::kEllipse:
+            case SkSVGTag::kImage:
+            case SkSVGTag::kLine:
+            case SkSVGTag::kPath:
+            case SkSVGTag::kPolygon:
+            case SkSVGTag::kPolyline:
+            case SkSVGTag::kRect:
+            case SkSVGTag::kText:
+            case SkSVGTag::kUse:
+                return true;
+            default:
+                return false;
+        }
+    }
+
+    // Calls visit(node, ctx) for all rendered nodes in paint order, where ctx is the context
+    // of the node's parent.
+    template <typename Visit>
+    void visitRendered(const SkSVGNode* node, const SkSVGRenderContext& ctx, Visit& visit) {
+        if (!isRenderedContainer(node) && !isRenderedLeaf(node)) {
+            return;
+        }
+        visit(node, ctx);
+        if (!isRenderedContainer(node)) {
+            return;
+        }
+        SkSVGRenderContext localContext(ctx, node);
+        if (!static_cast<const SkSVGNodeAccessor*>(node)->prepareToRender(&localContext)) {
+            return;
+        }
+        auto container = static_cast<const SkSVGContainerAccessor*>(node);
+        for (int i = 0; i < container->childrenCount(); ++i) {
+            visitRendered(container->children()[i].get(), localContext, visit);
+        }
+    }
+
+    // The name of the element a node is created for.
+    const char* elementName(SkSVGTag tag) {
+        switch (tag) {
+            case SkSVGTag::kCircle: return "circle";
+            case SkSVGTag::kClipPath: return "clipPath";
+            case SkSVGTag::kDefs: return "defs";
+            case SkSVGTag::kEllipse: return "ellipse";
+            case SkSVGTag::kFeBlend: return "feBlend";
+            case SkSVGTag::kFeColorMatrix: return "feColorMatrix";
+            case SkSVGTag::kFeComponentTransfer: return "feComponentTransfer";
+            case SkSVGTag::kFeComposite: return "feComposite";
+            case SkSVGTag::kFeDiffuseLighting: return "feDiffuseLighting";
+            case SkSVGTag::kFeDisplacementMap: return "feDisplacementMap";
+            case SkSVGTag::kFeDistantLight: return "feDistantLight";
+            case SkSVGTag::kFeFlood: return "feFlood";
+            case SkSVGTag::kFeFuncA: return "feFuncA";
+            case SkSVGTag::kFeFuncR: return "feFuncR";
+            case SkSVGTag::kFeFuncG: return "feFuncG";
+            case SkSVGTag::kFeFuncB: return "feFuncB";
+            case SkSVGTag::kFeGaussianBlur: return "feGaussianBlur";
+            case SkSVGTag::kFeImage: return "feImage";
+            case SkSVGTag::kFeMerge: return "feMerge";
+            case SkSVGTag::kFeMergeNode: return "feMergeNode";
+            case SkSVGTag::kFeMorphology: return "feMorphology";
+            case SkSVGTag::kFeOffset: return "feOffset";
+            case SkSVGTag::kFePointLight: return "fePointLight";
+            case SkSVGTag::kFeSpecularLighting: return "feSpecularLighting";
+            case SkSVGTag::kFeSpotLight: return "feSpotLight";
+            case SkSVGTag::kFeTurbulence: return "feTurbulence";
+            case SkSVGTag::kFilter: return "filter";
+            case SkSVGTag::kG: return "g";
+            case SkSVGTag::kImage: return "image";
+            case SkSVGTag::kLine: return "line";
+            case SkSVGTag::kLinearGradient: return "linearGradient";
+            case SkSVGTag::kMask: return "mask";
+            case SkSVGTag::kPath: return "path";
+            case SkSVGTag::kPattern: return "pattern";
+            case SkSVGTag::kPolygon: return "polygon";
+            case SkSVGTag::kPolyline: return "polyline";
+            case SkSVGTag::kRadialGradient: return "radialGradient";
+            case SkSVGTag::kRect: return "rect";
+            case SkSVGTag::kStop: return "stop";
+            case SkSVGTag::kSvg: return "svg";
+            case SkSVGTag::kText: return "text";
+            case SkSVGTag::kTextPath: return "textPath";
+            case SkSVGTag::kTSpan: return "tspan";
+            case SkSVGTag::kUse: return "use";
+            default: return nullptr;
+        }
+    }
+
+    // Whether SkSVGDOM creates nodes with `tag` for the elements named `name`. Anchors are
+    // created as groups.
+    bool isElementOf(const char* name, SkSVGTag tag) {
+        auto tagName = elementName(tag);
+        return (tagName && strcmp(tagName, name) == 0) || (tag == SkSVGTag::kG && strcmp(name, "a") == 0);
+    }
+
+    // Whether SkSVGDOM creates nodes for the elements named `name`.
+    bool isElementName(const char* name) {
+        for (auto tag = static_cast<int>(SkSVGTag::kCircle); tag <= static_cast<int>(SkSVGTag::kUse); ++tag) {
+            if (isElementOf(name, static_cast<SkSVGTag>(tag))) {
+                return true;
+            }
+        }
+        return false;
+    }
+
+    // Calls visit(node, names, values) for `node` and its descendants with the attributes of the
+    // XML elements they were created from.
+    template <typename Visit>
+    void visitAttributes(SkSVGDOM* dom, const SkDOM& xml, const SkDOM::Node* element, const SkSVGNode* node, Visit& visit) {
+        std::vector<SkString> names;
+        std::vector<SkString> values;
+        SkDOM::AttrIter iter(xml, element);
+        const char* value;
+        while (const char* name = iter.next(&value)) {
+            names.emplace_back(name);
+            values.emplace_back(value);
+        }
+        visit(node, names, values);
+
+        // Nodes are created in document order for the elements SkSVGDOM supports, other elements,
+        // including the ones with prefixed names, are skipped. A supported element that does not
+        // match the next node was dropped by SkSVGDOM, so the elements that follow it can not be
+        // paired reliably.
+        std::vector<const SkSVGNode*> children;
+        forEachChild(node, [&](const SkSVGNode* child) {
+            if (child->tag() != SkSVGTag::kTextLiteral) {
+                children.push_back(child);
+            }
+        });
+        size_t index = 0;
+        for (auto child = xml.getFirstChild(element); child && index < children.size();
+             child = xml.getNextSibling(child)) {
+            if (xml.getType(child) != SkDOM::kElement_Type || !isElementName(xml.getName(child))) {
+                continue;
+            }
+            if (!isElementOf(xml.getName(child), children[index]->tag())) {
+                break;
+            }
+            auto id = xml.findAttr(child, "id");
+            auto nodeWithId = id ? dom->findNodeById(id) : nullptr;
+            if (nodeWithId && nodeWithId->get() != children[index]) {
+                break;
+            }
+            visitAttributes(dom, xml, child, children[index++], visit);
+        }
+    }
+
+    // Calls visit(node, localContext, depth) for all nodes in depth-first order, where
+    // localContext is the context the node renders its content in.
+    template <typename Visit>
+    void visitAll(const SkSVGNode* node, const SkSVGRenderContext& ctx, size_t depth, Visit& visit) {
+        SkSVGRenderContext localContext(ctx, node);
+        static_cast<const SkSVGNodeAccessor*>(node)->prepareToRender(&localContext);
+        visit(node, localContext, depth);
+        forEachChild(node, [&](const SkSVGNode* child) {
+            visitAll(child, localContext, depth + 1, visit);
+        });
+    }
+
+    // The bounds of a rendered node in device coordinates of ctx's canvas.
+    SkRect renderedBounds(const SkSVGNode* node, const SkSVGRenderContext& ctx) {
+        SkRect bounds = SkRect::MakeEmpty();
+        if (isRenderedContainer(node)) {
+            auto visit = [&](const SkSVGNode* child, const SkSVGRenderContext& childCtx) {
+                if (child != node && isRenderedLeaf(child)) {
+                    bounds.join(renderedBounds(child, childCtx));
+                }
+            };
+            visitRendered(node, ctx, visit);
+            return bounds;
+        }
+
+        SkPath path = node->asPath(ctx);
+        if (!path.isEmpty()) {
+            return ctx.canvas()->getTotalMatrix().mapRect(path.computeTightBounds());
+        }
+
+        // Nodes without a path, like images.
+        SkSVGRenderContext localContext(ctx, node);
+        if (static_cast<const SkSVGNodeAccessor*>(node)->prepareToRender(&localContext)) {
+            bounds = localContext.canvas()->getTotalMatrix().mapRect(
+                node->objectBoundingBox(localContext));
+        }
+        return bounds;
+    }
+}
+
+extern "C" bool C_RustSVGRenderContext_objectBoundingBox(const RustSVGRenderContext* self, const SkSVGNode* node, SkRect* bounds) {
+    bool found = false;
+    auto visit = [&](const SkSVGNode* visited, const SkSVGRenderContext& ctx) {
+        if (visited == node) {
+            *bounds = renderedBounds(visited, ctx);
+            found = true;
+        }
+    };
+    visitRendered(self->root(), self->context(), visit);
+    return found;
+}
+
+extern "C" SkSVGNode* C_RustSVGRenderContext_hitTest(const RustSVGRenderContext* self, const SkPoint* point) {
+    const SkSVGNode* hit = nullptr;
+    auto visit = [&](const SkSVGNode* node, const SkSVGRenderContext& ctx) {
+        if (!isRenderedLeaf(node)) {
+            return;
+        }
+        SkPath path = node->asPath(ctx);
+        path.transform(ctx.canvas()->getTotalMatrix());
+        if (path.contains(point->x(), point->y())) {
+            hit = node;
+        }
+    };
+    visitRendered(self->root(), self->context(), visit);
+    return hit ? SkRef(const_cast<SkSVGNode*>(hit)) : nullptr;
+}
+
+// Walks the nodes of a DOM in depth-first order. Each node is returned with the context it renders
+// its content in.
+class RustSVGNodeIter {
+    public:
+        explicit RustSVGNodeIter(const SkSVGDOM* dom)
+            : fRenderContext(dom) {}
+
+        const SkSVGNode* next() {
+            if (!fStarted) {
+                fStarted = true;
+                fFrames.push_back(std::make_unique<Frame>(fRenderContext.context(), fRenderContext.root()));
+                return fRenderContext.root();
+            }
+            while (!fFrames.empty()) {
+                auto& frame = *fFrames.back();
+                if (frame.next == frame.children.size()) {
+                    fFrames.pop_back();
+                    continue;
+                }
+                auto child = frame.children[frame.next++].get();
+                fFrames.push_back(std::make_unique<Frame>(frame.context, child));
+                return child;
+            }
+            return nullptr;
+        }
+
+        // The context of the node returned last.
+        const SkSVGRenderContext& context() const {
+            return fFrames.back()->context;
+        }
+
+        size_t depth() const {
+            return fFrames.size() - 1;
+        }
+
+    private:
+        struct Frame {
+            Frame(const SkSVGRenderContext& parent, const SkSVGNode* node)
+                : context(parent, node) {
+                static_cast<const SkSVGNodeAccessor*>(node)->prepareToRender(&context);
+                forEachChild(node, [&](const SkSVGNode* child) {
+                    children.push_back(sk_ref_sp(child));
+                });
+            }
+
+            SkSVGRenderContext context;
+            // Keeps the children alive, even if they are removed from the node while walking.
+            std::vector<sk_sp<const SkSVGNode>> children;
+            size_t next = 0;
+        };
+
+        RustSVGRenderContext fRenderContext;
+        bool fStarted = false;
+        // The contexts refer to the contexts of their parents, so the frames must not move.
+        std::vector<std::unique_ptr<Frame>> fFrames;
+};
+
+extern "C" RustSVGNodeIter* C_RustSVGNodeIter_new(const SkSVGDOM* dom) {
+    return new RustSVGNodeIter(dom);
+}
+
+extern "C" void C_RustSVGNodeIter_delete(RustSVGNodeIter* self) {
+    delete self;
+}
+
+extern "C" SkSVGNode* C_RustSVGNodeIter_next(RustSVGNodeIter* self, SkMatrix* transform, size_t* depth) {
+    auto node = self->next();
+    if (!node) {
+        return nullptr;
+    }
+    *transform = self->context().canvas()->getTotalMatrix();
+    *depth = self->depth();
+    return SkRef(const_cast<SkSVGNode*>(node));
+}
+
+// The placement of the glyphs of a run. Runs without glyph positions are not supported and return
+// no xforms.
+static std::vector<SkRSXform> runXforms(const SkTextBlobRunIterator& it) {
+    std::vector<SkRSXform> xforms;
+    const SkScalar* pos = it.pos();
+    const SkPoint& offset = it.offset();
+    switch (it.positioning()) {
+        case SkTextBlobRunIterator::kRSXform_Positioning: {
+            auto runXforms = reinterpret_cast<const SkRSXform*>(pos);
+            xforms.assign(runXforms, runXforms + it.glyphCount());
+            break;
+        }
+        case SkTextBlobRunIterator::kFull_Positioning:
+            for (uint32_t i = 0; i < it.glyphCount(); ++i) {
+                xforms.push_back(SkRSXform::Make(1, 0, offset.x() + pos[2 * i], offset.y() + pos[2 * i + 1]));
+            }
+            break;
+        case SkTextBlobRunIterator::kHorizontal_Positioning:
+            for (uint32_t i = 0; i < it.glyphCount(); ++i) {
+                xforms.push_back(SkRSXform::Make(1, 0, offset.x() + pos[i], offset.y()));
+            }
+            break;
+        case SkTextBlobRunIterator::kDefault_Positioning:
+            break;
+    }
+    return xforms;
+}
+
+namespace {
+    // Reports the text blobs that are drawn on it with the matrix they are drawn with.
+    template <typename Visit>
+    class TextBlobRecordingCanvas final : public SkNoDrawCanvas {
+        public:
+            TextBlobRecordingCanvas(const SkCanvas* canvas, Visit& visit)
+                : SkNoDrawCanvas(canvas->getBaseLayerSize().width(), canvas->getBaseLayerSize().height())
+                , fVisit(visit) {
+                this->setMatrix(canvas->getLocalToDevice());
+            }
+
+        protected:
+            void onDrawTextBlob(const SkTextBlob* blob, SkScalar x, SkScalar y, const SkPaint&) override {
+                // Text that is filled and stroked draws its blobs twice.
+                if (blob->uniqueID() == fLastBlobID) {
+                    return;
+                }
+                fLastBlobID = blob->uniqueID();
+                fVisit(blob, this->getTotalMatrix().preTranslate(x, y));
+            }
+
+        private:
+            Visit& fVisit;
+            uint32_t fLastBlobID = SK_InvalidUniqueID;
+    };
+}
+
+// The text is rendered in the context of its parent, and the runs of the drawn text blobs are
+// reported.
+extern "C" void C_RustSVGRenderContext_shapeText(
+    const RustSVGRenderContext* self,
+    const SkSVGText* text,
+    void* context,
+    void (*visitRun)(void* context, const SkFont* font, const SkGlyphID* glyphs, const SkRSXform* xforms, size_t count, const SkMatrix* transform))
+{
+    auto visitBlob = [&](const SkTextBlob* blob, const SkMatrix& transform) {
+        for (SkTextBlobRunIterator it(blob); !it.done(); it.next()) {
+            auto xforms = runXforms(it);
+            if (xforms.size() == it.glyphCount()) {
+                visitRun(context, &it.font(), it.glyphs(), xforms.data(), it.glyphCount(), &transform);
+            }
+        }
+    };
+    auto visit = [&](const SkSVGNode* node, const SkSVGRenderContext& ctx, size_t) {
+        forEachChild(node, [&](const SkSVGNode* child) {
+            if (child != text) {
+                return;
+            }
+            TextBlobRecordingCanvas<decltype(visitBlob)> canvas(ctx.canvas(), visitBlob);
+            SkSVGRenderContext textContext(ctx, &canvas);
+            text->render(textContext);
+        });
+    };
+    visitAll(self->root(), self->context(), 0, visit);
+}
+
+namespace {
+    // Appends the text it shapes to `text`, so that the text of text nodes can be read after
+    // SkSVGTextContext processed its white space.
+    class TextRecordingShaper final : public SkShaper {
+        public:
+            TextRecordingShaper(std::unique_ptr<SkShaper> shaper, SkString* text)
+                : fShaper(std::move(shaper)), fText(text) {}
+
+            void shape(const char* utf8, size_t utf8Bytes, const SkFont& font, bool leftToRight,
+                       SkScalar width, RunHandler* handler) const override {
+                fText->append(utf8, utf8Bytes);
+                fShaper->shape(utf8, utf8Bytes, font, leftToRight, width, handler);
+            }
+
+            void shape(const char* utf8, size_t utf8Bytes, FontRunIterator& font,
+                       BiDiRunIterator& bidi, ScriptRunIterator& script,
+                       LanguageRunIterator& language, SkScalar width,
+                       RunHandler* handler) const override {
+                fText->append(utf8, utf8Bytes);
+                fShaper->shape(utf8, utf8Bytes, font, bidi, script, language, width, handler);
+            }
+
+            void shape(const char* utf8, size_t utf8Bytes, FontRunIterator& font,
+                       BiDiRunIterator& bidi, ScriptRunIterator& script,
+                       LanguageRunIterator& language, const Feature* features,
+                       size_t featuresSize, SkScalar width, RunHandler* handler) const override {
+                fText->append(utf8, utf8Bytes);
+                fShaper->shape(utf8, utf8Bytes, font, bidi, script, language, features,
+                               featuresSize, width, handler);
+            }
+
+        private:
+            std::unique_ptr<SkShaper> fShaper;
+            SkString* fText;
+    };
+
+    class TextRecordingFactory final : public SkShapers::Factory {
+        public:
+            explicit TextRecordingFactory(SkString* text)
+                : fFactory(SkShapers::Primitive::Factory()), fText(text) {}
+
+            std::unique_ptr<SkShaper> makeShaper(sk_sp<SkFontMgr> fontMgr) override {
+                return std::make_unique<TextRecordingShaper>(fFactory->makeShaper(fontMgr), fText);
+            }
+
+            std::unique_ptr<SkShaper::BiDiRunIterator> makeBidiRunIterator(
+                const char* utf8, size_t utf8Bytes, uint8_t bidiLevel) override {
+                return fFactory->makeBidiRunIterator(utf8, utf8Bytes, bidiLevel);
+            }
+
+            std::unique_ptr<SkShaper::ScriptRunIterator> makeScriptRunIterator(
+                const char* utf8, size_t utf8Bytes, SkFourByteTag script) override {
+                return fFactory->makeScriptRunIterator(utf8, utf8Bytes, script);
+            }
+
+            SkUnicode* getUnicode() override {
+                return fFactory->getUnicode();
+            }
+
+        private:
+            sk_sp<SkShapers::Factory> fFactory;
+            SkString* fText;
+    };
+}
+
+// SkSVGTextContainer does not expose its children, so the text is read by shaping it, like
+// SkSVGText does when it is rendered.
+extern "C" void C_SkSVGTextContainer_textContent(const SkSVGTextContainer* self, SkString* text) {
+    auto container = sk_ref_sp(const_cast<SkSVGTextContainer*>(self));
+    sk_sp<SkSVGNode> root = container;
+    if (self->tag() != SkSVGTag::kText) {
+        // Only text elements shape their content.
+        root = SkSVGText::Make();
+        root->appendChild(container);
+    }
+
+    auto fontMgr = SkFontMgr::RefEmpty();
+    sk_sp<skresources::ResourceProvider> resourceProvider;
+    SkSVGIDMapper ids;
+    sk_sp<SkShapers::Factory> factory = sk_make_sp<TextRecordingFactory>(text);
+    SkNoDrawCanvas canvas(0, 0);
+    SkSVGLengthContext lengthContext(SkSize::Make(0, 0));
+    SkSVGPresentationContext presentationContext;
+    SkSVGRenderContext ctx(&canvas, fontMgr, resourceProvider, ids, lengthContext,
+                           presentationContext, {nullptr, nullptr}, factory);
+    root->render(ctx);
+}
+
+extern "C" void C_SkSVGDOM_visitAttributes(
+    const SkSVGDOM* self,
+    SkStream& stream,
+    void* context,
+    void (*visitElement)(void* context, SkSVGNode* node, const SkString* names, const SkString* values, size_t count))
+{
+    SkDOM xml;
+    auto root = xml.build(stream);
+    if (!root) {
+        return;
+    }
+    auto visit = [&](const SkSVGNode* node, const std::vector<SkString>& names, const std::vector<SkString>& values) {
+        visitElement(context, const_cast<SkSVGNode*>(node), names.data(), values.data(), names.size());
+    };
+    visitAttributes(const_cast<SkSVGDOM*>(self), xml, root, self->getRoot(), visit);
+}
+
 extern "C" void C_SkSVGIRI_destruct(SkSVGIRI* self) {
     self->~SkSVGIRI();
 }
@@ -5,7 +5,9 @@ use std::{
     mem, ptr, result,
 };
 
-use skia_bindings::{self as sb, SkCodec, SkCodec_FrameInfo, SkCodec_Options};
+use skia_bindings::{
+    self as sb, RustCodec_Param, SkCodec, SkCodec_FrameInfo, SkCodec_Options, TraitObject,
+};
 
 use super::codec_animation;
 use crate::{
@@ -90,7 +92,58 @@ impl fmt::Debug for Codec<'_> {
     }
 }
 
+/// A trait for implementing [`Codec`]s in Rust.
+///
+/// Rust codecs decode each frame independently into unpremultiplied RGBA 8888 pixels in the sRGB
+/// color space. Skia converts them to the color type, alpha type and color space that is
+/// requested from the [`Codec`].
+pub trait CodecImpl: Send {
+    /// The dimensions of the encoded image, before applying the [`Self::origin()`].
+    fn dimensions(&self) -> ISize;
+
+    /// Returns `true` if all pixels are opaque.
+    fn is_opaque(&self) -> bool {
+        false
+    }
+
+    /// The [`EncodedImageFormat`] Skia reports for the codec.
+    fn encoded_format(&self) -> EncodedImageFormat;
+
+    fn origin(&self) -> EncodedOrigin {
+        EncodedOrigin::TopLeft
+    }
+
+    fn frame_count(&self) -> usize {
+        1
+    }
+
+    /// Returns information about the frame at `index`. The `required_frame` is ignored, because
+    /// frames are decoded independently.
+    fn frame_info(&self, _index: usize) -> Option<FrameInfo> {
+        None
+    }
+
+    /// The number of times to repeat the animation after the first play, `None` if the animation
+    /// repeats forever.
+    fn repetition_count(&self) -> Option<usize> {
+        Some(0)
+    }
+
+    /// Decodes the fully composited frame at `frame_index` into `pixels`.
+    ///
+    /// `pixels` has the [`Self::dimensions()`], a stride of `row_bytes` and is laid out as
+    /// unpremultiplied RGBA 8888. Return [`Result::IncompleteInput`] if only parts of the frame
+    /// could be decoded.
+    fn get_pixels(&mut self, frame_index: usize, pixels: &mut [u8], row_bytes: usize) -> Result;
+}
+
 impl Codec<'_> {
+    /// Creates a [`Codec`] that is implemented in Rust.
+    pub fn new(codec: impl CodecImpl + 'static) -> Codec<'static> {
+        let param = codec_param(Box::new(codec));
+        Codec::from_ptr(unsafe { sb::C_RustCodec_New(&param) }).unwrap()
+    }
+
     pub fn from_stream<'a, T: io::Read + io::Seek>(
         stream: &'a mut T,
         decoders: &[codecs::Decoder],
@@ -378,8 +431,6 @@ impl Codec<'_> {
         }
     }
 
-    // TODO: Register
-
     fn native(&self) -> &SkCodec {
         self.inner.native()
     }
@@ -396,12 +447,87 @@ impl Codec<'_> {
     }
 }
 
+fn codec_param(codec: Box<dyn CodecImpl>) -> RustCodec_Param {
+    let dimensions = codec.dimensions();
+    return RustCodec_Param {
+        width: dimensions.width,
+        height: dimensions.height,
+        opaque: codec.is_opaque(),
+        encodedFormat: codec.encoded_format(),
+        origin: codec.origin().into_native(),
+        trait_: unsafe { mem::transmute::<Box<dyn CodecImpl>, TraitObject>(codec) },
+        drop: Some(drop),
+        onGetPixels: Some(on_get_pixels),
+        onGetFrameCount: Some(on_get_frame_count),
+        onGetFrameInfo: Some(on_get_frame_info),
+        onGetRepetitionCount: Some(on_get_repetition_count),
+    };
+
+    extern "C" fn drop(codec: TraitObject) {
+        mem::drop(unsafe { mem::transmute::<TraitObject, Box<dyn CodecImpl>>(codec) });
+    }
+
+    extern "C" fn on_get_pixels(
+        codec: TraitObject,
+        frame_index: i32,
+        pixels: *mut ffi::c_void,
+        row_bytes: usize,
+    ) -> Result {
+        let codec = unsafe { codec_mut(codec) };
+        let Ok(frame_index) = frame_index.try_into() else {
+            return Result::InvalidParameters;
+        };
+        let height: usize = codec.dimensions().height.try_into().unwrap();
+        let pixels = unsafe { safer::from_raw_parts_mut(pixels as *mut u8, row_bytes * height) };
+        codec.get_pixels(frame_index, pixels, row_bytes)
+    }
+
+    extern "C" fn on_get_frame_count(codec: TraitObject) -> i32 {
+        unsafe { codec_mut(codec) }
+            .frame_count()
+            .try_into()
+            .unwrap()
+    }
+
+    extern "C" fn on_get_frame_info(
+        codec: TraitObject,
+        index: i32,
+        info: *mut SkCodec_FrameInfo,
+    ) -> bool {
+        let Ok(index) = index.try_into() else {
+            return false;
+        };
+        match unsafe { codec_mut(codec) }.frame_info(index) {
+            Some(frame_info) => {
+                unsafe { *info = frame_info.into_native() };
+                true
+            }
+            None => false,
+        }
+    }
+
+    extern "C" fn on_get_repetition_count(codec: TraitObject) -> i32 {
+        const REPETITION_COUNT_INFINITE: i32 = -1;
+        unsafe { codec_mut(codec) }
+            .repetition_count()
+            .map(|count| count.try_into().unwrap())
+            .unwrap_or(REPETITION_COUNT_INFINITE)
+    }
+
+    unsafe fn codec_mut<'a>(codec: TraitObject) -> &'a mut dyn CodecImpl {
+        mem::transmute(codec)
+    }
+}
+
 pub mod codecs {
-    use std::{fmt, io, ptr, result, str};
+    use std::{
+        ffi, fmt, io, ptr, result, str,
+        sync::{Arc, Once, RwLock},
+    };
 
-    use skia_bindings::{self as sb, SkCodecs_Decoder};
+    use skia_bindings::{self as sb, RustCodec_Param, SkCodecs_Decoder};
 
-    use super::Result;
+    use super::{CodecImpl, Result};
     use crate::{interop::RustStream, prelude::*, AlphaType, Codec, Image};
 
     pub type Decoder = Handle<SkCodecs_Decoder>;
@@ -464,7 +590,72 @@ pub mod codecs {
         }
     }
 
-    // TODO: wrap Register()
+    /// A trait for implementing decoders in Rust that can be registered with [`register()`].
+    pub trait DecoderImpl: Send + Sync {
+        /// A unique id of the decoder. Registering a decoder with the id of a decoder that is
+        /// already registered replaces it.
+        fn id(&self) -> &str;
+
+        /// Returns `true` if the decoder can decode `data`. `data` contains at least the first bytes
+        /// of the encoded image, but may not contain all of it.
+        fn is_format(&self, data: &[u8]) -> bool;
+
+        /// Creates a codec that decodes the encoded image in `stream`.
+        fn from_stream(
+            &self,
+            stream: &mut dyn io::Read,
+        ) -> result::Result<Box<dyn CodecImpl>, Result>;
+    }
+
+    static DECODERS: RwLock<Vec<Arc<dyn DecoderImpl>>> = RwLock::new(Vec::new());
+    static REGISTER: Once = Once::new();
+
+    /// Registers a decoder that is implemented in Rust globally.
+    ///
+    /// Registered decoders are used when Skia decodes images, for example by
+    /// [`Image::from_encoded()`], [`Codec::from_data()`], or the images in SVG documents. They are
+    /// consulted in registration order and only after Skia's built-in decoders.
+    pub fn register(decoder: impl DecoderImpl + 'static) {
+        {
+            let mut decoders = DECODERS.write().unwrap();
+            match decoders.iter_mut().find(|d| d.id() == decoder.id()) {
+                Some(registered) => *registered = Arc::new(decoder),
+                None => decoders.push(Arc::new(decoder)),
+            }
+        }
+
+        REGISTER.call_once(|| unsafe {
+            sb::C_SkCodecs_RegisterRust(Some(is_format), Some(make_codec))
+        });
+
+        extern "C" fn is_format(data: *const ffi::c_void, length: usize) -> bool {
+            let data = unsafe { safer::from_raw_parts(data as *const u8, length) };
+            registered().iter().any(|d| d.is_format(data))
+        }
+
+        extern "C" fn make_codec(
+            data: *const ffi::c_void,
+            length: usize,
+            param: *mut RustCodec_Param,
+        ) -> Result {
+            let data = unsafe { safer::from_raw_parts(data as *const u8, length) };
+            let Some(decoder) = registered().into_iter().find(|d| d.is_format(data)) else {
+                return Result::Unimplemented;
+            };
+            match decoder.from_stream(&mut &data[..]) {
+                Ok(codec) => {
+                    unsafe { param.write(super::codec_param(codec)) };
+                    Result::Success
+                }
+                Err(result) => result,
+            }
+        }
+
+        // The decoders are called without holding the lock, so that they can register decoders.
+        fn registered() -> Vec<Arc<dyn DecoderImpl>> {
+            DECODERS.read().unwrap().clone()
+        }
+    }
 
     pub fn deferred_image(
         codec: Codec<'_>,
@@ -479,3 +670,100 @@ pub mod codecs {
         .map(|h| unsafe { Borrows::unchecked_new(h) })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{io, result};
+
+    use super::{codecs, Codec, CodecImpl, EncodedImageFormat, Result};
+    use crate::{surfaces, AlphaType, Color, ColorType, ISize, Image, ImageInfo};
+
+    /// A test format that encodes a single color: `b"SOLID"`, width, height, and RGBA.
+    struct Solid {
+        size: ISize,
+        rgba: [u8; 4],
+    }
+
+    impl CodecImpl for Solid {
+        fn dimensions(&self) -> ISize {
+            self.size
+        }
+
+        fn encoded_format(&self) -> EncodedImageFormat {
+            EncodedImageFormat::PNG
+        }
+
+        fn get_pixels(
+            &mut self,
+            _frame_index: usize,
+            pixels: &mut [u8],
+            row_bytes: usize,
+        ) -> Result {
+            for row in pixels.chunks_mut(row_bytes) {
+                for pixel in row[..self.size.width as usize * 4].chunks_mut(4) {
+                    pixel.copy_from_slice(&self.rgba);
+                }
+            }
+            Result::Success
+        }
+    }
+
+    struct SolidDecoder;
+
+    impl codecs::DecoderImpl for SolidDecoder {
+        fn id(&self) -> &str {
+            "solid"
+        }
+
+        fn is_format(&self, data: &[u8]) -> bool {
+            data.starts_with(b"SOLID")
+        }
+
+        fn from_stream(
+            &self,
+            stream: &mut dyn io::Read,
+        ) -> result::Result<Box<dyn CodecImpl>, Result> {
+            let mut data = [0u8; 11];
+            stream
+                .read_exact(&mut data)
+                .map_err(|_| Result::IncompleteInput)?;
+            Ok(Box::new(Solid {
+                size: ISize::new(data[5].into(), data[6].into()),
+                rgba: data[7..].try_into().unwrap(),
+            }))
+        }
+    }
+
+    const ENCODED: &[u8] = b"SOLID\x03\x02\x00\x00\xff\xff";
+
+    #[test]
+    fn rust_codec_decodes_pixels() {
+        let mut codec = Codec::new(Solid {
+            size: ISize::new(3, 2),
+            rgba: [0, 0, 0xff, 0xff],
+        });
+        assert_eq!(codec.dimensions(), ISize::new(3, 2));
+        assert_eq!(codec.get_frame_count(), 1);
+        let info = ImageInfo::new((3, 2), ColorType::BGRA8888, AlphaType::Premul, None);
+        let mut pixels = vec![0u8; info.compute_min_byte_size()];
+        assert_eq!(
+            codec.get_pixels_with_options(&info, &mut pixels, info.min_row_bytes(), None),
+            Result::Success
+        );
+        assert_eq!(&pixels[..4], &[0xff, 0, 0, 0xff]);
+    }
+
+    #[test]
+    fn registered_decoder_is_used_by_skia() {
+        codecs::register(SolidDecoder);
+
+        let codec = Codec::from_data(crate::Data::new_copy(ENCODED)).unwrap();
+        assert_eq!(codec.dimensions(), ISize::new(3, 2));
+
+        let image = Image::from_encoded(crate::Data::new_copy(ENCODED)).unwrap();
+        let mut surface = surfaces::raster_n32_premul((3, 2)).unwrap();
+        surface.canvas().draw_image(&image, (0, 0), None);
+        let pixmap = surface.peek_pixels().unwrap();
+        assert_eq!(pixmap.get_color((2, 1)), Color::BLUE);
+    }
+}
@@ -42,8 +42,9 @@ mod image_info;
 mod m44;
 mod mask_filter;
 pub mod matrix;
-mod mesh;
+pub mod mesh;
 mod milestone;
+mod open_type_svg_decoder;
 pub mod paint;
 pub mod path;
 mod path_builder;
@@ -64,6 +65,7 @@ pub mod rrect;
 mod rsxform;
 pub mod sampling_options;
 mod scalar_;
+mod serial_procs;
 pub mod shader;
 mod size;
 pub mod stroke_rec;
@@ -119,7 +121,9 @@ pub use image_info::*;
 pub use m44::*;
 pub use mask_filter::*;
 pub use matrix::Matrix;
+pub use mesh::{meshes, Mesh, MeshSpecification};
 pub use milestone::*;
+pub use open_type_svg_decoder::OpenTypeSVGDecoder;
 pub use paint::Paint;
 pub use tile_mode::*;
 // We keep these around for the time being.
@@ -149,6 +153,7 @@ pub use sampling_options::{
     CubicResampler, FilterMode, FilterOptions, MipmapMode, SamplingMode, SamplingOptions,
 };
 pub use scalar_::*;
+pub use serial_procs::*;
 pub use shader::Shader;
 pub use size::*;
 pub use stroke_rec::StrokeRec;
@@ -9,10 +9,11 @@ use skia_bindings::{
 #[cfg(feature = "gpu")]
 use crate::gpu;
 use crate::{
-    prelude::*, scalar, Bitmap, BlendMode, ClipOp, Color, Color4f, Data, Drawable, FilterMode,
-    Font, GlyphId, IPoint, IRect, ISize, Image, ImageFilter, ImageInfo, Matrix, Paint, Path,
-    Picture, Pixmap, Point, QuickReject, RRect, RSXform, Rect, Region, SamplingOptions, Shader,
-    Surface, SurfaceProps, TextBlob, TextEncoding, TileMode, Vector, Vertices, M44,
+    prelude::*, scalar, Bitmap, BlendMode, Blender, ClipOp, Color, Color4f, Data, Drawable,
+    FilterMode, Font, GlyphId, IPoint, IRect, ISize, Image, ImageFilter, ImageInfo, Matrix, Mesh,
+    Paint, Path, Picture, Pixmap, Point, QuickReject, RRect, RSXform, Rect, Region,
+    SamplingOptions, Shader, Surface, SurfaceProps, TextBlob, TextEncoding, TileMode, Vector,
+    Vertices, M44,
 };
 use crate::{Arc, ColorSpace};
 
@@ -2010,6 +2011,37 @@ impl Canvas {
         self
     }
 
+    /// Draws a [`Mesh`] using the current clip and matrix.
+    ///
+    /// If the mesh's specification outputs a color, `blender` combines it (as dst) with the
+    /// [`Shader`] or color of `paint` (as src). `blender` defaults to [`BlendMode::Modulate`].
+    ///
+    /// The local coordinates returned by the mesh's fragment program are used to evaluate the
+    /// [`Shader`] of `paint`.
+    ///
+    /// [`crate::MaskFilter`], [`crate::PathEffect`], and antialiasing on [`Paint`] are ignored.
+    ///
+    /// - `mesh` the mesh vertices and compatible specification.
+    /// - `blender` combines vertices colors with [`Shader`] if present or [`Paint`] opaque color
+    ///   if not. Ignored if the mesh's specification does not output a color.
+    /// - `paint` specifies the [`Shader`], used as [`Mesh`] texture, and [`crate::ColorFilter`].
+    pub fn draw_mesh(
+        &self,
+        mesh: &Mesh,
+        blender: impl Into<Option<Blender>>,
+        paint: &Paint,
+    ) -> &Self {
+        unsafe {
+            sb::C_SkCanvas_drawMesh(
+                self.native_mut(),
+                mesh.native(),
+                blender.into().into_ptr_or_null(),
+                paint.native(),
+            )
+        }
+        self
+    }
+
     /// Draws a Coons patch: the interpolation of four cubics with shared corners,
     /// associating a color, and optionally a texture [`Point`], with each corner.
     ///
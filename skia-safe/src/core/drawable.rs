@@ -1,6 +1,9 @@
-use std::fmt;
+use std::{fmt, mem, ptr};
 
-use skia_bindings::{self as sb, SkDrawable, SkFlattenable, SkRefCntBase};
+use skia_bindings::{
+    self as sb, RustDrawable_Param, SkCanvas, SkDrawable, SkFlattenable, SkPicture, SkRect,
+    SkRefCntBase, TraitObject,
+};
 
 use crate::{prelude::*, Canvas, Matrix, NativeFlattenable, Picture, Point, Rect};
 
@@ -30,7 +33,82 @@ impl fmt::Debug for Drawable {
     }
 }
 
+/// A trait for implementing [`Drawable`]s in Rust.
+///
+/// A [`Drawable`] created with [`Drawable::new()`] forwards all drawing requests to the
+/// implementation, even when it is played back as part of a [`Picture`] that was recorded with
+/// [`crate::PictureRecorder::finish_recording_as_drawable()`].
+pub trait DrawableImpl {
+    /// Draws the contents into the canvas. The canvas' matrix and clip are already set up by the
+    /// caller.
+    fn on_draw(&mut self, canvas: &Canvas);
+
+    /// Returns the (conservative) bounds of what the drawable will draw.
+    ///
+    /// If the drawable can change what it draws (e.g. animation or in response to some external
+    /// change), then this must return a bounds that is always valid for all possible states.
+    fn on_get_bounds(&mut self) -> Rect;
+
+    /// Returns an approximation of the memory used by the drawable.
+    fn on_approximate_bytes_used(&mut self) -> usize {
+        0
+    }
+
+    /// Returns a picture that represents the current state of the drawable.
+    ///
+    /// If `None` is returned, Skia records the output of [`Self::on_draw()`] instead.
+    fn on_make_picture_snapshot(&mut self) -> Option<Picture> {
+        None
+    }
+}
+
 impl Drawable {
+    /// Creates a [`Drawable`] that is implemented in Rust.
+    pub fn new(drawable: impl DrawableImpl + 'static) -> Drawable {
+        let drawable: Box<dyn DrawableImpl> = Box::new(drawable);
+
+        let param = RustDrawable_Param {
+            trait_: unsafe { mem::transmute::<Box<dyn DrawableImpl>, TraitObject>(drawable) },
+            drop: Some(drop),
+            onDraw: Some(on_draw),
+            onGetBounds: Some(on_get_bounds),
+            onApproximateBytesUsed: Some(on_approximate_bytes_used),
+            onMakePictureSnapshot: Some(on_make_picture_snapshot),
+        };
+
+        return Drawable::from_ptr(unsafe { sb::C_RustDrawable_New(&param) } as *mut SkDrawable)
+            .unwrap();
+
+        extern "C" fn drop(drawable: TraitObject) {
+            mem::drop(unsafe { mem::transmute::<TraitObject, Box<dyn DrawableImpl>>(drawable) });
+        }
+
+        extern "C" fn on_draw(drawable: TraitObject, canvas: *mut SkCanvas) {
+            let canvas = Canvas::borrow_from_native(unsafe { &*canvas });
+            unsafe { drawable_mut(drawable) }.on_draw(canvas)
+        }
+
+        extern "C" fn on_get_bounds(drawable: TraitObject, bounds: *mut SkRect) {
+            let r = unsafe { drawable_mut(drawable) }.on_get_bounds();
+            unsafe { *bounds = r.into_native() }
+        }
+
+        extern "C" fn on_approximate_bytes_used(drawable: TraitObject) -> usize {
+            unsafe { drawable_mut(drawable) }.on_approximate_bytes_used()
+        }
+
+        extern "C" fn on_make_picture_snapshot(drawable: TraitObject) -> *mut SkPicture {
+            unsafe { drawable_mut(drawable) }
+                .on_make_picture_snapshot()
+                .map(|picture| picture.into_ptr())
+                .unwrap_or(ptr::null_mut())
+        }
+
+        unsafe fn drawable_mut<'a>(drawable: TraitObject) -> &'a mut dyn DrawableImpl {
+            mem::transmute(drawable)
+        }
+    }
+
     pub fn draw(&mut self, canvas: &Canvas, matrix: Option<&Matrix>) {
         unsafe {
             self.native_mut()
@@ -121,3 +199,80 @@ pub mod gpu_draw_handler {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, rc::Rc};
+
+    use super::DrawableImpl;
+    use crate::{surfaces, Canvas, Color, Drawable, Paint, PictureRecorder, Rect};
+
+    struct Square {
+        color: Color,
+        draws: Rc<Cell<usize>>,
+    }
+
+    impl DrawableImpl for Square {
+        fn on_draw(&mut self, canvas: &Canvas) {
+            self.draws.set(self.draws.get() + 1);
+            canvas.draw_rect(
+                self.on_get_bounds(),
+                &Paint::default().set_color(self.color),
+            );
+        }
+
+        fn on_get_bounds(&mut self) -> Rect {
+            Rect::from_xywh(0.0, 0.0, 10.0, 10.0)
+        }
+    }
+
+    #[test]
+    fn rust_drawable_draws_and_reports_bounds() {
+        let draws = Rc::new(Cell::new(0));
+        let mut drawable = Drawable::new(Square {
+            color: Color::RED,
+            draws: draws.clone(),
+        });
+        assert_eq!(drawable.bounds(), Rect::from_xywh(0.0, 0.0, 10.0, 10.0));
+        assert_eq!(drawable.approximate_bytes_used(), 0);
+
+        let mut surface = surfaces::raster_n32_premul((20, 20)).unwrap();
+        surface.canvas().draw_drawable(&mut drawable, None);
+        assert_eq!(draws.get(), 1);
+        let pixmap = surface.peek_pixels().unwrap();
+        assert_eq!(pixmap.get_color((5, 5)), Color::RED);
+        assert_eq!(pixmap.get_color((15, 15)), Color::TRANSPARENT);
+    }
+
+    #[test]
+    fn rust_drawable_is_called_back_at_playback_time() {
+        let draws = Rc::new(Cell::new(0));
+        let mut drawable = Drawable::new(Square {
+            color: Color::BLUE,
+            draws: draws.clone(),
+        });
+
+        let mut recorder = PictureRecorder::new();
+        let canvas = recorder.begin_recording(Rect::from_wh(20.0, 20.0), None);
+        canvas.draw_drawable(&mut drawable, None);
+        let mut recorded = recorder.finish_recording_as_drawable().unwrap();
+        assert_eq!(draws.get(), 0);
+
+        let mut surface = surfaces::raster_n32_premul((20, 20)).unwrap();
+        recorded.draw(surface.canvas(), None);
+        recorded.draw(surface.canvas(), None);
+        assert_eq!(draws.get(), 2);
+    }
+
+    #[test]
+    fn rust_drawable_is_dropped_with_its_drawable() {
+        let draws = Rc::new(Cell::new(0));
+        let drawable = Drawable::new(Square {
+            color: Color::RED,
+            draws: draws.clone(),
+        });
+        assert_eq!(Rc::strong_count(&draws), 2);
+        drop(drawable);
+        assert_eq!(Rc::strong_count(&draws), 1);
+    }
+}
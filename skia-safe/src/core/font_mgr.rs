@@ -1,12 +1,23 @@
-use skia_bindings::{self as sb, SkFontMgr, SkFontStyleSet, SkRefCntBase};
-use std::{ffi::CString, fmt, mem, os::raw::c_char, ptr};
+use skia_bindings::{
+    self as sb, RustFontMgr_Param, SkData, SkFontMgr, SkFontStyle, SkFontStyleSet, SkRefCntBase,
+    SkString, SkTypeface, TraitObject,
+};
+use std::{
+    ffi::{CStr, CString},
+    fmt, mem,
+    os::raw::{c_char, c_int},
+    ptr,
+};
 
 use crate::{
-    interop::{self, DynamicMemoryWStream},
+    interop::{self, DynamicMemoryWStream, SetStr, StreamAsset},
     prelude::*,
-    FontStyle, Typeface, Unichar,
+    Data, FontStyle, Typeface, Unichar,
 };
 
+mod builder;
+pub use builder::*;
+
 pub type FontStyleSet = RCHandle<SkFontStyleSet>;
 
 impl NativeBase<SkRefCntBase> for SkFontStyleSet {}
@@ -79,9 +90,23 @@ impl FontStyleSet {
     pub fn new_empty() -> Self {
         FontStyleSet::from_ptr(unsafe { sb::C_SkFontStyleSet_CreateEmpty() }).unwrap()
     }
+
+    /// Creates a style set of the given typefaces.
+    ///
+    /// The styles are reported in the order of the typefaces and have no names.
+    /// [`Self::match_style()`] picks the closest style by the CSS3 font matching rules.
+    pub fn from_typefaces(typefaces: impl IntoIterator<Item = Typeface>) -> Self {
+        let mut typefaces: Vec<*mut SkTypeface> =
+            typefaces.into_iter().map(|t| t.into_ptr()).collect();
+        FontStyleSet::from_ptr(unsafe {
+            sb::C_SkFontStyleSet_MakeFromTypefaces(typefaces.as_mut_ptr(), typefaces.len())
+        })
+        .unwrap()
+    }
 }
 
 pub type FontMgr = RCHandle<SkFontMgr>;
+unsafe_send_sync!(FontMgr);
 
 impl NativeBase<SkRefCntBase> for SkFontMgr {}
 
@@ -115,6 +140,34 @@ impl FontMgr {
         FontMgr::from_ptr(unsafe { sb::C_SkFontMgr_RefEmpty() }).unwrap()
     }
 
+    /// Creates a FreeType based font manager with the fonts found in `dir` and its
+    /// subdirectories.
+    ///
+    /// Returns `None` if the font manager is not available on the target platform. See
+    /// [`FontMgrBuilder`] for a font manager that is available everywhere and does not depend
+    /// on the order in which the file system lists the fonts.
+    pub fn new_custom_directory(dir: impl AsRef<std::path::Path>) -> Option<Self> {
+        let dir = CString::new(dir.as_ref().to_str()?).ok()?;
+        FontMgr::from_ptr(unsafe { sb::C_SkFontMgr_NewCustomDirectory(dir.as_ptr()) })
+    }
+
+    /// Creates a FreeType based font manager with the fonts in `data`, for example fonts that
+    /// are embedded with [`include_bytes!`].
+    ///
+    /// Returns `None` if the font manager is not available on the target platform.
+    pub fn new_custom_data(data: impl IntoIterator<Item = Data>) -> Option<Self> {
+        let mut data: Vec<*mut SkData> = data.into_iter().map(|d| d.into_ptr()).collect();
+        FontMgr::from_ptr(unsafe { sb::C_SkFontMgr_NewCustomData(data.as_mut_ptr(), data.len()) })
+    }
+
+    /// Creates a FreeType based font manager without fonts that can create typefaces from font
+    /// data.
+    ///
+    /// Returns `None` if the font manager is not available on the target platform.
+    pub fn new_custom_empty() -> Option<Self> {
+        FontMgr::from_ptr(unsafe { sb::C_SkFontMgr_NewCustomEmpty() })
+    }
+
     pub fn count_families(&self) -> usize {
         unsafe { self.native().countFamilies().try_into().unwrap() }
     }
@@ -217,7 +270,30 @@ impl FontMgr {
         ttc_index: impl Into<Option<usize>>,
     ) -> Option<Typeface> {
         let mut stream = DynamicMemoryWStream::from_bytes(bytes);
-        let mut stream = stream.detach_as_stream();
+        self.new_from_stream(stream.detach_as_stream(), ttc_index)
+    }
+
+    /// Creates a typeface from the font at `ttc_index` in `data`.
+    ///
+    /// In contrast to [`Self::new_from_data()`], the data is not copied, so all the fonts of a
+    /// collection can share the same [`Data`].
+    pub fn new_from_shared_data(
+        &self,
+        data: &Data,
+        ttc_index: impl Into<Option<usize>>,
+    ) -> Option<Typeface> {
+        let stream = StreamAsset::from_ptr(unsafe {
+            sb::C_SkMemoryStream_MakeFromData(data.clone().into_ptr())
+        })
+        .unwrap();
+        self.new_from_stream(stream, ttc_index)
+    }
+
+    fn new_from_stream(
+        &self,
+        mut stream: StreamAsset,
+        ttc_index: impl Into<Option<usize>>,
+    ) -> Option<Typeface> {
         Typeface::from_ptr(unsafe {
             let stream_ptr = stream.native_mut() as *mut _;
             // makeFromStream takes ownership of the stream, so don't drop it.
@@ -247,13 +323,217 @@ impl FontMgr {
             )
         })
     }
+}
+
+/// A trait for implementing [`FontMgr`]s in Rust.
+///
+/// A [`FontMgr`] created with [`FontMgr::from_impl()`] can be used wherever Skia asks a font
+/// manager for typefaces, for example in [`crate::textlayout::FontCollection`] or `svg::Dom`, so
+/// that family matching and fallback decisions are made by the implementation.
+///
+/// Font data that Skia loads from streams or files is read into memory and passed to
+/// [`Self::make_from_data()`]. Variation and palette arguments are ignored.
+///
+/// Skia may call the font manager from any thread.
+pub trait FontMgrImpl: Send + Sync {
+    /// Returns the number of font families.
+    fn count_families(&self) -> usize;
+
+    /// Returns the name of the family at `index`, which is less than [`Self::count_families()`].
+    fn family_name(&self, index: usize) -> String;
+
+    /// Returns the styles of the family at `index`, see [`FontStyleSet::from_typefaces()`].
+    fn new_style_set(&self, index: usize) -> FontStyleSet;
+
+    /// Returns the styles of the family named `family_name`.
+    ///
+    /// The default implementation returns the style set of the family with exactly that name, or
+    /// an empty set if there is none.
+    fn match_family(&self, family_name: &str) -> FontStyleSet {
+        (0..self.count_families())
+            .find(|&i| self.family_name(i) == family_name)
+            .map(|i| self.new_style_set(i))
+            .unwrap_or_default()
+    }
+
+    /// Returns the typeface of the family named `family_name` that is closest to `style`.
+    ///
+    /// `family_name` is `None` if Skia asks for the default family.
+    fn match_family_style(&self, family_name: Option<&str>, style: FontStyle) -> Option<Typeface>;
+
+    /// Returns a typeface that can render `character`, used to find fallback fonts.
+    ///
+    /// `bcp_47` contains BCP 47 language tags. The first tag is the least and the last tag is the
+    /// most significant. If none of them matches, any typeface that supports `character` may be
+    /// returned.
+    fn match_family_style_character(
+        &self,
+        family_name: Option<&str>,
+        style: FontStyle,
+        bcp_47: &[&str],
+        character: Unichar,
+    ) -> Option<Typeface>;
 
-    // TODO: makeFromStream(.., ttcIndex).
+    /// Creates a typeface from font data. `ttc_index` selects the font in a font collection.
+    fn make_from_data(&self, data: Data, ttc_index: usize) -> Option<Typeface>;
+
+    /// Returns a typeface for the legacy API that matches `family_name` and `style`.
+    ///
+    /// The default implementation forwards to [`Self::match_family_style()`].
+    fn legacy_make_typeface(
+        &self,
+        family_name: Option<&str>,
+        style: FontStyle,
+    ) -> Option<Typeface> {
+        self.match_family_style(family_name, style)
+    }
+}
+
+impl FontMgr {
+    /// Creates a [`FontMgr`] that is implemented in Rust.
+    pub fn from_impl(font_mgr: impl FontMgrImpl + 'static) -> FontMgr {
+        let font_mgr: Box<dyn FontMgrImpl> = Box::new(font_mgr);
+
+        let param = RustFontMgr_Param {
+            trait_: unsafe { mem::transmute::<Box<dyn FontMgrImpl>, TraitObject>(font_mgr) },
+            drop: Some(drop),
+            countFamilies: Some(count_families),
+            getFamilyName: Some(get_family_name),
+            createStyleSet: Some(create_style_set),
+            matchFamily: Some(match_family),
+            matchFamilyStyle: Some(match_family_style),
+            matchFamilyStyleCharacter: Some(match_family_style_character),
+            makeFromData: Some(make_from_data),
+            legacyMakeTypeface: Some(legacy_make_typeface),
+        };
+
+        return FontMgr::from_ptr(unsafe { sb::C_RustFontMgr_New(&param) }).unwrap();
+
+        extern "C" fn drop(font_mgr: TraitObject) {
+            mem::drop(unsafe { mem::transmute::<TraitObject, Box<dyn FontMgrImpl>>(font_mgr) });
+        }
+
+        extern "C" fn count_families(font_mgr: TraitObject) -> c_int {
+            unsafe { font_mgr_ref(font_mgr) }
+                .count_families()
+                .try_into()
+                .unwrap_or(c_int::MAX)
+        }
+
+        extern "C" fn get_family_name(
+            font_mgr: TraitObject,
+            index: c_int,
+            family_name: *mut SkString,
+        ) {
+            let font_mgr = unsafe { font_mgr_ref(font_mgr) };
+            let name = family_index(font_mgr, index)
+                .map(|index| font_mgr.family_name(index))
+                .unwrap_or_default();
+            unsafe { (*family_name).set_str(name) }
+        }
+
+        extern "C" fn create_style_set(font_mgr: TraitObject, index: c_int) -> *mut SkFontStyleSet {
+            let font_mgr = unsafe { font_mgr_ref(font_mgr) };
+            family_index(font_mgr, index)
+                .map(|index| font_mgr.new_style_set(index))
+                .unwrap_or_default()
+                .into_ptr()
+        }
+
+        extern "C" fn match_family(
+            font_mgr: TraitObject,
+            family_name: *const c_char,
+        ) -> *mut SkFontStyleSet {
+            let family_name = unsafe { family_name_from_ptr(family_name) };
+            unsafe { font_mgr_ref(font_mgr) }
+                .match_family(family_name.unwrap_or_default())
+                .into_ptr()
+        }
+
+        extern "C" fn match_family_style(
+            font_mgr: TraitObject,
+            family_name: *const c_char,
+            style: *const SkFontStyle,
+        ) -> *mut SkTypeface {
+            let family_name = unsafe { family_name_from_ptr(family_name) };
+            let style = *FontStyle::from_native_ref(unsafe { &*style });
+            unsafe { font_mgr_ref(font_mgr) }
+                .match_family_style(family_name, style)
+                .into_ptr_or_null()
+        }
+
+        extern "C" fn match_family_style_character(
+            font_mgr: TraitObject,
+            family_name: *const c_char,
+            style: *const SkFontStyle,
+            bcp_47: *mut *const c_char,
+            bcp_47_count: c_int,
+            character: Unichar,
+        ) -> *mut SkTypeface {
+            let family_name = unsafe { family_name_from_ptr(family_name) };
+            let style = *FontStyle::from_native_ref(unsafe { &*style });
+            let bcp_47: Vec<&str> =
+                unsafe { safer::from_raw_parts(bcp_47, bcp_47_count.try_into().unwrap_or(0)) }
+                    .iter()
+                    .filter_map(|&tag| unsafe { family_name_from_ptr(tag) })
+                    .collect();
+            unsafe { font_mgr_ref(font_mgr) }
+                .match_family_style_character(family_name, style, &bcp_47, character)
+                .into_ptr_or_null()
+        }
+
+        extern "C" fn make_from_data(
+            font_mgr: TraitObject,
+            data: *mut SkData,
+            ttc_index: c_int,
+        ) -> *mut SkTypeface {
+            let (Some(data), Ok(ttc_index)) = (Data::from_ptr(data), ttc_index.try_into()) else {
+                return ptr::null_mut();
+            };
+            unsafe { font_mgr_ref(font_mgr) }
+                .make_from_data(data, ttc_index)
+                .into_ptr_or_null()
+        }
+
+        extern "C" fn legacy_make_typeface(
+            font_mgr: TraitObject,
+            family_name: *const c_char,
+            style: *const SkFontStyle,
+        ) -> *mut SkTypeface {
+            let family_name = unsafe { family_name_from_ptr(family_name) };
+            let style = *FontStyle::from_native_ref(unsafe { &*style });
+            unsafe { font_mgr_ref(font_mgr) }
+                .legacy_make_typeface(family_name, style)
+                .into_ptr_or_null()
+        }
+
+        unsafe fn font_mgr_ref<'a>(font_mgr: TraitObject) -> &'a dyn FontMgrImpl {
+            mem::transmute(font_mgr)
+        }
+
+        // Indices Skia passes are not validated, out of range ones return empty results.
+        fn family_index(font_mgr: &dyn FontMgrImpl, index: c_int) -> Option<usize> {
+            usize::try_from(index)
+                .ok()
+                .filter(|&index| index < font_mgr.count_families())
+        }
+
+        // Skia passes null for the default family. Names that are not UTF-8 are ignored.
+        unsafe fn family_name_from_ptr<'a>(name: *const c_char) -> Option<&'a str> {
+            if name.is_null() {
+                return None;
+            }
+            CStr::from_ptr(name).to_str().ok()
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::FontMgr;
+    use std::sync::{Arc, Mutex};
+
+    use super::{FontMgrImpl, FontStyleSet};
+    use crate::{Data, FontMgr, FontStyle, Typeface, Unichar};
 
     #[test]
     #[serial_test::serial]
@@ -278,4 +558,115 @@ mod tests {
             }
         }
     }
+
+    struct OneFamily {
+        font_mgr: FontMgr,
+        typeface: Typeface,
+        fallback_tags: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl FontMgrImpl for OneFamily {
+        fn count_families(&self) -> usize {
+            1
+        }
+
+        fn family_name(&self, _index: usize) -> String {
+            "Rust Sans".into()
+        }
+
+        fn new_style_set(&self, _index: usize) -> FontStyleSet {
+            FontStyleSet::from_typefaces([self.typeface.clone()])
+        }
+
+        fn match_family_style(
+            &self,
+            family_name: Option<&str>,
+            _style: FontStyle,
+        ) -> Option<Typeface> {
+            matches!(family_name, None | Some("Rust Sans")).then(|| self.typeface.clone())
+        }
+
+        fn match_family_style_character(
+            &self,
+            _family_name: Option<&str>,
+            _style: FontStyle,
+            bcp_47: &[&str],
+            character: Unichar,
+        ) -> Option<Typeface> {
+            self.fallback_tags
+                .lock()
+                .unwrap()
+                .extend(bcp_47.iter().map(|tag| tag.to_string()));
+            (self.typeface.unichar_to_glyph(character) != 0).then(|| self.typeface.clone())
+        }
+
+        fn make_from_data(&self, data: Data, ttc_index: usize) -> Option<Typeface> {
+            self.font_mgr.new_from_data(data.as_bytes(), ttc_index)
+        }
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn font_mgr_implemented_in_rust() {
+        let system = FontMgr::new();
+        let typeface = system
+            .legacy_make_typeface(None, FontStyle::normal())
+            .expect("No default typeface");
+        let fallback_tags = Arc::new(Mutex::new(Vec::new()));
+        let font_mgr = FontMgr::from_impl(OneFamily {
+            font_mgr: system,
+            typeface: typeface.clone(),
+            fallback_tags: fallback_tags.clone(),
+        });
+
+        assert_eq!(font_mgr.family_names().collect::<Vec<_>>(), ["Rust Sans"]);
+        assert_eq!(font_mgr.new_style_set(0).count(), 1);
+        assert_eq!(font_mgr.match_family("Rust Sans").count(), 1);
+        assert_eq!(font_mgr.match_family("Other Sans").count(), 0);
+
+        let matched = font_mgr
+            .match_family_style("Rust Sans", FontStyle::bold())
+            .unwrap();
+        assert_eq!(matched.unique_id(), typeface.unique_id());
+        assert!(font_mgr
+            .match_family_style("Other Sans", FontStyle::normal())
+            .is_none());
+        assert!(font_mgr
+            .legacy_make_typeface(None, FontStyle::normal())
+            .is_some());
+
+        let fallback = font_mgr.match_family_style_character(
+            "",
+            FontStyle::normal(),
+            &["en", "en-US"],
+            'a' as Unichar,
+        );
+        assert!(fallback.is_some());
+        assert_eq!(*fallback_tags.lock().unwrap(), ["en", "en-US"]);
+
+        // `new_from_data()` passes a stream, which is read into memory.
+        let (data, ttc_index) = typeface.to_font_data().unwrap();
+        let loaded = font_mgr.new_from_data(&data, ttc_index).unwrap();
+        assert_eq!(loaded.family_name(), typeface.family_name());
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn style_set_from_typefaces_matches_styles() {
+        let system = FontMgr::new();
+        let family = system.family_name(0);
+        let mut family_styles = system.match_family(&family);
+        let typefaces: Vec<_> = (0..family_styles.count())
+            .filter_map(|i| family_styles.new_typeface(i))
+            .collect();
+
+        let mut style_set = FontStyleSet::from_typefaces(typefaces.clone());
+        assert_eq!(style_set.count(), typefaces.len());
+        for (i, typeface) in typefaces.iter().enumerate() {
+            assert_eq!(style_set.style(i), (typeface.font_style(), None));
+            let matched = style_set.match_style(typeface.font_style()).unwrap();
+            assert_eq!(matched.font_style(), typeface.font_style());
+        }
+        assert_eq!(FontStyleSet::from_typefaces([]).count(), 0);
+    }
 }
@@ -0,0 +1,355 @@
+use std::{collections::BTreeMap, fs, io, path::Path};
+
+use crate::{Data, FontMgr, FontMgrImpl, FontStyle, FontStyleSet, Typeface, Unichar};
+
+/// Builds a [`FontMgr`] from font files, font data, and typefaces.
+///
+/// In contrast to [`FontMgr::new_custom_directory()`], the built font manager is available on
+/// all platforms and its content does not depend on the order in which the file system lists
+/// the files: The families are sorted by name and the typefaces of a family keep the order in
+/// which they were added. All the fonts of a font collection (`.ttc`, `.otc`) are loaded.
+#[derive(Debug)]
+pub struct FontMgrBuilder {
+    loader: FontMgr,
+    families: BTreeMap<String, Vec<Typeface>>,
+    default_family: Option<String>,
+}
+
+impl Default for FontMgrBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FontMgrBuilder {
+    /// Creates a builder that loads fonts with [`FontMgr::new_custom_empty()`], or with the
+    /// system font manager if the custom font manager is not available.
+    pub fn new() -> Self {
+        Self::with_loader(FontMgr::new_custom_empty().unwrap_or_default())
+    }
+
+    /// Creates a builder that loads fonts with `loader`.
+    ///
+    /// The loader is only used to create typefaces from font data, its families are not added.
+    pub fn with_loader(loader: FontMgr) -> Self {
+        Self {
+            loader,
+            families: BTreeMap::new(),
+            default_family: None,
+        }
+    }
+
+    /// Adds the font files in `dir` and its subdirectories and returns the number of typefaces
+    /// added.
+    ///
+    /// Files are recognized by their extension (`.ttf`, `.otf`, `.ttc`, `.otc`) and visited in
+    /// the order of their paths. Symbolic links to directories are not followed.
+    pub fn add_dir(&mut self, dir: impl AsRef<Path>) -> io::Result<usize> {
+        let mut paths = fs::read_dir(dir)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<io::Result<Vec<_>>>()?;
+        paths.sort();
+
+        let mut added = 0;
+        for path in paths {
+            if fs::symlink_metadata(&path)?.is_dir() {
+                added += self.add_dir(&path)?;
+            } else if is_font_file(&path) {
+                added += self.add_file(&path)?;
+            }
+        }
+        Ok(added)
+    }
+
+    /// Adds the fonts in the file at `path` and returns the number of typefaces added.
+    pub fn add_file(&mut self, path: impl AsRef<Path>) -> io::Result<usize> {
+        let data = Data::new_copy(&fs::read(path)?);
+        Ok(self.add_data(data))
+    }
+
+    /// Adds the fonts in `data` and returns the number of typefaces added.
+    ///
+    /// If `data` contains a font collection, all of its fonts are added and share `data`.
+    pub fn add_data(&mut self, data: Data) -> usize {
+        let typefaces: Vec<_> = (0..collection_size(data.as_bytes()))
+            .filter_map(|ttc_index| self.loader.new_from_shared_data(&data, ttc_index))
+            .collect();
+        let added = typefaces.len();
+        for typeface in typefaces {
+            self.add_typeface(typeface);
+        }
+        added
+    }
+
+    /// Adds `typeface` to the family it reports with [`Typeface::family_name()`].
+    pub fn add_typeface(&mut self, typeface: Typeface) -> &mut Self {
+        self.families
+            .entry(typeface.family_name())
+            .or_default()
+            .push(typeface);
+        self
+    }
+
+    /// Sets the family that is used when no family name is requested and that is searched
+    /// first for fallback typefaces.
+    ///
+    /// If this is not set, or no family has this name, the first family is the default.
+    pub fn set_default_family(&mut self, family_name: impl Into<String>) -> &mut Self {
+        self.default_family = Some(family_name.into());
+        self
+    }
+
+    /// Builds the font manager.
+    pub fn build(self) -> FontMgr {
+        let families: Vec<_> = self.families.into_iter().collect();
+        let default_family = self
+            .default_family
+            .and_then(|name| find_family(&families, &name))
+            .or_else(|| (!families.is_empty()).then_some(0));
+        FontMgr::from_impl(BuiltFontMgr {
+            loader: self.loader,
+            families,
+            default_family,
+        })
+    }
+}
+
+/// A font manager built by [`FontMgrBuilder`].
+///
+/// Family names are matched ASCII case-insensitively, and an empty family name selects the
+/// default family. Fallback typefaces are searched in the requested family, the default family,
+/// and then in all families in order. BCP 47 language tags are ignored.
+struct BuiltFontMgr {
+    loader: FontMgr,
+    families: Vec<(String, Vec<Typeface>)>,
+    default_family: Option<usize>,
+}
+
+impl BuiltFontMgr {
+    fn family(&self, family_name: Option<&str>) -> Option<usize> {
+        match family_name {
+            None | Some("") => self.default_family,
+            Some(family_name) => find_family(&self.families, family_name),
+        }
+    }
+}
+
+impl FontMgrImpl for BuiltFontMgr {
+    fn count_families(&self) -> usize {
+        self.families.len()
+    }
+
+    fn family_name(&self, index: usize) -> String {
+        self.families[index].0.clone()
+    }
+
+    fn new_style_set(&self, index: usize) -> FontStyleSet {
+        FontStyleSet::from_typefaces(self.families[index].1.iter().cloned())
+    }
+
+    fn match_family(&self, family_name: &str) -> FontStyleSet {
+        self.family(Some(family_name))
+            .map(|index| self.new_style_set(index))
+            .unwrap_or_default()
+    }
+
+    fn match_family_style(&self, family_name: Option<&str>, style: FontStyle) -> Option<Typeface> {
+        self.new_style_set(self.family(family_name)?)
+            .match_style(style)
+    }
+
+    fn match_family_style_character(
+        &self,
+        family_name: Option<&str>,
+        style: FontStyle,
+        _bcp_47: &[&str],
+        character: Unichar,
+    ) -> Option<Typeface> {
+        let mut searched = Vec::with_capacity(self.families.len());
+        let candidates = self
+            .family(family_name)
+            .into_iter()
+            .chain(self.default_family)
+            .chain(0..self.families.len());
+        for index in candidates {
+            if searched.contains(&index) {
+                continue;
+            }
+            searched.push(index);
+            let typefaces = self.families[index]
+                .1
+                .iter()
+                .filter(|typeface| typeface.unichar_to_glyph(character) != 0)
+                .cloned();
+            if let Some(typeface) = FontStyleSet::from_typefaces(typefaces).match_style(style) {
+                return Some(typeface);
+            }
+        }
+        None
+    }
+
+    fn make_from_data(&self, data: Data, ttc_index: usize) -> Option<Typeface> {
+        self.loader.new_from_shared_data(&data, ttc_index)
+    }
+}
+
+fn find_family(families: &[(String, Vec<Typeface>)], family_name: &str) -> Option<usize> {
+    families
+        .iter()
+        .position(|(name, _)| name.eq_ignore_ascii_case(family_name))
+}
+
+fn is_font_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| {
+            ["ttf", "otf", "ttc", "otc"]
+                .iter()
+                .any(|font_extension| extension.eq_ignore_ascii_case(font_extension))
+        })
+}
+
+/// Returns the number of fonts in `font_data`, which is `1` if it does not contain a font
+/// collection.
+fn collection_size(font_data: &[u8]) -> usize {
+    match font_data {
+        [b't', b't', b'c', b'f', _, _, _, _, n0, n1, n2, n3, offsets @ ..] => {
+            let num_fonts = u32::from_be_bytes([*n0, *n1, *n2, *n3]) as usize;
+            // Every font needs a 32 bit offset in the header.
+            num_fonts.min(offsets.len() / 4)
+        }
+        _ => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use tempfile::TempDir;
+
+    use super::{collection_size, FontMgrBuilder};
+    use crate::{FontMgr, FontStyle, Unichar};
+
+    /// Wraps a single font into a collection in which both fonts share the same tables.
+    fn two_font_collection(font_data: &[u8]) -> Vec<u8> {
+        const HEADER_SIZE: u32 = 20;
+        let mut collection = Vec::with_capacity(font_data.len() + HEADER_SIZE as usize);
+        collection.extend_from_slice(b"ttcf");
+        collection.extend_from_slice(&0x0001_0000u32.to_be_bytes());
+        collection.extend_from_slice(&2u32.to_be_bytes());
+        collection.extend_from_slice(&HEADER_SIZE.to_be_bytes());
+        collection.extend_from_slice(&HEADER_SIZE.to_be_bytes());
+
+        let mut font = font_data.to_vec();
+        let num_tables = u16::from_be_bytes([font[4], font[5]]) as usize;
+        for table in 0..num_tables {
+            let offset = 12 + table * 16 + 8;
+            let table_offset = u32::from_be_bytes(font[offset..offset + 4].try_into().unwrap());
+            font[offset..offset + 4].copy_from_slice(&(table_offset + HEADER_SIZE).to_be_bytes());
+        }
+        collection.extend_from_slice(&font);
+        collection
+    }
+
+    #[test]
+    fn collection_size_is_read_from_the_header() {
+        assert_eq!(collection_size(b"\0\x01\0\0\0\x0a"), 1);
+        let mut header = b"ttcf\0\x01\0\0\0\0\0\x03".to_vec();
+        header.extend_from_slice(&[0; 12]);
+        assert_eq!(collection_size(&header), 3);
+        header.truncate(16);
+        assert_eq!(collection_size(&header), 1);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn build_from_directory() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path();
+        fs::create_dir_all(path.join("a")).unwrap();
+        fs::create_dir_all(path.join("b")).unwrap();
+
+        let typeface = FontMgr::new()
+            .legacy_make_typeface(None, FontStyle::normal())
+            .expect("No default typeface");
+        let family_name = typeface.family_name();
+        let (font_data, _) = typeface.to_font_data().unwrap();
+        let collection = if font_data.starts_with(b"ttcf") {
+            font_data.clone()
+        } else {
+            two_font_collection(&font_data)
+        };
+        let font_count = collection_size(&font_data) + collection_size(&collection);
+
+        fs::write(path.join("a/font.TTF"), &font_data).unwrap();
+        fs::write(path.join("b/fonts.ttc"), &collection).unwrap();
+        fs::write(path.join("readme.txt"), b"not a font").unwrap();
+
+        let build = || {
+            let mut builder = FontMgrBuilder::new();
+            let added = builder.add_dir(path).unwrap();
+            assert_eq!(added, font_count);
+            builder.build()
+        };
+        let font_mgr = build();
+
+        assert_eq!(
+            font_mgr.family_names().collect::<Vec<_>>(),
+            [family_name.clone()]
+        );
+        assert_eq!(font_mgr.new_style_set(0).count(), font_count);
+        assert_eq!(
+            build().family_names().collect::<Vec<_>>(),
+            font_mgr.family_names().collect::<Vec<_>>()
+        );
+
+        let upper_case = family_name.to_ascii_uppercase();
+        assert_eq!(font_mgr.match_family(&upper_case).count(), font_count);
+        assert!(font_mgr
+            .match_family_style(&upper_case, FontStyle::normal())
+            .is_some());
+        assert!(font_mgr
+            .match_family_style("Unknown Family", FontStyle::normal())
+            .is_none());
+        assert!(font_mgr
+            .legacy_make_typeface(None, FontStyle::normal())
+            .is_some());
+        assert!(font_mgr
+            .match_family_style_character(
+                "Unknown Family",
+                FontStyle::normal(),
+                &[],
+                'a' as Unichar
+            )
+            .is_some());
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn default_family_is_searched_first() {
+        let system = FontMgr::new();
+        let typefaces: Vec<_> = system
+            .family_names()
+            .filter_map(|family_name| system.match_family_style(family_name, FontStyle::normal()))
+            .take(2)
+            .collect();
+        let default_family = typefaces.last().unwrap().family_name();
+
+        let mut builder = FontMgrBuilder::new();
+        for typeface in typefaces {
+            builder.add_typeface(typeface);
+        }
+        builder.set_default_family(default_family.to_ascii_lowercase());
+        let font_mgr = builder.build();
+
+        let typeface = font_mgr
+            .legacy_make_typeface(None, FontStyle::normal())
+            .unwrap();
+        assert_eq!(typeface.family_name(), default_family);
+        let fallback = font_mgr
+            .match_family_style_character("", FontStyle::normal(), &[], 'a' as Unichar)
+            .unwrap();
+        assert_eq!(fallback.family_name(), default_family);
+    }
+}
@@ -1,4 +1,12 @@
-use skia_bindings::SkGraphics;
+use std::{
+    ptr,
+    sync::{Arc, RwLock},
+};
+
+use skia_bindings::{self as sb, SkGraphics, SkOpenTypeSVGDecoder};
+
+use super::open_type_svg_decoder::new_native_decoder;
+use crate::{prelude::*, OpenTypeSVGDecoder};
 
 pub fn init() {
     unsafe { SkGraphics::Init() };
@@ -87,4 +95,56 @@ pub fn purge_all_caches() {
 }
 
 // TODO: ImageGeneratorFromEncodedDataFactory
-// TODO: SetOpenTypeSVGDecoderFactory & GetOpenTypeSVGDecoderFactory
+
+type OpenTypeSVGDecoderFactory =
+    dyn Fn(&[u8]) -> Option<Box<dyn OpenTypeSVGDecoder>> + Send + Sync + 'static;
+
+static OPEN_TYPE_SVG_DECODER_FACTORY: RwLock<Option<Arc<OpenTypeSVGDecoderFactory>>> =
+    RwLock::new(None);
+
+/// Sets the factory that creates the decoders for the SVG documents in the `SVG ` tables of
+/// OpenType fonts. The factory returns `None` if a document can not be decoded.
+///
+/// Without a decoder, glyphs that are only available as SVG documents are not rendered. With the
+/// `svg` feature, [`set_default_open_type_svg_decoder()`] sets a decoder that is based on
+/// [`crate::svg::Dom`].
+pub fn set_open_type_svg_decoder(
+    factory: impl Fn(&[u8]) -> Option<Box<dyn OpenTypeSVGDecoder>> + Send + Sync + 'static,
+) {
+    *OPEN_TYPE_SVG_DECODER_FACTORY.write().unwrap() = Some(Arc::new(factory));
+    unsafe { sb::C_SkGraphics_SetOpenTypeSVGDecoderFactory(Some(make_open_type_svg_decoder)) }
+}
+
+/// Sets a decoder for the SVG documents of OpenType fonts that renders them with
+/// [`crate::svg::OpenTypeDecoder`].
+#[cfg(feature = "svg")]
+pub fn set_default_open_type_svg_decoder() {
+    unsafe {
+        sb::C_SkGraphics_SetOpenTypeSVGDecoderFactory(Some(sb::C_SkSVGOpenTypeSVGDecoder_Make))
+    }
+    *OPEN_TYPE_SVG_DECODER_FACTORY.write().unwrap() = None;
+}
+
+/// Removes the decoder for the SVG documents of OpenType fonts.
+pub fn remove_open_type_svg_decoder() {
+    unsafe { sb::C_SkGraphics_SetOpenTypeSVGDecoderFactory(None) }
+    *OPEN_TYPE_SVG_DECODER_FACTORY.write().unwrap() = None;
+}
+
+/// Returns `true` if a decoder for the SVG documents of OpenType fonts is set.
+pub fn has_open_type_svg_decoder() -> bool {
+    unsafe { sb::C_SkGraphics_HasOpenTypeSVGDecoderFactory() }
+}
+
+extern "C" fn make_open_type_svg_decoder(
+    svg: *const u8,
+    length: usize,
+) -> *mut SkOpenTypeSVGDecoder {
+    let svg = unsafe { safer::from_raw_parts(svg, length) };
+    // The factory may set or remove decoders itself.
+    let factory = OPEN_TYPE_SVG_DECODER_FACTORY.read().unwrap().clone();
+    factory
+        .and_then(|factory| factory(svg))
+        .map(new_native_decoder)
+        .unwrap_or(ptr::null_mut())
+}
@@ -4,7 +4,7 @@ use crate::{
     SamplingOptions, Shader, SurfaceProps, TextureCompressionType, TileMode,
 };
 use skia_bindings::{self as sb, SkImage, SkRefCntBase};
-use std::{fmt, ptr};
+use std::{ffi, fmt, ptr};
 
 pub use super::CubicResampler;
 
@@ -250,6 +250,148 @@ pub mod images {
 pub use skia_bindings::SkImage_CachingHint as CachingHint;
 variant_name!(CachingHint::Allow);
 
+/// Controls whether rescaling is performed in the source color space or in a linear version of
+/// it.
+pub use skia_bindings::SkImage_RescaleGamma as RescaleGamma;
+variant_name!(RescaleGamma::Linear);
+
+/// Controls the quality of the rescaling performed by the async read functions.
+pub use skia_bindings::SkImage_RescaleMode as RescaleMode;
+variant_name!(RescaleMode::RepeatedCubic);
+
+/// The result of an asynchronous read, returned by [`Image::async_rescale_and_read_pixels()`],
+/// [`crate::Surface::async_rescale_and_read_pixels()`], and their YUV variants.
+///
+/// Contains one plane for RGBA reads, three planes (Y, U, V) for YUV420 reads, and four planes
+/// (Y, U, V, A) for YUVA420 reads.
+pub struct AsyncReadResult {
+    native: RefHandle<sb::SkImage_AsyncReadResult>,
+    planes: Vec<PlaneSize>,
+}
+
+/// The number of rows of a plane and the number of bytes of pixels in each row.
+#[derive(Copy, Clone, Debug)]
+struct PlaneSize {
+    height: usize,
+    row_len: usize,
+}
+
+impl NativeDrop for sb::SkImage_AsyncReadResult {
+    fn drop(&mut self) {
+        unsafe { sb::C_SkImage_AsyncReadResult_delete(self) }
+    }
+}
+
+impl fmt::Debug for AsyncReadResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AsyncReadResult")
+            .field("count", &self.count())
+            .field("planes", &self.planes)
+            .finish()
+    }
+}
+
+impl AsyncReadResult {
+    /// Returns the number of planes.
+    pub fn count(&self) -> usize {
+        unsafe { sb::C_SkImage_AsyncReadResult_count(self.native.native()) }
+            .try_into()
+            .unwrap()
+    }
+
+    /// Returns the pixels of plane `i`.
+    ///
+    /// The last row ends after its pixels and may be shorter than [`Self::row_bytes()`].
+    pub fn data(&self, i: usize) -> &[u8] {
+        assert!(i < self.count());
+        let PlaneSize { height, row_len } = self.planes[i];
+        let len = match height {
+            0 => 0,
+            height => self.row_bytes(i) * (height - 1) + row_len,
+        };
+        unsafe {
+            let data =
+                sb::C_SkImage_AsyncReadResult_data(self.native.native(), i.try_into().unwrap());
+            safer::from_raw_parts(data as *const u8, len)
+        }
+    }
+
+    /// Returns the number of bytes between the start of two consecutive rows of plane `i`.
+    pub fn row_bytes(&self, i: usize) -> usize {
+        assert!(i < self.count());
+        unsafe {
+            sb::C_SkImage_AsyncReadResult_rowBytes(self.native.native(), i.try_into().unwrap())
+        }
+    }
+
+    /// Returns the number of rows of plane `i`.
+    pub fn height(&self, i: usize) -> usize {
+        assert!(i < self.count());
+        self.planes[i].height
+    }
+}
+
+/// The callback invoked when an asynchronous read finishes. Receives `None` if the read failed.
+pub(crate) type ReadPixelsCallback = Box<dyn FnOnce(Option<AsyncReadResult>)>;
+
+/// State passed through Skia's `ReadPixelsContext` to [`read_pixels_callback()`].
+pub(crate) struct ReadPixelsContext {
+    planes: Vec<PlaneSize>,
+    callback: ReadPixelsCallback,
+}
+
+impl ReadPixelsContext {
+    /// Boxes the context for a read of a single RGBA plane described by `info`.
+    pub(crate) fn rgba(info: &ImageInfo, callback: ReadPixelsCallback) -> *mut ffi::c_void {
+        let plane = PlaneSize {
+            height: info.height().max(0) as usize,
+            row_len: info.width().max(0) as usize * info.bytes_per_pixel(),
+        };
+        Self::new(vec![plane], callback)
+    }
+
+    /// Boxes the context for a YUV420 read of `dst_size`, optionally including a full size alpha
+    /// plane. U and V planes are half the width and height of the Y plane, all planes use one
+    /// byte per pixel.
+    pub(crate) fn yuv420(
+        dst_size: ISize,
+        with_alpha: bool,
+        callback: ReadPixelsCallback,
+    ) -> *mut ffi::c_void {
+        let y = PlaneSize {
+            height: dst_size.height.max(0) as usize,
+            row_len: dst_size.width.max(0) as usize,
+        };
+        let uv = PlaneSize {
+            height: y.height / 2,
+            row_len: y.row_len / 2,
+        };
+        let mut planes = vec![y, uv, uv];
+        if with_alpha {
+            planes.push(y);
+        }
+        Self::new(planes, callback)
+    }
+
+    fn new(planes: Vec<PlaneSize>, callback: ReadPixelsCallback) -> *mut ffi::c_void {
+        Box::into_raw(Box::new(Self { planes, callback })) as _
+    }
+}
+
+/// Invoked by Skia exactly once per asynchronous read, takes back the [`ReadPixelsContext`] and
+/// the ownership of `result`.
+pub(crate) unsafe extern "C" fn read_pixels_callback(
+    context: *mut ffi::c_void,
+    result: *const sb::SkImage_AsyncReadResult,
+) {
+    let context = Box::from_raw(context as *mut ReadPixelsContext);
+    let result = RefHandle::from_ptr(result as *mut _).map(|native| AsyncReadResult {
+        native,
+        planes: context.planes,
+    });
+    (context.callback)(result)
+}
+
 /// [`Image`] describes a two dimensional array of pixels to draw. The pixels may be
 /// decoded in a raster bitmap, encoded in a [`Picture`] or compressed data stream,
 /// or located in GPU memory as a GPU texture.
@@ -1188,15 +1330,131 @@ impl Image {
             .readPixels1(ptr::null_mut(), dst.native(), src.x, src.y, caching_hint)
     }
 
-    // TODO:
-    // AsyncReadResult,
-    // ReadPixelsContext,
-    // ReadPixelsCallback,
-    // RescaleGamma,
-    // RescaleMode,
-    // asyncRescaleAndReadPixels,
-    // asyncRescaleAndReadPixelsYUV420,
-    // asyncRescaleAndReadPixelsYUVA420
+    /// Makes image pixel data available to the caller, possibly asynchronously. It can also
+    /// rescale the image pixels.
+    ///
+    /// Currently asynchronous reads are only supported on the GPU backend and only when the
+    /// underlying 3D API supports transfer buffers and CPU/GPU synchronization primitives. In all
+    /// other cases this operates synchronously.
+    ///
+    /// Data is read from the source sub-rectangle, is optionally converted to a linear gamma, is
+    /// rescaled to the size indicated by `info`, is then converted to the color space, color
+    /// type, and alpha type of `info`. A `src_rect` that is not contained by the bounds of the
+    /// image causes failure.
+    ///
+    /// When the pixel data is ready the caller's `callback` is called with an
+    /// [`AsyncReadResult`] containing pixel data in the requested color type, alpha type, and
+    /// color space. The [`AsyncReadResult`] will have `count() == 1`. Upon failure the callback
+    /// is called with `None`. For a GPU image this flushes work but a submit must occur to
+    /// guarantee a finite time before the callback is called.
+    ///
+    /// The data is valid for the lifetime of [`AsyncReadResult`] with the exception that if the
+    /// [`Image`] is GPU-backed the data is immediately invalidated if the context is abandoned or
+    /// destroyed.
+    ///
+    /// * `info` - info of the requested pixels
+    /// * `src_rect` - subrectangle of image to read
+    /// * `rescale_gamma` - controls whether rescaling is done in the image's gamma or whether
+    ///                     the source data is transformed to a linear gamma before rescaling.
+    /// * `rescale_mode` - controls the technique (and cost) of the rescaling
+    /// * `callback` - function to call with result of the read
+    pub fn async_rescale_and_read_pixels(
+        &self,
+        info: &ImageInfo,
+        src_rect: impl AsRef<IRect>,
+        rescale_gamma: RescaleGamma,
+        rescale_mode: RescaleMode,
+        callback: impl FnOnce(Option<AsyncReadResult>) + 'static,
+    ) {
+        unsafe {
+            sb::C_SkImage_asyncRescaleAndReadPixels(
+                self.native(),
+                info.native(),
+                src_rect.as_ref().native(),
+                rescale_gamma,
+                rescale_mode,
+                Some(read_pixels_callback),
+                ReadPixelsContext::rgba(info, Box::new(callback)),
+            )
+        }
+    }
+
+    /// Similar to [`Self::async_rescale_and_read_pixels()`] but performs an additional conversion
+    /// to YUV. The RGB->YUV conversion is controlled by `yuv_color_space`. The YUV data is
+    /// returned as three planes ordered y, u, v. The u and v planes are half the width and
+    /// height of the resized rectangle. The y, u, and v values are single bytes. Currently this
+    /// fails if `dst_size` width and height are not even. A `src_rect` that is not contained by
+    /// the bounds of the image causes failure.
+    ///
+    /// When the pixel data is ready the caller's `callback` is called with an
+    /// [`AsyncReadResult`] containing the planar data. The [`AsyncReadResult`] will have
+    /// `count() == 3`. Upon failure the callback is called with `None`.
+    ///
+    /// * `yuv_color_space` - The transformation from RGB to YUV. Applied to the resized image
+    ///                       after it is converted to `dst_color_space`.
+    /// * `dst_color_space` - The color space to convert the resized image to, after rescaling.
+    /// * `src_rect` - The portion of the image to rescale and convert to YUV planes.
+    /// * `dst_size` - The size to rescale `src_rect` to
+    /// * `rescale_gamma` - controls whether rescaling is done in the image's gamma or whether
+    ///                     the source data is transformed to a linear gamma before rescaling.
+    /// * `rescale_mode` - controls the technique (and cost) of the rescaling
+    /// * `callback` - function to call with the planar read result
+    #[allow(clippy::too_many_arguments)]
+    pub fn async_rescale_and_read_pixels_yuv420(
+        &self,
+        yuv_color_space: crate::YUVColorSpace,
+        dst_color_space: impl Into<Option<ColorSpace>>,
+        src_rect: impl AsRef<IRect>,
+        dst_size: impl Into<ISize>,
+        rescale_gamma: RescaleGamma,
+        rescale_mode: RescaleMode,
+        callback: impl FnOnce(Option<AsyncReadResult>) + 'static,
+    ) {
+        let dst_size = dst_size.into();
+        unsafe {
+            sb::C_SkImage_asyncRescaleAndReadPixelsYUV420(
+                self.native(),
+                yuv_color_space,
+                dst_color_space.into().into_ptr_or_null(),
+                src_rect.as_ref().native(),
+                dst_size.native(),
+                rescale_gamma,
+                rescale_mode,
+                Some(read_pixels_callback),
+                ReadPixelsContext::yuv420(dst_size, false, Box::new(callback)),
+            )
+        }
+    }
+
+    /// Identical to [`Self::async_rescale_and_read_pixels_yuv420()`] but a fourth plane is
+    /// returned in the [`AsyncReadResult`] passed to `callback`. The fourth plane contains the
+    /// alpha channel at the same full resolution as the Y plane.
+    #[allow(clippy::too_many_arguments)]
+    pub fn async_rescale_and_read_pixels_yuva420(
+        &self,
+        yuv_color_space: crate::YUVColorSpace,
+        dst_color_space: impl Into<Option<ColorSpace>>,
+        src_rect: impl AsRef<IRect>,
+        dst_size: impl Into<ISize>,
+        rescale_gamma: RescaleGamma,
+        rescale_mode: RescaleMode,
+        callback: impl FnOnce(Option<AsyncReadResult>) + 'static,
+    ) {
+        let dst_size = dst_size.into();
+        unsafe {
+            sb::C_SkImage_asyncRescaleAndReadPixelsYUVA420(
+                self.native(),
+                yuv_color_space,
+                dst_color_space.into().into_ptr_or_null(),
+                src_rect.as_ref().native(),
+                dst_size.native(),
+                rescale_gamma,
+                rescale_mode,
+                Some(read_pixels_callback),
+                ReadPixelsContext::yuv420(dst_size, true, Box::new(callback)),
+            )
+        }
+    }
 
     /// Copies [`Image`] to dst, scaling pixels to fit `dst.width()` and `dst.height()`, and
     /// converting pixels to match `dst.color_type()` and `dst.alpha_type()`. Returns `true` if
@@ -1641,3 +1899,86 @@ impl Image {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use super::{AsyncReadResult, RescaleGamma, RescaleMode};
+    use crate::{surfaces, AlphaType, Color, ColorType, IRect, Image, ImageInfo, YUVColorSpace};
+
+    fn image(color: Color) -> Image {
+        let mut surface = surfaces::raster_n32_premul((100, 100)).unwrap();
+        surface.canvas().clear(color);
+        surface.image_snapshot()
+    }
+
+    // Skia calls the callback from native code, where a failing assert would abort the test
+    // binary, so the result is taken out of the callback and checked afterwards.
+    fn capture_read_result() -> (
+        Rc<RefCell<Option<Option<AsyncReadResult>>>>,
+        impl FnOnce(Option<AsyncReadResult>) + 'static,
+    ) {
+        let captured = Rc::new(RefCell::new(None));
+        let captured_in_callback = captured.clone();
+        let callback = move |result| *captured_in_callback.borrow_mut() = Some(result);
+        (captured, callback)
+    }
+
+    #[test]
+    fn async_rescale_and_read_pixels() {
+        let image = image(Color::BLUE);
+        let info = ImageInfo::new((25, 25), ColorType::RGBA8888, AlphaType::Premul, None);
+
+        let (captured, callback) = capture_read_result();
+        image.async_rescale_and_read_pixels(
+            &info,
+            IRect::from_xywh(50, 50, 50, 50),
+            RescaleGamma::Src,
+            RescaleMode::Linear,
+            callback,
+        );
+
+        // Raster images complete the read synchronously.
+        let result = captured.borrow_mut().take().unwrap().unwrap();
+        assert_eq!(result.count(), 1);
+        assert_eq!(result.height(0), 25);
+        assert_eq!(result.data(0).len(), result.row_bytes(0) * 24 + 25 * 4);
+        assert_eq!(result.data(0)[..4], [0, 0, 0xff, 0xff]);
+
+        // Source rectangles outside of the image fail.
+        let (captured, callback) = capture_read_result();
+        image.async_rescale_and_read_pixels(
+            &info,
+            IRect::from_xywh(50, 50, 100, 100),
+            RescaleGamma::Src,
+            RescaleMode::Linear,
+            callback,
+        );
+        assert!(captured.borrow_mut().take().unwrap().is_none());
+    }
+
+    #[test]
+    fn async_rescale_and_read_pixels_yuv420() {
+        let image = image(Color::WHITE);
+
+        let (captured, callback) = capture_read_result();
+        image.async_rescale_and_read_pixels_yuv420(
+            YUVColorSpace::JPEG,
+            None,
+            IRect::from_wh(100, 100),
+            (50, 50),
+            RescaleGamma::Src,
+            RescaleMode::Nearest,
+            callback,
+        );
+
+        let result = captured.borrow_mut().take().unwrap().unwrap();
+        assert_eq!(result.count(), 3);
+        assert_eq!(result.height(0), 50);
+        assert_eq!(result.height(1), 25);
+        assert_eq!(result.height(2), 25);
+        // Full range white has the maximum luma.
+        assert_eq!(result.data(0)[0], 0xff);
+    }
+}
@@ -1,8 +1,11 @@
 #[cfg(feature = "gpu")]
 use crate::gpu;
-use crate::{prelude::*, yuva_pixmap_info, Data, ImageInfo, YUVAPixmapInfo};
-use skia_bindings::{self as sb, SkImageGenerator};
-use std::fmt;
+use crate::{prelude::*, yuva_pixmap_info, Data, ImageInfo, YUVAPixmapInfo, YUVAPixmaps};
+use skia_bindings::{
+    self as sb, RustImageGenerator_Param, SkData, SkImageGenerator, SkImageInfo, SkYUVAPixmapInfo,
+    SkYUVAPixmapInfo_SupportedDataTypes, SkYUVAPixmaps, TraitObject,
+};
+use std::{ffi::c_void, fmt, mem, ptr};
 
 pub type ImageGenerator = RefHandle<SkImageGenerator>;
 unsafe_send_sync!(ImageGenerator);
@@ -22,7 +25,123 @@ impl fmt::Debug for ImageGenerator {
     }
 }
 
+/// A trait for implementing [`ImageGenerator`]s in Rust.
+///
+/// Images created from such a generator with [`crate::images::deferred_from_generator()`] request
+/// their pixels only when Skia actually needs them, for example when the image is drawn.
+pub trait ImageGeneratorImpl: Send {
+    /// Returns the [`ImageInfo`] of the pixels the generator produces.
+    ///
+    /// This is queried once when the [`ImageGenerator`] is created.
+    fn info(&self) -> ImageInfo;
+
+    /// Decodes or generates the pixels into `pixels`.
+    ///
+    /// `info` matches [`Self::info()`] in its dimensions, but may differ in its color type, alpha
+    /// type, or color space. Return `false` if the requested conversion is not supported.
+    fn get_pixels(&mut self, info: &ImageInfo, pixels: &mut [u8], row_bytes: usize) -> bool;
+
+    /// Returns the encoded data the pixels originate from, if available.
+    fn encoded_data(&mut self) -> Option<Data> {
+        None
+    }
+
+    /// Returns the layout of the YUVA planes if the generator can produce them with one of the
+    /// supported data types. If this returns `Some`, [`Self::get_yuva_planes()`] must be
+    /// implemented, too.
+    fn query_yuva_info(
+        &self,
+        _supported_data_types: &yuva_pixmap_info::SupportedDataTypes,
+    ) -> Option<YUVAPixmapInfo> {
+        None
+    }
+
+    /// Writes the YUVA planes into the pixmaps, which are laid out as returned by
+    /// [`Self::query_yuva_info()`].
+    fn get_yuva_planes(&mut self, _pixmaps: &YUVAPixmaps) -> bool {
+        false
+    }
+}
+
 impl ImageGenerator {
+    /// Creates an [`ImageGenerator`] that is implemented in Rust.
+    pub fn new(generator: impl ImageGeneratorImpl + 'static) -> Self {
+        let info = generator.info();
+        let generator: Box<dyn ImageGeneratorImpl> = Box::new(generator);
+
+        let param = RustImageGenerator_Param {
+            trait_: unsafe {
+                mem::transmute::<Box<dyn ImageGeneratorImpl>, TraitObject>(generator)
+            },
+            drop: Some(drop),
+            onRefEncodedData: Some(on_ref_encoded_data),
+            onGetPixels: Some(on_get_pixels),
+            onQueryYUVAInfo: Some(on_query_yuva_info),
+            onGetYUVAPlanes: Some(on_get_yuva_planes),
+        };
+
+        return ImageGenerator::from_ptr(unsafe {
+            sb::C_RustImageGenerator_New(info.native(), &param)
+        })
+        .unwrap();
+
+        extern "C" fn drop(generator: TraitObject) {
+            mem::drop(unsafe {
+                mem::transmute::<TraitObject, Box<dyn ImageGeneratorImpl>>(generator)
+            });
+        }
+
+        extern "C" fn on_ref_encoded_data(generator: TraitObject) -> *mut SkData {
+            unsafe { generator_mut(generator) }
+                .encoded_data()
+                .map(|data| data.into_ptr())
+                .unwrap_or(ptr::null_mut())
+        }
+
+        extern "C" fn on_get_pixels(
+            generator: TraitObject,
+            info: *const SkImageInfo,
+            pixels: *mut c_void,
+            row_bytes: usize,
+        ) -> bool {
+            let info = ImageInfo::from_native_ref(unsafe { &*info });
+            let pixels = unsafe {
+                safer::from_raw_parts_mut(pixels as *mut u8, info.compute_byte_size(row_bytes))
+            };
+            unsafe { generator_mut(generator) }.get_pixels(info, pixels, row_bytes)
+        }
+
+        extern "C" fn on_query_yuva_info(
+            generator: TraitObject,
+            supported_data_types: *const SkYUVAPixmapInfo_SupportedDataTypes,
+            yuva_pixmap_info: *mut SkYUVAPixmapInfo,
+        ) -> bool {
+            let supported_data_types =
+                yuva_pixmap_info::SupportedDataTypes::from_native_ref(unsafe {
+                    &*supported_data_types
+                });
+            match unsafe { generator_mut(generator) }.query_yuva_info(supported_data_types) {
+                Some(info) => {
+                    mem::drop(info.replace_native(unsafe { &mut *yuva_pixmap_info }));
+                    true
+                }
+                None => false,
+            }
+        }
+
+        extern "C" fn on_get_yuva_planes(
+            generator: TraitObject,
+            pixmaps: *const SkYUVAPixmaps,
+        ) -> bool {
+            let pixmaps = YUVAPixmaps::from_native_ref(unsafe { &*pixmaps });
+            unsafe { generator_mut(generator) }.get_yuva_planes(pixmaps)
+        }
+
+        unsafe fn generator_mut<'a>(generator: TraitObject) -> &'a mut dyn ImageGeneratorImpl {
+            mem::transmute(generator)
+        }
+    }
+
     pub fn unique_id(&self) -> u32 {
         self.native().fUniqueID
     }
@@ -80,3 +199,74 @@ impl ImageGenerator {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use super::{ImageGenerator, ImageGeneratorImpl};
+    use crate::{images, surfaces, AlphaType, Color, ColorType, ImageInfo};
+
+    /// Generates a horizontal gray ramp and counts how often pixels were requested.
+    struct Ramp {
+        requests: Arc<AtomicUsize>,
+    }
+
+    impl ImageGeneratorImpl for Ramp {
+        fn info(&self) -> ImageInfo {
+            ImageInfo::new((256, 4), ColorType::RGBA8888, AlphaType::Opaque, None)
+        }
+
+        fn get_pixels(&mut self, info: &ImageInfo, pixels: &mut [u8], row_bytes: usize) -> bool {
+            if info.color_type() != ColorType::RGBA8888 {
+                return false;
+            }
+            self.requests.fetch_add(1, Ordering::SeqCst);
+            for (y, row) in pixels.chunks_mut(row_bytes).enumerate() {
+                if y >= info.height() as usize {
+                    break;
+                }
+                for (x, pixel) in row[..info.min_row_bytes()].chunks_mut(4).enumerate() {
+                    pixel.copy_from_slice(&[x as u8, x as u8, x as u8, 0xff]);
+                }
+            }
+            true
+        }
+    }
+
+    #[test]
+    fn pixels_are_generated_lazily() {
+        let requests = Arc::new(AtomicUsize::new(0));
+        let generator = ImageGenerator::new(Ramp {
+            requests: requests.clone(),
+        });
+        assert_eq!(generator.info().width(), 256);
+
+        let image = images::deferred_from_generator(generator).unwrap();
+        assert!(image.is_lazy_generated());
+        assert_eq!(requests.load(Ordering::SeqCst), 0);
+
+        let mut surface = surfaces::raster_n32_premul((256, 4)).unwrap();
+        surface.canvas().draw_image(&image, (0, 0), None);
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+        let pixmap = surface.peek_pixels().unwrap();
+        assert_eq!(pixmap.get_color((0, 0)), Color::from_rgb(0, 0, 0));
+        assert_eq!(pixmap.get_color((128, 2)), Color::from_rgb(128, 128, 128));
+    }
+
+    #[test]
+    fn generator_impl_is_dropped_with_the_image() {
+        let requests = Arc::new(AtomicUsize::new(0));
+        let image = images::deferred_from_generator(ImageGenerator::new(Ramp {
+            requests: requests.clone(),
+        }))
+        .unwrap();
+        assert_eq!(Arc::strong_count(&requests), 2);
+        drop(image);
+        assert_eq!(Arc::strong_count(&requests), 1);
+    }
+}
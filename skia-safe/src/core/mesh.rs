@@ -1 +1,669 @@
-// TODO
+use std::{fmt, ops::DerefMut, ptr};
+
+use skia_bindings::{
+    self as sb, SkMesh, SkMeshSpecification, SkMeshSpecification_Attribute,
+    SkMeshSpecification_Varying, SkMesh_IndexBuffer, SkMesh_VertexBuffer, SkNVRefCnt, SkRefCntBase,
+};
+
+use crate::{
+    interop::{self, AsStr},
+    prelude::*,
+    runtime_effect::{Child, ChildPtr, Uniform},
+    AlphaType, ColorSpace, Data, Rect,
+};
+
+/// A vertex attribute of a [`MeshSpecification`]: its type, its byte offset inside a vertex, and
+/// the name under which it is accessible in the `Attributes` struct of the vertex program.
+pub type Attribute = Handle<SkMeshSpecification_Attribute>;
+unsafe_send_sync!(Attribute);
+
+pub mod attribute {
+    pub use skia_bindings::SkMeshSpecification_Attribute_Type as Type;
+    variant_name!(Type::Float2);
+}
+
+impl NativeDrop for SkMeshSpecification_Attribute {
+    fn drop(&mut self) {
+        unsafe { sb::C_SkMeshSpecification_Attribute_destruct(self) }
+    }
+}
+
+impl fmt::Debug for Attribute {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Attribute")
+            .field("ty", &self.ty())
+            .field("offset", &self.offset())
+            .field("name", &self.name())
+            .finish()
+    }
+}
+
+impl Attribute {
+    pub fn new(ty: attribute::Type, offset: usize, name: impl AsRef<str>) -> Self {
+        let name = interop::String::from_str(name);
+        Self::construct(|attribute| unsafe {
+            sb::C_SkMeshSpecification_Attribute_Construct(attribute, ty, offset, name.native())
+        })
+    }
+
+    pub fn ty(&self) -> attribute::Type {
+        self.native().type_
+    }
+
+    pub fn offset(&self) -> usize {
+        self.native().offset
+    }
+
+    pub fn name(&self) -> &str {
+        self.native().name.as_str()
+    }
+}
+
+/// A value passed from the vertex program to the fragment program of a [`MeshSpecification`].
+/// Varyings are accessible under their name in the `Varyings` struct of both programs.
+pub type Varying = Handle<SkMeshSpecification_Varying>;
+unsafe_send_sync!(Varying);
+
+pub mod varying {
+    pub use skia_bindings::SkMeshSpecification_Varying_Type as Type;
+    variant_name!(Type::Half4);
+}
+
+impl NativeDrop for SkMeshSpecification_Varying {
+    fn drop(&mut self) {
+        unsafe { sb::C_SkMeshSpecification_Varying_destruct(self) }
+    }
+}
+
+impl fmt::Debug for Varying {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Varying")
+            .field("ty", &self.ty())
+            .field("name", &self.name())
+            .finish()
+    }
+}
+
+impl Varying {
+    pub fn new(ty: varying::Type, name: impl AsRef<str>) -> Self {
+        let name = interop::String::from_str(name);
+        Self::construct(|varying| unsafe {
+            sb::C_SkMeshSpecification_Varying_Construct(varying, ty, name.native())
+        })
+    }
+
+    pub fn ty(&self) -> varying::Type {
+        self.native().type_
+    }
+
+    pub fn name(&self) -> &str {
+        self.native().name.as_str()
+    }
+}
+
+/// A specification for custom mesh data. Describes the vertex buffer attributes and the SkSL
+/// vertex and fragment programs that process them.
+///
+/// The vertex program receives the attributes of a vertex in a struct named `Attributes` and
+/// returns a struct named `Varyings` that always contains a `float2 position` field and the
+/// additional [`Varying`]s of the specification:
+///
+/// ```text
+/// Varyings main(const Attributes attributes) { ... }
+/// ```
+///
+/// The fragment program receives the interpolated `Varyings` and returns the local position
+/// used to evaluate the paint's shader. It may optionally output a color that is blended with
+/// the paint's color or shader:
+///
+/// ```text
+/// float2 main(const Varyings varyings, out half4 color) { ... }
+/// ```
+pub type MeshSpecification = RCHandle<SkMeshSpecification>;
+unsafe_send_sync!(MeshSpecification);
+require_base_type!(SkMeshSpecification, SkNVRefCnt);
+
+impl NativeRefCounted for SkMeshSpecification {
+    fn _ref(&self) {
+        unsafe { sb::C_SkMeshSpecification_ref(self) }
+    }
+
+    fn _unref(&self) {
+        unsafe { sb::C_SkMeshSpecification_unref(self) }
+    }
+
+    fn unique(&self) -> bool {
+        unsafe { sb::C_SkMeshSpecification_unique(self) }
+    }
+}
+
+impl fmt::Debug for MeshSpecification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MeshSpecification")
+            .field("attributes", &self.attributes())
+            .field("uniform_size", &self.uniform_size())
+            .field("uniforms", &self.uniforms())
+            .field("children", &self.children())
+            .field("stride", &self.stride())
+            .field("color_space", &self.color_space())
+            .finish()
+    }
+}
+
+impl MeshSpecification {
+    /// The largest supported vertex stride.
+    pub const MAX_STRIDE: usize = 1024;
+    /// The maximum number of attributes.
+    pub const MAX_ATTRIBUTES: usize = 8;
+    /// The required alignment of the vertex stride.
+    pub const STRIDE_ALIGNMENT: usize = 4;
+    /// The required alignment of attribute offsets.
+    pub const OFFSET_ALIGNMENT: usize = 4;
+    /// The maximum number of varyings, not counting the implicit `position` varying.
+    pub const MAX_VARYINGS: usize = 6;
+
+    /// Creates a [`MeshSpecification`] from the attributes of a vertex, the size of a vertex,
+    /// the varyings and the SkSL vertex and fragment programs.
+    ///
+    /// The color space and alpha type describe the color output of the fragment program, they
+    /// default to sRGB and [`AlphaType::Premul`].
+    ///
+    /// Returns the compiler or validation errors if the specification is invalid.
+    pub fn new(
+        attributes: &[Attribute],
+        vertex_stride: usize,
+        varyings: &[Varying],
+        vs: impl AsRef<str>,
+        fs: impl AsRef<str>,
+        color_space: impl Into<Option<ColorSpace>>,
+        alpha_type: impl Into<Option<AlphaType>>,
+    ) -> Result<MeshSpecification, String> {
+        let attributes = attributes.native();
+        let varyings = varyings.native();
+        let vs = interop::String::from_str(vs);
+        let fs = interop::String::from_str(fs);
+        let color_space = color_space.into().unwrap_or_else(ColorSpace::new_srgb);
+        let alpha_type = alpha_type.into().unwrap_or(AlphaType::Premul);
+        let mut error = interop::String::default();
+        MeshSpecification::from_ptr(unsafe {
+            sb::C_SkMeshSpecification_Make(
+                attributes.as_ptr(),
+                attributes.len(),
+                vertex_stride,
+                varyings.as_ptr(),
+                varyings.len(),
+                vs.native(),
+                fs.native(),
+                color_space.into_ptr(),
+                alpha_type,
+                error.native_mut(),
+            )
+        })
+        .ok_or_else(|| error.to_string())
+    }
+
+    pub fn attributes(&self) -> &[Attribute] {
+        unsafe {
+            let mut count: usize = 0;
+            let ptr = sb::C_SkMeshSpecification_attributes(self.native(), &mut count);
+            safer::from_raw_parts(Attribute::from_native_ptr(ptr), count)
+        }
+    }
+
+    pub fn uniform_size(&self) -> usize {
+        unsafe { sb::C_SkMeshSpecification_uniformSize(self.native()) }
+    }
+
+    pub fn uniforms(&self) -> &[Uniform] {
+        unsafe {
+            let mut count: usize = 0;
+            let ptr = sb::C_SkMeshSpecification_uniforms(self.native(), &mut count);
+            safer::from_raw_parts(Uniform::from_native_ptr(ptr), count)
+        }
+    }
+
+    pub fn children(&self) -> &[Child] {
+        unsafe {
+            let mut count: usize = 0;
+            let ptr = sb::C_SkMeshSpecification_children(self.native(), &mut count);
+            safer::from_raw_parts(Child::from_native_ptr(ptr), count)
+        }
+    }
+
+    pub fn find_child(&self, name: impl AsRef<str>) -> Option<&Child> {
+        let name = name.as_ref().as_bytes();
+        unsafe {
+            sb::C_SkMeshSpecification_findChild(self.native(), name.as_ptr() as _, name.len())
+        }
+        .into_option()
+        .map(|ptr| Child::from_native_ref(unsafe { &*ptr }))
+    }
+
+    pub fn find_uniform(&self, name: impl AsRef<str>) -> Option<&Uniform> {
+        let name = name.as_ref().as_bytes();
+        unsafe {
+            sb::C_SkMeshSpecification_findUniform(self.native(), name.as_ptr() as _, name.len())
+        }
+        .into_option()
+        .map(|ptr| Uniform::from_native_ref(unsafe { &*ptr }))
+    }
+
+    pub fn find_attribute(&self, name: impl AsRef<str>) -> Option<&Attribute> {
+        let name = name.as_ref().as_bytes();
+        unsafe {
+            sb::C_SkMeshSpecification_findAttribute(self.native(), name.as_ptr() as _, name.len())
+        }
+        .into_option()
+        .map(|ptr| Attribute::from_native_ref(unsafe { &*ptr }))
+    }
+
+    pub fn find_varying(&self, name: impl AsRef<str>) -> Option<&Varying> {
+        let name = name.as_ref().as_bytes();
+        unsafe {
+            sb::C_SkMeshSpecification_findVarying(self.native(), name.as_ptr() as _, name.len())
+        }
+        .into_option()
+        .map(|ptr| Varying::from_native_ref(unsafe { &*ptr }))
+    }
+
+    pub fn stride(&self) -> usize {
+        unsafe { sb::C_SkMeshSpecification_stride(self.native()) }
+    }
+
+    pub fn color_space(&self) -> Option<ColorSpace> {
+        ColorSpace::from_unshared_ptr(unsafe {
+            sb::C_SkMeshSpecification_colorSpace(self.native())
+        })
+    }
+}
+
+pub use sb::SkMesh_Mode as Mode;
+variant_name!(Mode::TriangleStrip);
+
+/// A buffer of vertex data, created with [`meshes::make_vertex_buffer()`].
+pub type VertexBuffer = RCHandle<SkMesh_VertexBuffer>;
+unsafe_send_sync!(VertexBuffer);
+require_base_type!(SkMesh_VertexBuffer, sb::SkRefCnt);
+
+impl NativeRefCountedBase for SkMesh_VertexBuffer {
+    type Base = SkRefCntBase;
+}
+
+impl fmt::Debug for VertexBuffer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VertexBuffer")
+            .field("size", &self.size())
+            .finish()
+    }
+}
+
+impl VertexBuffer {
+    /// Size of the buffer in bytes.
+    pub fn size(&self) -> usize {
+        unsafe { sb::C_SkMesh_VertexBuffer_size(self.native()) }
+    }
+}
+
+/// A buffer of 16 bit vertex indices, created with [`meshes::make_index_buffer()`].
+pub type IndexBuffer = RCHandle<SkMesh_IndexBuffer>;
+unsafe_send_sync!(IndexBuffer);
+require_base_type!(SkMesh_IndexBuffer, sb::SkRefCnt);
+
+impl NativeRefCountedBase for SkMesh_IndexBuffer {
+    type Base = SkRefCntBase;
+}
+
+impl fmt::Debug for IndexBuffer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IndexBuffer")
+            .field("size", &self.size())
+            .finish()
+    }
+}
+
+impl IndexBuffer {
+    /// Size of the buffer in bytes.
+    pub fn size(&self) -> usize {
+        unsafe { sb::C_SkMesh_IndexBuffer_size(self.native()) }
+    }
+}
+
+pub mod meshes {
+    use skia_bindings as sb;
+
+    use super::{IndexBuffer, VertexBuffer};
+    use crate::prelude::*;
+
+    /// Makes a CPU-backed [`VertexBuffer`] that contains a copy of `data`.
+    pub fn make_vertex_buffer(data: &[u8]) -> Option<VertexBuffer> {
+        VertexBuffer::from_ptr(unsafe {
+            sb::C_SkMeshes_MakeVertexBuffer(data.as_ptr() as _, data.len())
+        })
+    }
+
+    /// Makes a copy of a [`VertexBuffer`]. If the source buffer is GPU-backed, the contents are
+    /// read back to the CPU.
+    pub fn copy_vertex_buffer(vertex_buffer: &VertexBuffer) -> Option<VertexBuffer> {
+        VertexBuffer::from_ptr(unsafe {
+            sb::C_SkMeshes_CopyVertexBuffer(vertex_buffer.native_mut_force())
+        })
+    }
+
+    /// Makes a CPU-backed [`IndexBuffer`] that contains a copy of `indices`.
+    pub fn make_index_buffer(indices: &[u16]) -> Option<IndexBuffer> {
+        IndexBuffer::from_ptr(unsafe {
+            sb::C_SkMeshes_MakeIndexBuffer(indices.as_ptr() as _, std::mem::size_of_val(indices))
+        })
+    }
+
+    /// Makes a copy of an [`IndexBuffer`]. If the source buffer is GPU-backed, the contents are
+    /// read back to the CPU.
+    pub fn copy_index_buffer(index_buffer: &IndexBuffer) -> Option<IndexBuffer> {
+        IndexBuffer::from_ptr(unsafe {
+            sb::C_SkMeshes_CopyIndexBuffer(index_buffer.native_mut_force())
+        })
+    }
+}
+
+/// A vertex buffer, an optional index buffer, and uniform values combined with a
+/// [`MeshSpecification`]. Drawn with [`crate::Canvas::draw_mesh()`].
+pub type Mesh = RefHandle<SkMesh>;
+unsafe_send_sync!(Mesh);
+
+impl NativeDrop for SkMesh {
+    fn drop(&mut self) {
+        unsafe { sb::C_SkMesh_delete(self) }
+    }
+}
+
+impl Clone for Mesh {
+    fn clone(&self) -> Self {
+        Self::from_ptr(unsafe { sb::C_SkMesh_clone(self.native()) }).unwrap()
+    }
+}
+
+impl fmt::Debug for Mesh {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Mesh")
+            .field("spec", &self.spec())
+            .field("mode", &self.mode())
+            .field("vertex_buffer", &self.vertex_buffer())
+            .field("vertex_offset", &self.vertex_offset())
+            .field("vertex_count", &self.vertex_count())
+            .field("index_buffer", &self.index_buffer())
+            .field("index_offset", &self.index_offset())
+            .field("index_count", &self.index_count())
+            .field("uniforms", &self.uniforms())
+            .field("bounds", &self.bounds())
+            .finish()
+    }
+}
+
+impl Mesh {
+    /// Creates a non-indexed [`Mesh`] that draws `vertex_count` vertices from `vertex_buffer`,
+    /// starting at the byte offset `vertex_offset`.
+    ///
+    /// `uniforms` must match the layout of the specification's uniforms, and `children` its
+    /// child effects. `bounds` must contain all the positions produced by the vertex program.
+    ///
+    /// Returns an error if the buffers, offsets or counts are not compatible with `spec`, or
+    /// with `mode`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        spec: &MeshSpecification,
+        mode: Mode,
+        vertex_buffer: &VertexBuffer,
+        vertex_count: usize,
+        vertex_offset: usize,
+        uniforms: impl Into<Option<Data>>,
+        children: &[ChildPtr],
+        bounds: impl AsRef<Rect>,
+    ) -> Result<Mesh, String> {
+        let mut children: Vec<_> = children
+            .iter()
+            .map(|child_ptr| child_ptr.native())
+            .collect();
+        let children_ptr = children
+            .first_mut()
+            .map(|c| c.deref_mut() as *mut _)
+            .unwrap_or(ptr::null_mut());
+        let mut error = interop::String::default();
+        Mesh::from_ptr(unsafe {
+            sb::C_SkMesh_Make(
+                spec.clone().into_ptr(),
+                mode,
+                vertex_buffer.clone().into_ptr(),
+                vertex_count,
+                vertex_offset,
+                uniforms.into().into_ptr_or_null(),
+                children_ptr,
+                children.len(),
+                bounds.as_ref().native(),
+                error.native_mut(),
+            )
+        })
+        .ok_or_else(|| error.to_string())
+    }
+
+    /// Creates an indexed [`Mesh`] that draws `index_count` indices from `index_buffer`,
+    /// starting at the byte offset `index_offset`. The indices refer to the `vertex_count`
+    /// vertices in `vertex_buffer` that start at the byte offset `vertex_offset`.
+    ///
+    /// See [`Self::new()`] for the other parameters.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_indexed(
+        spec: &MeshSpecification,
+        mode: Mode,
+        vertex_buffer: &VertexBuffer,
+        vertex_count: usize,
+        vertex_offset: usize,
+        index_buffer: &IndexBuffer,
+        index_count: usize,
+        index_offset: usize,
+        uniforms: impl Into<Option<Data>>,
+        children: &[ChildPtr],
+        bounds: impl AsRef<Rect>,
+    ) -> Result<Mesh, String> {
+        let mut children: Vec<_> = children
+            .iter()
+            .map(|child_ptr| child_ptr.native())
+            .collect();
+        let children_ptr = children
+            .first_mut()
+            .map(|c| c.deref_mut() as *mut _)
+            .unwrap_or(ptr::null_mut());
+        let mut error = interop::String::default();
+        Mesh::from_ptr(unsafe {
+            sb::C_SkMesh_MakeIndexed(
+                spec.clone().into_ptr(),
+                mode,
+                vertex_buffer.clone().into_ptr(),
+                vertex_count,
+                vertex_offset,
+                index_buffer.clone().into_ptr(),
+                index_count,
+                index_offset,
+                uniforms.into().into_ptr_or_null(),
+                children_ptr,
+                children.len(),
+                bounds.as_ref().native(),
+                error.native_mut(),
+            )
+        })
+        .ok_or_else(|| error.to_string())
+    }
+
+    pub fn spec(&self) -> MeshSpecification {
+        MeshSpecification::from_ptr(unsafe { sb::C_SkMesh_refSpec(self.native()) }).unwrap()
+    }
+
+    pub fn mode(&self) -> Mode {
+        unsafe { sb::C_SkMesh_mode(self.native()) }
+    }
+
+    pub fn vertex_buffer(&self) -> VertexBuffer {
+        VertexBuffer::from_ptr(unsafe { sb::C_SkMesh_refVertexBuffer(self.native()) }).unwrap()
+    }
+
+    pub fn vertex_offset(&self) -> usize {
+        unsafe { sb::C_SkMesh_vertexOffset(self.native()) }
+    }
+
+    pub fn vertex_count(&self) -> usize {
+        unsafe { sb::C_SkMesh_vertexCount(self.native()) }
+    }
+
+    pub fn index_buffer(&self) -> Option<IndexBuffer> {
+        IndexBuffer::from_ptr(unsafe { sb::C_SkMesh_refIndexBuffer(self.native()) })
+    }
+
+    pub fn index_offset(&self) -> usize {
+        unsafe { sb::C_SkMesh_indexOffset(self.native()) }
+    }
+
+    pub fn index_count(&self) -> usize {
+        unsafe { sb::C_SkMesh_indexCount(self.native()) }
+    }
+
+    pub fn uniforms(&self) -> Option<Data> {
+        Data::from_ptr(unsafe { sb::C_SkMesh_refUniforms(self.native()) })
+    }
+
+    pub fn bounds(&self) -> Rect {
+        Rect::construct(|r| unsafe { sb::C_SkMesh_bounds(self.native(), r) })
+    }
+
+    pub fn is_valid(&self) -> bool {
+        unsafe { sb::C_SkMesh_isValid(self.native()) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{surfaces, Color, Paint};
+
+    const VS: &str = r#"
+        Varyings main(const Attributes attributes) {
+            Varyings varyings;
+            varyings.position = attributes.position;
+            return varyings;
+        }
+    "#;
+
+    const FS: &str = r#"
+        uniform half4 u_color;
+
+        float2 main(const Varyings varyings, out half4 color) {
+            color = u_color;
+            return varyings.position;
+        }
+    "#;
+
+    fn spec() -> MeshSpecification {
+        MeshSpecification::new(
+            &[Attribute::new(attribute::Type::Float2, 0, "position")],
+            8,
+            &[],
+            VS,
+            FS,
+            None,
+            None,
+        )
+        .unwrap()
+    }
+
+    fn vertex_bytes(vertices: &[[f32; 2]]) -> Vec<u8> {
+        vertices
+            .iter()
+            .flatten()
+            .flat_map(|f| f.to_ne_bytes())
+            .collect()
+    }
+
+    #[test]
+    fn specification_reflects_attributes_and_uniforms() {
+        let spec = spec();
+        assert_eq!(spec.stride(), 8);
+        assert_eq!(spec.attributes().len(), 1);
+        let position = spec.find_attribute("position").unwrap();
+        assert_eq!(position.ty(), attribute::Type::Float2);
+        assert_eq!(position.offset(), 0);
+        assert!(spec.find_uniform("u_color").is_some());
+        assert_eq!(spec.uniform_size(), 16);
+        assert!(spec.find_varying("missing").is_none());
+    }
+
+    #[test]
+    fn specification_errors_are_reported() {
+        let error = MeshSpecification::new(
+            &[Attribute::new(attribute::Type::Float2, 0, "position")],
+            8,
+            &[],
+            "this is not SkSL",
+            FS,
+            None,
+            None,
+        )
+        .unwrap_err();
+        assert!(!error.is_empty());
+    }
+
+    #[test]
+    fn invalid_mesh_is_reported() {
+        let vertex_buffer = meshes::make_vertex_buffer(&vertex_bytes(&[[0.0, 0.0]])).unwrap();
+        // Two vertices do not fit into a buffer that holds only one.
+        let error = Mesh::new(
+            &spec(),
+            Mode::Triangles,
+            &vertex_buffer,
+            2,
+            0,
+            None,
+            &[],
+            Rect::from_wh(1.0, 1.0),
+        )
+        .unwrap_err();
+        assert!(!error.is_empty());
+    }
+
+    #[test]
+    fn draw_indexed_mesh() {
+        let vertices = vertex_bytes(&[[0.0, 0.0], [16.0, 0.0], [16.0, 16.0], [0.0, 16.0]]);
+        let vertex_buffer = meshes::make_vertex_buffer(&vertices).unwrap();
+        assert_eq!(vertex_buffer.size(), 32);
+        let index_buffer = meshes::make_index_buffer(&[0, 1, 2, 0, 2, 3]).unwrap();
+        assert_eq!(index_buffer.size(), 12);
+
+        let uniforms: Vec<u8> = [0.0f32, 0.0, 1.0, 1.0]
+            .iter()
+            .flat_map(|f| f.to_ne_bytes())
+            .collect();
+        let mesh = Mesh::new_indexed(
+            &spec(),
+            Mode::Triangles,
+            &vertex_buffer,
+            4,
+            0,
+            &index_buffer,
+            6,
+            0,
+            Data::new_copy(&uniforms),
+            &[],
+            Rect::from_wh(16.0, 16.0),
+        )
+        .unwrap();
+        assert!(mesh.is_valid());
+        assert_eq!(mesh.index_count(), 6);
+        assert_eq!(mesh.clone().vertex_count(), 4);
+
+        let mut surface = surfaces::raster_n32_premul((16, 16)).unwrap();
+        surface.canvas().clear(Color::WHITE);
+        let mut paint = Paint::default();
+        paint.set_color(Color::WHITE);
+        surface.canvas().draw_mesh(&mesh, None, &paint);
+
+        let pixmap = surface.peek_pixels().unwrap();
+        assert_eq!(pixmap.get_color((8, 8)), Color::BLUE);
+    }
+}
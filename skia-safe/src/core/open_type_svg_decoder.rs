@@ -0,0 +1,82 @@
+use std::mem;
+
+use skia_bindings::{
+    self as sb, RustOpenTypeSVGDecoder_Param, SkCanvas, SkColor, SkOpenTypeSVGDecoder, TraitObject,
+};
+
+use crate::{prelude::*, Canvas, Color, GlyphId};
+
+/// Renders the glyphs of the `SVG ` table of an OpenType font.
+///
+/// A decoder is created for every SVG document of a font by the factory that is set with
+/// [`crate::graphics::set_open_type_svg_decoder()`]. Skia caches decoders and may use them from
+/// different threads, but never concurrently.
+pub trait OpenTypeSVGDecoder: Send {
+    /// Returns the approximate number of bytes the decoder retains, which is used to limit the
+    /// size of the cache.
+    fn approximate_size(&self) -> usize;
+
+    /// Renders the glyph with `glyph_id` into `canvas`, which is set up so that one unit is one
+    /// font unit of a font with `upem` units per em.
+    ///
+    /// `foreground_color` is the color of the text, which SVG documents refer to as
+    /// `currentColor`. `palette` contains the colors of the selected font palette, which SVG
+    /// documents refer to as `var(--color0)`, `var(--color1)`, and so on.
+    ///
+    /// Returns `false` if the glyph could not be rendered.
+    fn render(
+        &mut self,
+        canvas: &Canvas,
+        upem: i32,
+        glyph_id: GlyphId,
+        foreground_color: Color,
+        palette: &[Color],
+    ) -> bool;
+}
+
+/// Moves `decoder` into a newly created native decoder.
+pub(crate) fn new_native_decoder(
+    decoder: Box<dyn OpenTypeSVGDecoder>,
+) -> *mut SkOpenTypeSVGDecoder {
+    let param = RustOpenTypeSVGDecoder_Param {
+        trait_: unsafe { mem::transmute::<Box<dyn OpenTypeSVGDecoder>, TraitObject>(decoder) },
+        drop: Some(drop),
+        approximateSize: Some(approximate_size),
+        render: Some(render),
+    };
+
+    return unsafe { sb::C_RustOpenTypeSVGDecoder_New(&param) };
+
+    extern "C" fn drop(decoder: TraitObject) {
+        mem::drop(unsafe { mem::transmute::<TraitObject, Box<dyn OpenTypeSVGDecoder>>(decoder) });
+    }
+
+    extern "C" fn approximate_size(decoder: TraitObject) -> usize {
+        unsafe { decoder_mut(decoder) }.approximate_size()
+    }
+
+    extern "C" fn render(
+        decoder: TraitObject,
+        canvas: *mut SkCanvas,
+        upem: i32,
+        glyph_id: GlyphId,
+        foreground_color: SkColor,
+        palette: *const SkColor,
+        palette_count: usize,
+    ) -> bool {
+        let canvas = Canvas::borrow_from_native(unsafe { &*canvas });
+        let palette =
+            unsafe { safer::from_raw_parts(Color::from_native_ptr(palette), palette_count) };
+        unsafe { decoder_mut(decoder) }.render(
+            canvas,
+            upem,
+            glyph_id,
+            Color::from_native_c(foreground_color),
+            palette,
+        )
+    }
+
+    unsafe fn decoder_mut<'a>(decoder: TraitObject) -> &'a mut dyn OpenTypeSVGDecoder {
+        mem::transmute(decoder)
+    }
+}
@@ -1,4 +1,7 @@
-use crate::{prelude::*, Canvas, Data, FilterMode, Matrix, Rect, Shader, TileMode};
+use crate::{
+    prelude::*, Canvas, Data, DeserialProcs, FilterMode, Matrix, Rect, SerialProcs, Shader,
+    TileMode,
+};
 use skia_bindings::{self as sb, SkPicture, SkRefCntBase};
 use std::fmt;
 
@@ -23,8 +26,6 @@ impl fmt::Debug for Picture {
 impl Picture {
     // TODO: wrap MakeFromStream
 
-    // TODO: may support SkSerialProcs in MakeFromData?
-
     pub fn from_data(data: &Data) -> Option<Picture> {
         Picture::from_ptr(unsafe { sb::C_SkPicture_MakeFromData(data.native()) })
     }
@@ -35,6 +36,13 @@ impl Picture {
         })
     }
 
+    /// Deserializes a picture and uses `procs` to resolve the pictures, images and typefaces it
+    /// references.
+    pub fn from_data_with_procs(data: &Data, procs: &mut DeserialProcs) -> Option<Picture> {
+        let procs = procs.native();
+        Picture::from_ptr(unsafe { sb::C_SkPicture_MakeFromDataWithProcs(data.native(), &procs) })
+    }
+
     // TODO: AbortCallback and the function that use it.
 
     pub fn playback(&self, canvas: &Canvas) {
@@ -49,12 +57,22 @@ impl Picture {
         unsafe { sb::C_SkPicture_uniqueID(self.native()) }
     }
 
-    // TODO: support SkSerialProcs in serialize()?
-
     pub fn serialize(&self) -> Data {
         Data::from_ptr(unsafe { sb::C_SkPicture_serialize(self.native()) }).unwrap()
     }
 
+    /// Serializes the picture and uses `procs` to serialize the pictures, images and typefaces it
+    /// references.
+    ///
+    /// If the typeface procedure of `procs` returns data, the picture must be deserialized with
+    /// [`Self::from_data_with_procs()`] and a typeface procedure that resolves the data. See
+    /// [`SerialProcs`] for how the data is stored.
+    pub fn serialize_with_procs(&self, procs: &mut SerialProcs) -> Data {
+        let procs = procs.native();
+        Data::from_ptr(unsafe { sb::C_SkPicture_serializeWithProcs(self.native(), &procs) })
+            .unwrap()
+    }
+
     pub fn new_placeholder(cull: impl AsRef<Rect>) -> Picture {
         Picture::from_ptr(unsafe { sb::C_SkPicture_MakePlaceholder(cull.as_ref().native()) })
             .unwrap()
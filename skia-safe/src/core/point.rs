@@ -98,6 +98,7 @@ pub type Vector = Point;
 
 #[repr(C)]
 #[derive(Copy, Clone, PartialEq, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Point {
     pub x: scalar,
     pub y: scalar,
@@ -25,6 +25,7 @@ variant_name!(MipmapMode::Nearest);
 /// Nice overview <https://entropymine.com/imageworsener/bicubic/>
 #[repr(C)]
 #[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CubicResampler {
     pub b: f32,
     pub c: f32,
@@ -0,0 +1,338 @@
+use std::{ffi::c_void, fmt, ptr};
+
+use skia_bindings::{RustDeserialProcs, RustSerialProcs, SkData, SkImage, SkPicture, SkTypeface};
+
+use crate::{prelude::*, Data, Image, Picture, Typeface};
+
+type SerialProc<'a, T> = Box<dyn FnMut(&T) -> Option<Data> + 'a>;
+type DeserialProc<'a, T> = Box<dyn FnMut(&[u8]) -> Option<T> + 'a>;
+
+/// Procedures that customize how the pictures, images and typefaces that are referenced by a
+/// [`Picture`] are serialized.
+///
+/// A procedure may return `None` to use Skia's default serialization for an object. This makes it
+/// possible to store references, like content hashes or asset ids, instead of the objects
+/// themselves and to resolve them with [`DeserialProcs`] when the picture is loaded.
+///
+/// The data a typeface procedure returns is written with a 4 byte marker and its length, so that
+/// it can be told apart from the typefaces Skia serializes. A picture that contains such data must
+/// be deserialized with [`DeserialProcs`] that provide a typeface procedure, otherwise text that
+/// uses these typefaces is drawn with the default typeface.
+#[derive(Default)]
+pub struct SerialProcs<'a> {
+    picture: Option<SerialProc<'a, Picture>>,
+    image: Option<SerialProc<'a, Image>>,
+    typeface: Option<SerialProc<'a, Typeface>>,
+}
+
+impl fmt::Debug for SerialProcs<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SerialProcs")
+            .field("picture", &self.picture.is_some())
+            .field("image", &self.image.is_some())
+            .field("typeface", &self.typeface.is_some())
+            .finish()
+    }
+}
+
+impl<'a> SerialProcs<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the procedure that serializes nested pictures.
+    pub fn picture_proc(mut self, proc: impl FnMut(&Picture) -> Option<Data> + 'a) -> Self {
+        self.picture = Some(Box::new(proc));
+        self
+    }
+
+    /// Sets the procedure that serializes images.
+    pub fn image_proc(mut self, proc: impl FnMut(&Image) -> Option<Data> + 'a) -> Self {
+        self.image = Some(Box::new(proc));
+        self
+    }
+
+    /// Sets the procedure that serializes typefaces.
+    pub fn typeface_proc(mut self, proc: impl FnMut(&Typeface) -> Option<Data> + 'a) -> Self {
+        self.typeface = Some(Box::new(proc));
+        self
+    }
+
+    /// Returns the native procs. `self` must not be moved while they are in use.
+    pub(crate) fn native(&mut self) -> RustSerialProcs {
+        return RustSerialProcs {
+            context: self as *mut Self as *mut c_void,
+            pictureProc: if self.picture.is_some() {
+                Some(picture_proc)
+            } else {
+                None
+            },
+            imageProc: if self.image.is_some() {
+                Some(image_proc)
+            } else {
+                None
+            },
+            typefaceProc: if self.typeface.is_some() {
+                Some(typeface_proc)
+            } else {
+                None
+            },
+        };
+
+        extern "C" fn picture_proc(context: *mut c_void, picture: *mut SkPicture) -> *mut SkData {
+            let picture = Picture::from_unshared_ptr(picture).unwrap();
+            let proc = unsafe { procs(context) }.picture.as_mut().unwrap();
+            proc(&picture)
+                .map(|data| data.into_ptr())
+                .unwrap_or(ptr::null_mut())
+        }
+
+        extern "C" fn image_proc(context: *mut c_void, image: *mut SkImage) -> *mut SkData {
+            let image = Image::from_unshared_ptr(image).unwrap();
+            let proc = unsafe { procs(context) }.image.as_mut().unwrap();
+            proc(&image)
+                .map(|data| data.into_ptr())
+                .unwrap_or(ptr::null_mut())
+        }
+
+        extern "C" fn typeface_proc(
+            context: *mut c_void,
+            typeface: *mut SkTypeface,
+        ) -> *mut SkData {
+            let typeface = Typeface::from_unshared_ptr(typeface).unwrap();
+            let proc = unsafe { procs(context) }.typeface.as_mut().unwrap();
+            proc(&typeface)
+                .map(|data| data.into_ptr())
+                .unwrap_or(ptr::null_mut())
+        }
+
+        unsafe fn procs<'a, 'b>(context: *mut c_void) -> &'b mut SerialProcs<'a> {
+            &mut *(context as *mut SerialProcs)
+        }
+    }
+}
+
+/// Procedures that resolve the pictures, images and typefaces that were serialized with
+/// [`SerialProcs`].
+///
+/// A procedure receives the data its [`SerialProcs`] counterpart returned. If a picture or image
+/// procedure returns `None`, Skia tries to decode the data itself. If a typeface procedure returns
+/// `None`, text that uses the typeface is drawn with the default typeface.
+///
+/// A typeface procedure is only called for the data a [`SerialProcs`] typeface procedure returned.
+/// The typefaces Skia serialized are deserialized by Skia, so pictures that were serialized without
+/// procedures can be read with a typeface procedure, too.
+#[derive(Default)]
+pub struct DeserialProcs<'a> {
+    picture: Option<DeserialProc<'a, Picture>>,
+    image: Option<DeserialProc<'a, Image>>,
+    typeface: Option<DeserialProc<'a, Typeface>>,
+}
+
+impl fmt::Debug for DeserialProcs<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DeserialProcs")
+            .field("picture", &self.picture.is_some())
+            .field("image", &self.image.is_some())
+            .field("typeface", &self.typeface.is_some())
+            .finish()
+    }
+}
+
+impl<'a> DeserialProcs<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the procedure that deserializes nested pictures.
+    pub fn picture_proc(mut self, proc: impl FnMut(&[u8]) -> Option<Picture> + 'a) -> Self {
+        self.picture = Some(Box::new(proc));
+        self
+    }
+
+    /// Sets the procedure that deserializes images.
+    pub fn image_proc(mut self, proc: impl FnMut(&[u8]) -> Option<Image> + 'a) -> Self {
+        self.image = Some(Box::new(proc));
+        self
+    }
+
+    /// Sets the procedure that deserializes typefaces.
+    pub fn typeface_proc(mut self, proc: impl FnMut(&[u8]) -> Option<Typeface> + 'a) -> Self {
+        self.typeface = Some(Box::new(proc));
+        self
+    }
+
+    /// Returns the native procs. `self` must not be moved while they are in use.
+    pub(crate) fn native(&mut self) -> RustDeserialProcs {
+        return RustDeserialProcs {
+            context: self as *mut Self as *mut c_void,
+            pictureProc: if self.picture.is_some() {
+                Some(picture_proc)
+            } else {
+                None
+            },
+            imageProc: if self.image.is_some() {
+                Some(image_proc)
+            } else {
+                None
+            },
+            typefaceProc: if self.typeface.is_some() {
+                Some(typeface_proc)
+            } else {
+                None
+            },
+        };
+
+        extern "C" fn picture_proc(
+            context: *mut c_void,
+            data: *const c_void,
+            length: usize,
+        ) -> *mut SkPicture {
+            let proc = unsafe { procs(context) }.picture.as_mut().unwrap();
+            proc(unsafe { bytes(data, length) }).into_ptr_or_null()
+        }
+
+        extern "C" fn image_proc(
+            context: *mut c_void,
+            data: *const c_void,
+            length: usize,
+        ) -> *mut SkImage {
+            let proc = unsafe { procs(context) }.image.as_mut().unwrap();
+            proc(unsafe { bytes(data, length) }).into_ptr_or_null()
+        }
+
+        extern "C" fn typeface_proc(
+            context: *mut c_void,
+            data: *const c_void,
+            length: usize,
+        ) -> *mut SkTypeface {
+            let proc = unsafe { procs(context) }.typeface.as_mut().unwrap();
+            proc(unsafe { bytes(data, length) }).into_ptr_or_null()
+        }
+
+        unsafe fn procs<'a, 'b>(context: *mut c_void) -> &'b mut DeserialProcs<'a> {
+            &mut *(context as *mut DeserialProcs)
+        }
+
+        unsafe fn bytes<'a>(data: *const c_void, length: usize) -> &'a [u8] {
+            safer::from_raw_parts(data as *const u8, length)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{DeserialProcs, SerialProcs};
+    use crate::{
+        surfaces, Color, Data, Font, FontMgr, FontStyle, Image, Paint, Picture, PictureRecorder,
+        Rect, Typeface,
+    };
+
+    fn record(image: &Image, typeface: &Typeface) -> Picture {
+        let mut recorder = PictureRecorder::new();
+        let canvas = recorder.begin_recording(Rect::new(0.0, 0.0, 100.0, 100.0), None);
+        canvas.draw_image(image, (0, 0), None);
+        let font = Font::from_typeface(typeface.clone(), 20.0);
+        canvas.draw_str("Hello", (10, 50), &font, &Paint::default());
+        canvas.draw_str("World", (10, 80), &font, &Paint::default());
+        recorder.finish_recording_as_picture(None).unwrap()
+    }
+
+    fn red_image() -> Image {
+        let mut surface = surfaces::raster_n32_premul((2, 2)).unwrap();
+        surface.canvas().clear(Color::RED);
+        surface.image_snapshot()
+    }
+
+    #[test]
+    fn references_are_serialized_and_resolved() {
+        let image = red_image();
+        let typeface = FontMgr::new()
+            .legacy_make_typeface(None, FontStyle::normal())
+            .unwrap();
+        let picture = record(&image, &typeface);
+
+        let mut typefaces_serialized = 0;
+        let data = picture.serialize_with_procs(
+            &mut SerialProcs::new()
+                .image_proc(|_| Some(Data::new_copy(b"image:red")))
+                .typeface_proc(|_| {
+                    typefaces_serialized += 1;
+                    Some(Data::new_copy(b"typeface:default"))
+                }),
+        );
+        assert_eq!(typefaces_serialized, 1);
+        // Neither the image's pixels nor the typeface's data are embedded.
+        assert!(data.size() < picture.serialize().size());
+
+        let assets: HashMap<&[u8], Image> = [(b"image:red".as_slice(), image)].into();
+        let mut resolved = Vec::new();
+        let deserialized = Picture::from_data_with_procs(
+            &data,
+            &mut DeserialProcs::new()
+                .image_proc(|id| assets.get(id).cloned())
+                .typeface_proc(|id| {
+                    resolved.push(id.to_vec());
+                    Some(typeface.clone())
+                }),
+        )
+        .unwrap();
+
+        assert_eq!(resolved, [b"typeface:default".to_vec()]);
+        assert_eq!(deserialized.cull_rect(), picture.cull_rect());
+
+        let mut surface = surfaces::raster_n32_premul((100, 100)).unwrap();
+        surface.canvas().draw_picture(&deserialized, None, None);
+        let pixmap = surface.peek_pixels().unwrap();
+        assert_eq!(pixmap.get_color((1, 1)), Color::RED);
+    }
+
+    #[test]
+    fn pictures_serialized_without_procs_are_read_with_a_typeface_proc() {
+        let typeface = FontMgr::new()
+            .legacy_make_typeface(None, FontStyle::normal())
+            .unwrap();
+        let picture = record(&red_image(), &typeface);
+
+        let mut resolved = 0;
+        let deserialized = Picture::from_data_with_procs(
+            &picture.serialize(),
+            &mut DeserialProcs::new().typeface_proc(|_| {
+                resolved += 1;
+                None
+            }),
+        )
+        .unwrap();
+        assert_eq!(resolved, 0);
+        assert_eq!(deserialized.cull_rect(), picture.cull_rect());
+
+        let mut surface = surfaces::raster_n32_premul((100, 100)).unwrap();
+        surface.canvas().draw_picture(&deserialized, None, None);
+        let pixmap = surface.peek_pixels().unwrap();
+        assert_eq!(pixmap.get_color((1, 1)), Color::RED);
+    }
+
+    #[test]
+    fn procs_returning_none_use_the_default_serialization() {
+        let image = red_image();
+        let typeface = FontMgr::new()
+            .legacy_make_typeface(None, FontStyle::normal())
+            .unwrap();
+        let picture = record(&image, &typeface);
+
+        let data = picture.serialize_with_procs(&mut SerialProcs::new().typeface_proc(|_| None));
+        let mut resolved = 0;
+        let deserialized = Picture::from_data_with_procs(
+            &data,
+            &mut DeserialProcs::new().typeface_proc(|_| {
+                resolved += 1;
+                None
+            }),
+        )
+        .unwrap();
+        assert_eq!(resolved, 0);
+        assert_eq!(deserialized.cull_rect(), picture.cull_rect());
+    }
+}
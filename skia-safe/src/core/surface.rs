@@ -3,8 +3,11 @@ use std::{fmt, ptr};
 use skia_bindings::{self as sb, SkRefCntBase, SkSurface};
 
 use crate::{
-    gpu, prelude::*, Bitmap, Canvas, IPoint, IRect, ISize, Image, ImageInfo, Paint, Pixmap, Point,
-    SamplingOptions, SurfaceProps,
+    gpu,
+    image::{self, AsyncReadResult, RescaleGamma, RescaleMode},
+    prelude::*,
+    Bitmap, Canvas, ColorSpace, IPoint, IRect, ISize, Image, ImageInfo, Paint, Pixmap, Point,
+    SamplingOptions, SurfaceProps, YUVColorSpace,
 };
 
 pub mod surfaces {
@@ -871,10 +874,131 @@ impl Surface {
         unsafe { self.native_mut().readPixels2(bitmap.native(), src.x, src.y) }
     }
 
-    // TODO: AsyncReadResult, RescaleGamma (m79, m86)
-    // TODO: wrap asyncRescaleAndReadPixels (m76, m79, m89)
-    // TODO: wrap asyncRescaleAndReadPixelsYUV420 (m77, m79, m89)
-    // TODO: wrap asyncRescaleAndReadPixelsYUVA420 (m117)
+    /// Makes surface pixel data available to the caller, possibly asynchronously. It can also
+    /// rescale the surface pixels.
+    ///
+    /// Currently asynchronous reads are only supported on the GPU backend and only when the
+    /// underlying 3D API supports transfer buffers and CPU/GPU synchronization primitives. In all
+    /// other cases this operates synchronously.
+    ///
+    /// Data is read from the source sub-rectangle, is optionally converted to a linear gamma, is
+    /// rescaled to the size indicated by `info`, is then converted to the color space, color
+    /// type, and alpha type of `info`. A `src_rect` that is not contained by the bounds of the
+    /// surface causes failure.
+    ///
+    /// When the pixel data is ready the caller's `callback` is called with an
+    /// [`AsyncReadResult`] containing pixel data in the requested color type, alpha type, and
+    /// color space. The [`AsyncReadResult`] will have `count() == 1`. Upon failure the callback
+    /// is called with `None`. For a GPU surface this flushes work but a submit must occur to
+    /// guarantee a finite time before the callback is called.
+    ///
+    /// The data is valid for the lifetime of [`AsyncReadResult`] with the exception that if the
+    /// [`Surface`] is GPU-backed the data is immediately invalidated if the context is abandoned
+    /// or destroyed.
+    ///
+    /// * `info` - info of the requested pixels
+    /// * `src_rect` - subrectangle of surface to read
+    /// * `rescale_gamma` - controls whether rescaling is done in the surface's gamma or whether
+    ///                     the source data is transformed to a linear gamma before rescaling.
+    /// * `rescale_mode` - controls the technique (and cost) of the rescaling
+    /// * `callback` - function to call with result of the read
+    pub fn async_rescale_and_read_pixels(
+        &mut self,
+        info: &ImageInfo,
+        src_rect: impl AsRef<IRect>,
+        rescale_gamma: RescaleGamma,
+        rescale_mode: RescaleMode,
+        callback: impl FnOnce(Option<AsyncReadResult>) + 'static,
+    ) {
+        unsafe {
+            sb::C_SkSurface_asyncRescaleAndReadPixels(
+                self.native_mut(),
+                info.native(),
+                src_rect.as_ref().native(),
+                rescale_gamma,
+                rescale_mode,
+                Some(image::read_pixels_callback),
+                image::ReadPixelsContext::rgba(info, Box::new(callback)),
+            )
+        }
+    }
+
+    /// Similar to [`Self::async_rescale_and_read_pixels()`] but performs an additional conversion
+    /// to YUV. The RGB->YUV conversion is controlled by `yuv_color_space`. The YUV data is
+    /// returned as three planes ordered y, u, v. The u and v planes are half the width and
+    /// height of the resized rectangle. The y, u, and v values are single bytes. Currently this
+    /// fails if `dst_size` width and height are not even. A `src_rect` that is not contained by
+    /// the bounds of the surface causes failure.
+    ///
+    /// When the pixel data is ready the caller's `callback` is called with an
+    /// [`AsyncReadResult`] containing the planar data. The [`AsyncReadResult`] will have
+    /// `count() == 3`. Upon failure the callback is called with `None`.
+    ///
+    /// * `yuv_color_space` - The transformation from RGB to YUV. Applied to the resized image
+    ///                       after it is converted to `dst_color_space`.
+    /// * `dst_color_space` - The color space to convert the resized image to, after rescaling.
+    /// * `src_rect` - The portion of the surface to rescale and convert to YUV planes.
+    /// * `dst_size` - The size to rescale `src_rect` to
+    /// * `rescale_gamma` - controls whether rescaling is done in the surface's gamma or whether
+    ///                     the source data is transformed to a linear gamma before rescaling.
+    /// * `rescale_mode` - controls the technique (and cost) of the rescaling
+    /// * `callback` - function to call with the planar read result
+    #[allow(clippy::too_many_arguments)]
+    pub fn async_rescale_and_read_pixels_yuv420(
+        &mut self,
+        yuv_color_space: YUVColorSpace,
+        dst_color_space: impl Into<Option<ColorSpace>>,
+        src_rect: impl AsRef<IRect>,
+        dst_size: impl Into<ISize>,
+        rescale_gamma: RescaleGamma,
+        rescale_mode: RescaleMode,
+        callback: impl FnOnce(Option<AsyncReadResult>) + 'static,
+    ) {
+        let dst_size = dst_size.into();
+        unsafe {
+            sb::C_SkSurface_asyncRescaleAndReadPixelsYUV420(
+                self.native_mut(),
+                yuv_color_space,
+                dst_color_space.into().into_ptr_or_null(),
+                src_rect.as_ref().native(),
+                dst_size.native(),
+                rescale_gamma,
+                rescale_mode,
+                Some(image::read_pixels_callback),
+                image::ReadPixelsContext::yuv420(dst_size, false, Box::new(callback)),
+            )
+        }
+    }
+
+    /// Identical to [`Self::async_rescale_and_read_pixels_yuv420()`] but a fourth plane is
+    /// returned in the [`AsyncReadResult`] passed to `callback`. The fourth plane contains the
+    /// alpha channel at the same full resolution as the Y plane.
+    #[allow(clippy::too_many_arguments)]
+    pub fn async_rescale_and_read_pixels_yuva420(
+        &mut self,
+        yuv_color_space: YUVColorSpace,
+        dst_color_space: impl Into<Option<ColorSpace>>,
+        src_rect: impl AsRef<IRect>,
+        dst_size: impl Into<ISize>,
+        rescale_gamma: RescaleGamma,
+        rescale_mode: RescaleMode,
+        callback: impl FnOnce(Option<AsyncReadResult>) + 'static,
+    ) {
+        let dst_size = dst_size.into();
+        unsafe {
+            sb::C_SkSurface_asyncRescaleAndReadPixelsYUVA420(
+                self.native_mut(),
+                yuv_color_space,
+                dst_color_space.into().into_ptr_or_null(),
+                src_rect.as_ref().native(),
+                dst_size.native(),
+                rescale_gamma,
+                rescale_mode,
+                Some(image::read_pixels_callback),
+                image::ReadPixelsContext::yuv420(dst_size, true, Box::new(callback)),
+            )
+        }
+    }
 
     /// Copies [`crate::Rect`] of pixels from the src [`Pixmap`] to the [`Surface`].
     ///
@@ -949,6 +1073,8 @@ impl Surface {
 
 #[cfg(test)]
 mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
     use super::*;
 
     #[test]
@@ -1000,4 +1126,106 @@ mod tests {
             surface.draw(canvas, (10.0, 10.0), SamplingOptions::default(), None);
         }
     }
+
+    // Skia calls the callback from native code, where a failing assert would abort the test
+    // binary, so the result is taken out of the callback and checked afterwards.
+    fn capture_read_result() -> (
+        Rc<RefCell<Option<Option<AsyncReadResult>>>>,
+        impl FnOnce(Option<AsyncReadResult>) + 'static,
+    ) {
+        let captured = Rc::new(RefCell::new(None));
+        let captured_in_callback = captured.clone();
+        let callback = move |result| *captured_in_callback.borrow_mut() = Some(result);
+        (captured, callback)
+    }
+
+    #[test]
+    fn async_rescale_and_read_pixels() {
+        let mut surface = surfaces::raster_n32_premul((100, 100)).unwrap();
+        surface.canvas().clear(crate::Color::RED);
+
+        let info = ImageInfo::new(
+            (50, 50),
+            crate::ColorType::RGBA8888,
+            crate::AlphaType::Premul,
+            None,
+        );
+        let (captured, callback) = capture_read_result();
+        surface.async_rescale_and_read_pixels(
+            &info,
+            IRect::from_wh(100, 100),
+            RescaleGamma::Src,
+            RescaleMode::Linear,
+            callback,
+        );
+
+        // Raster surfaces complete the read synchronously.
+        let result = captured.borrow_mut().take().unwrap().unwrap();
+        assert_eq!(result.count(), 1);
+        assert_eq!(result.height(0), 50);
+        assert!(result.row_bytes(0) >= 50 * 4);
+        assert_eq!(result.data(0).len(), result.row_bytes(0) * 49 + 50 * 4);
+        assert_eq!(result.data(0)[..4], [0xff, 0, 0, 0xff]);
+    }
+
+    #[test]
+    fn async_rescale_and_read_pixels_yuv420() {
+        let mut surface = surfaces::raster_n32_premul((100, 100)).unwrap();
+        surface.canvas().clear(crate::Color::WHITE);
+
+        let (captured, callback) = capture_read_result();
+        surface.async_rescale_and_read_pixels_yuv420(
+            YUVColorSpace::JPEG,
+            None,
+            IRect::from_wh(100, 100),
+            (50, 50),
+            RescaleGamma::Src,
+            RescaleMode::Nearest,
+            callback,
+        );
+
+        let result = captured.borrow_mut().take().unwrap().unwrap();
+        assert_eq!(result.count(), 3);
+        assert_eq!(result.height(0), 50);
+        assert_eq!(result.height(1), 25);
+        assert_eq!(result.height(2), 25);
+        assert_eq!(result.data(1).len(), result.row_bytes(1) * 24 + 25);
+    }
+
+    #[test]
+    fn async_rescale_and_read_pixels_yuva420() {
+        let mut surface = surfaces::raster_n32_premul((100, 100)).unwrap();
+        surface.canvas().clear(crate::Color::WHITE);
+
+        let (captured, callback) = capture_read_result();
+        surface.async_rescale_and_read_pixels_yuva420(
+            YUVColorSpace::JPEG,
+            None,
+            IRect::from_wh(100, 100),
+            (50, 50),
+            RescaleGamma::Src,
+            RescaleMode::Nearest,
+            callback,
+        );
+
+        let result = captured.borrow_mut().take().unwrap().unwrap();
+        assert_eq!(result.count(), 4);
+        assert_eq!(result.height(3), 50);
+        assert_eq!(result.data(3).len(), result.row_bytes(3) * 49 + 50);
+    }
+
+    #[test]
+    fn async_rescale_and_read_pixels_fails_outside_bounds() {
+        let mut surface = surfaces::raster_n32_premul((10, 10)).unwrap();
+        let info = ImageInfo::new_n32_premul((10, 10), None);
+        let (captured, callback) = capture_read_result();
+        surface.async_rescale_and_read_pixels(
+            &info,
+            IRect::from_wh(20, 20),
+            RescaleGamma::Src,
+            RescaleMode::Nearest,
+            callback,
+        );
+        assert!(captured.borrow_mut().take().unwrap().is_none());
+    }
 }
@@ -1,7 +1,8 @@
 use crate::{
     interop::{self, AsStr},
     prelude::*,
-    Blender, ColorFilter, Data, Matrix, Shader,
+    sksl::DebugTrace,
+    Blender, Color4f, ColorFilter, Data, IPoint, Matrix, Point, Point3, Shader, M44,
 };
 use sb::{SkFlattenable, SkRuntimeEffect_Child};
 use skia_bindings::{
@@ -275,7 +276,28 @@ impl RuntimeEffect {
         })
     }
 
-    // TODO: wrap MakeTraced
+    /// Wraps a runtime effect shader so that its evaluation of the pixel at `trace_coord` is
+    /// recorded into a [`DebugTrace`] when it is drawn with the CPU raster backend.
+    ///
+    /// Returns `None` if `shader` is not a runtime effect shader.
+    pub fn make_traced(
+        shader: impl Into<Shader>,
+        trace_coord: impl Into<IPoint>,
+    ) -> Option<TracedShader> {
+        let trace_coord = trace_coord.into();
+        let mut debug_trace = ptr::null_mut();
+        let shader = Shader::from_ptr(unsafe {
+            sb::C_SkRuntimeEffect_MakeTraced(
+                shader.into().into_ptr(),
+                trace_coord.native(),
+                &mut debug_trace,
+            )
+        })?;
+        Some(TracedShader {
+            shader,
+            debug_trace: DebugTrace::from_ptr(debug_trace)?,
+        })
+    }
 
     pub fn source(&self) -> &str {
         let mut len = 0;
@@ -345,6 +367,13 @@ impl RuntimeEffect {
     }
 }
 
+/// A shader returned by [`RuntimeEffect::make_traced()`] and the trace it records into.
+#[derive(Clone, Debug)]
+pub struct TracedShader {
+    pub shader: Shader,
+    pub debug_trace: DebugTrace,
+}
+
 #[derive(Clone, Debug)]
 pub enum ChildPtr {
     Shader(Shader),
@@ -386,7 +415,7 @@ impl ChildPtr {
     //
     // Skia will copy the pointers and increase the reference counters if it uses the actual
     // objects.
-    pub(self) fn native(&self) -> Borrows<sb::SkRuntimeEffect_ChildPtr> {
+    pub(crate) fn native(&self) -> Borrows<sb::SkRuntimeEffect_ChildPtr> {
         let flattenable: *mut SkFlattenable = match self {
             // casting to &T &mut T is UB, so we don't use the base() indirection and directly cast
             // to a pointer.
@@ -405,8 +434,473 @@ impl ChildPtr {
     }
 }
 
-// TODO: wrap SkRuntimeEffectBuilder, SkRuntimeColorFilterBuilder,
-// SkRuntimeBlendBuilder
+/// A Rust value that can be assigned to an SkSL uniform with
+/// [`RuntimeEffectBuilder::set_uniform()`] or [`RuntimeEffectBuilder::set_uniform_array()`].
+pub trait UniformValue {
+    /// Returns `true` if values of this type can be assigned to uniforms of the SkSL type `ty`.
+    fn accepts(ty: uniform::Type) -> bool;
+
+    /// Appends the uniform's memory representation to `bytes`. Matrices are written in column
+    /// major order, as SkSL expects them.
+    fn write_bytes(&self, bytes: &mut Vec<u8>);
+}
+
+macro_rules! impl_uniform_value {
+    ($t:ty, [$($ty:ident),+], |$v:ident| $floats:expr) => {
+        impl UniformValue for $t {
+            fn accepts(ty: uniform::Type) -> bool {
+                matches!(ty, $(uniform::Type::$ty)|+)
+            }
+
+            fn write_bytes(&self, bytes: &mut Vec<u8>) {
+                let $v = self;
+                for v in $floats {
+                    bytes.extend_from_slice(&v.to_ne_bytes())
+                }
+            }
+        }
+    };
+}
+
+impl_uniform_value!(f32, [Float], |v| [*v]);
+impl_uniform_value!([f32; 2], [Float2], |v| *v);
+impl_uniform_value!([f32; 3], [Float3], |v| *v);
+impl_uniform_value!([f32; 4], [Float4, Float2x2], |v| *v);
+impl_uniform_value!([f32; 9], [Float3x3], |v| *v);
+impl_uniform_value!([f32; 16], [Float4x4], |v| *v);
+impl_uniform_value!(i32, [Int], |v| [*v]);
+impl_uniform_value!([i32; 2], [Int2], |v| *v);
+impl_uniform_value!([i32; 3], [Int3], |v| *v);
+impl_uniform_value!([i32; 4], [Int4], |v| *v);
+impl_uniform_value!(Point, [Float2], |v| [v.x, v.y]);
+impl_uniform_value!(Point3, [Float3], |v| [v.x, v.y, v.z]);
+impl_uniform_value!(Color4f, [Float4], |v| [v.r, v.g, v.b, v.a]);
+impl_uniform_value!(Matrix, [Float3x3], |v| [
+    v[0], v[3], v[6], v[1], v[4], v[7], v[2], v[5], v[8]
+]);
+impl_uniform_value!(M44, [Float4x4], |v| {
+    let mut col_major = [0.0; 16];
+    v.get_col_major(&mut col_major);
+    col_major
+});
+
+/// Errors reported by [`RuntimeEffectBuilder`] when a uniform or child does not match its
+/// declaration in the effect's SkSL.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum BuilderError {
+    /// The effect does not declare a uniform of this name.
+    UniformNotFound(String),
+    /// The effect declares a uniform that is not provided.
+    UniformMissing(String),
+    /// The uniform is provided more than once.
+    UniformDuplicate(String),
+    /// The uniform is provided at a different position than the effect declares it.
+    UniformOrderMismatch {
+        name: String,
+        index: usize,
+        expected_index: usize,
+    },
+    /// The uniform is declared with an SkSL type that the Rust value can not be assigned to.
+    UniformTypeMismatch {
+        name: String,
+        ty: uniform::Type,
+        value_type: &'static str,
+    },
+    /// The number of values does not match the uniform's array size.
+    UniformCountMismatch {
+        name: String,
+        count: usize,
+        value_count: usize,
+    },
+    /// The uniform is not located at the offset at which the effect declares it.
+    UniformOffsetMismatch {
+        name: String,
+        offset: usize,
+        expected_offset: usize,
+    },
+    /// The effect does not declare a child of this name.
+    ChildNotFound(String),
+    /// The child is declared with a different type.
+    ChildTypeMismatch {
+        name: String,
+        ty: ChildType,
+        child_type: ChildType,
+    },
+}
+
+impl fmt::Display for BuilderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuilderError::UniformNotFound(name) => write!(f, "No uniform named '{name}'"),
+            BuilderError::UniformMissing(name) => write!(f, "Uniform '{name}' is not provided"),
+            BuilderError::UniformDuplicate(name) => {
+                write!(f, "Uniform '{name}' is provided more than once")
+            }
+            BuilderError::UniformOrderMismatch {
+                name,
+                index,
+                expected_index,
+            } => write!(
+                f,
+                "Uniform '{name}' is provided at position {index}, but is declared at position {expected_index}"
+            ),
+            BuilderError::UniformTypeMismatch {
+                name,
+                ty,
+                value_type,
+            } => write!(
+                f,
+                "Uniform '{name}' is declared as {ty:?}, which can't be set from a {value_type}"
+            ),
+            BuilderError::UniformCountMismatch {
+                name,
+                count,
+                value_count,
+            } => write!(
+                f,
+                "Uniform '{name}' holds {count} value(s), but {value_count} were provided"
+            ),
+            BuilderError::UniformOffsetMismatch {
+                name,
+                offset,
+                expected_offset,
+            } => write!(
+                f,
+                "Uniform '{name}' is located at offset {offset}, but is declared at offset {expected_offset}"
+            ),
+            BuilderError::ChildNotFound(name) => write!(f, "No child named '{name}'"),
+            BuilderError::ChildTypeMismatch {
+                name,
+                ty,
+                child_type,
+            } => write!(
+                f,
+                "Child '{name}' is declared as {ty:?}, but a {child_type:?} was provided"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for BuilderError {}
+
+/// Describes a field of a [`Uniforms`] struct and the uniform it provides.
+#[derive(Copy, Clone, Debug)]
+pub struct UniformDescriptor {
+    /// The name of the uniform in SkSL.
+    pub name: &'static str,
+    /// The name of the Rust type of a single value, used in error messages.
+    pub value_type: &'static str,
+    /// The number of values, `1` for uniforms that are not arrays.
+    pub count: usize,
+    pub is_array: bool,
+    /// Returns `true` if the Rust type can be assigned to uniforms of the given SkSL type.
+    pub accepts: fn(uniform::Type) -> bool,
+}
+
+/// A Rust struct that provides the values of all uniforms of a runtime effect in the order they
+/// are declared in SkSL.
+///
+/// With the `sksl-macros` feature, this trait can be derived with `#[derive(Uniforms)]`. Use
+/// [`TypedRuntimeEffect`] to verify that the struct matches the uniforms of an effect.
+pub trait Uniforms {
+    /// The uniforms in declaration order.
+    const DESCRIPTORS: &'static [UniformDescriptor];
+
+    /// Appends the memory representation of all uniforms to `bytes`.
+    fn write_bytes(&self, bytes: &mut Vec<u8>);
+
+    /// Returns the uniform [`Data`] to create shaders, color filters, or blenders from.
+    fn to_data(&self) -> Data {
+        let mut bytes = Vec::new();
+        self.write_bytes(&mut bytes);
+        Data::new_copy(&bytes)
+    }
+}
+
+#[cfg(feature = "sksl-macros")]
+pub use skia_sksl_macros::Uniforms;
+
+impl RuntimeEffect {
+    /// Verifies that the fields of `U` match the names, order, types, array sizes, and offsets of
+    /// the uniforms this effect declares.
+    pub fn validate_uniforms<U: Uniforms>(&self) -> Result<(), BuilderError> {
+        let uniforms = self.uniforms();
+        let mut offset = 0;
+        for (i, descriptor) in U::DESCRIPTORS.iter().enumerate() {
+            let name = descriptor.name;
+            if U::DESCRIPTORS[..i].iter().any(|d| d.name == name) {
+                return Err(BuilderError::UniformDuplicate(name.into()));
+            }
+            let Some(expected_index) = uniforms.iter().position(|u| u.name() == name) else {
+                return Err(BuilderError::UniformNotFound(name.into()));
+            };
+            let uniform = &uniforms[expected_index];
+            if !(descriptor.accepts)(uniform.ty()) {
+                return Err(BuilderError::UniformTypeMismatch {
+                    name: name.into(),
+                    ty: uniform.ty(),
+                    value_type: descriptor.value_type,
+                });
+            }
+            let count: usize = uniform.count().try_into().unwrap();
+            if descriptor.count != count || descriptor.is_array != uniform.is_array() {
+                return Err(BuilderError::UniformCountMismatch {
+                    name: name.into(),
+                    count,
+                    value_count: descriptor.count,
+                });
+            }
+            if expected_index != i {
+                return Err(BuilderError::UniformOrderMismatch {
+                    name: name.into(),
+                    index: i,
+                    expected_index,
+                });
+            }
+            if uniform.offset() != offset {
+                return Err(BuilderError::UniformOffsetMismatch {
+                    name: name.into(),
+                    offset,
+                    expected_offset: uniform.offset(),
+                });
+            }
+            offset += uniform.size_in_bytes();
+        }
+
+        match uniforms.get(U::DESCRIPTORS.len()) {
+            Some(missing) => Err(BuilderError::UniformMissing(missing.name().into())),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A [`RuntimeEffect`] whose uniforms are verified to match the Rust struct `U`.
+///
+/// The verification happens once when the effect is created, shaders, color filters, and
+/// blenders are then created from values of `U`.
+pub struct TypedRuntimeEffect<U> {
+    effect: RuntimeEffect,
+    uniforms: PhantomData<fn(&U)>,
+}
+
+impl<U> Clone for TypedRuntimeEffect<U> {
+    fn clone(&self) -> Self {
+        Self {
+            effect: self.effect.clone(),
+            uniforms: PhantomData,
+        }
+    }
+}
+
+impl<U> fmt::Debug for TypedRuntimeEffect<U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TypedRuntimeEffect")
+            .field("effect", &self.effect)
+            .finish()
+    }
+}
+
+impl<U: Uniforms> TypedRuntimeEffect<U> {
+    pub fn new(effect: RuntimeEffect) -> Result<Self, BuilderError> {
+        effect.validate_uniforms::<U>()?;
+        Ok(Self {
+            effect,
+            uniforms: PhantomData,
+        })
+    }
+
+    pub fn effect(&self) -> &RuntimeEffect {
+        &self.effect
+    }
+
+    pub fn make_shader<'a>(
+        &self,
+        uniforms: &U,
+        children: &[ChildPtr],
+        local_matrix: impl Into<Option<&'a Matrix>>,
+    ) -> Option<Shader> {
+        self.effect
+            .make_shader(uniforms.to_data(), children, local_matrix)
+    }
+
+    pub fn make_color_filter<'a>(
+        &self,
+        uniforms: &U,
+        children: impl Into<Option<&'a [ChildPtr]>>,
+    ) -> Option<ColorFilter> {
+        self.effect.make_color_filter(uniforms.to_data(), children)
+    }
+
+    pub fn make_blender<'a>(
+        &self,
+        uniforms: &U,
+        children: impl Into<Option<&'a [ChildPtr]>>,
+    ) -> Option<Blender> {
+        self.effect.make_blender(uniforms.to_data(), children)
+    }
+}
+
+/// Collects the uniform values and children of a [`RuntimeEffect`] by name and creates shaders,
+/// color filters, or blenders from them.
+///
+/// Every value is validated against the effect's declarations, so a mismatch in type or array
+/// size is reported as a [`BuilderError`] instead of being packed into the wrong bytes. Uniforms
+/// that are not set are zero, children that are not set are `null`.
+#[derive(Clone, Debug)]
+pub struct RuntimeEffectBuilder {
+    effect: RuntimeEffect,
+    uniforms: Vec<u8>,
+    children: Vec<Option<ChildPtr>>,
+}
+
+impl RuntimeEffectBuilder {
+    pub fn new(effect: RuntimeEffect) -> Self {
+        let uniforms = vec![0; effect.uniform_size()];
+        let children = vec![None; effect.children().len()];
+        Self {
+            effect,
+            uniforms,
+            children,
+        }
+    }
+
+    pub fn effect(&self) -> &RuntimeEffect {
+        &self.effect
+    }
+
+    /// The uniform values in the layout the effect expects.
+    pub fn uniforms(&self) -> &[u8] {
+        &self.uniforms
+    }
+
+    pub fn children(&self) -> &[Option<ChildPtr>] {
+        &self.children
+    }
+
+    /// Sets the uniform `name` to `value`. The uniform must not be an array of more than one
+    /// element.
+    pub fn set_uniform<T: UniformValue>(
+        &mut self,
+        name: impl AsRef<str>,
+        value: T,
+    ) -> Result<&mut Self, BuilderError> {
+        self.set_uniform_array(name, &[value])
+    }
+
+    /// Sets all elements of the uniform array `name`. The number of `values` must match the
+    /// declared array size.
+    pub fn set_uniform_array<T: UniformValue>(
+        &mut self,
+        name: impl AsRef<str>,
+        values: &[T],
+    ) -> Result<&mut Self, BuilderError> {
+        let name = name.as_ref();
+        let uniform = self
+            .effect
+            .find_uniform(name)
+            .ok_or_else(|| BuilderError::UniformNotFound(name.into()))?;
+        if !T::accepts(uniform.ty()) {
+            return Err(BuilderError::UniformTypeMismatch {
+                name: name.into(),
+                ty: uniform.ty(),
+                value_type: std::any::type_name::<T>(),
+            });
+        }
+        let count = uniform.count().try_into().unwrap();
+        if values.len() != count {
+            return Err(BuilderError::UniformCountMismatch {
+                name: name.into(),
+                count,
+                value_count: values.len(),
+            });
+        }
+
+        let mut bytes = Vec::with_capacity(uniform.size_in_bytes());
+        values.iter().for_each(|v| v.write_bytes(&mut bytes));
+        assert_eq!(bytes.len(), uniform.size_in_bytes());
+        let offset = uniform.offset();
+        self.uniforms[offset..offset + bytes.len()].copy_from_slice(&bytes);
+        Ok(self)
+    }
+
+    /// Sets the child `name` to a [`Shader`], [`ColorFilter`], or [`Blender`] of the declared
+    /// type.
+    pub fn set_child(
+        &mut self,
+        name: impl AsRef<str>,
+        child: impl Into<ChildPtr>,
+    ) -> Result<&mut Self, BuilderError> {
+        let name = name.as_ref();
+        let child = child.into();
+        let declaration = self
+            .effect
+            .find_child(name)
+            .ok_or_else(|| BuilderError::ChildNotFound(name.into()))?;
+        if declaration.ty() != child.ty() {
+            return Err(BuilderError::ChildTypeMismatch {
+                name: name.into(),
+                ty: declaration.ty(),
+                child_type: child.ty(),
+            });
+        }
+        let index = declaration.index();
+        self.children[index] = Some(child);
+        Ok(self)
+    }
+
+    pub fn make_shader<'a>(&self, local_matrix: impl Into<Option<&'a Matrix>>) -> Option<Shader> {
+        let mut children = self.native_children();
+        Shader::from_ptr(unsafe {
+            sb::C_SkRuntimeEffect_makeShader(
+                self.effect.native(),
+                Data::new_copy(&self.uniforms).into_ptr(),
+                children.as_mut_ptr(),
+                children.len(),
+                local_matrix.into().native_ptr_or_null(),
+            )
+        })
+    }
+
+    pub fn make_color_filter(&self) -> Option<ColorFilter> {
+        let mut children = self.native_children();
+        ColorFilter::from_ptr(unsafe {
+            sb::C_SkRuntimeEffect_makeColorFilter(
+                self.effect.native(),
+                Data::new_copy(&self.uniforms).into_ptr(),
+                children.as_mut_ptr(),
+                children.len(),
+            )
+        })
+    }
+
+    pub fn make_blender(&self) -> Option<Blender> {
+        let mut children = self.native_children();
+        Blender::from_ptr(unsafe {
+            sb::C_SkRuntimeEffect_makeBlender(
+                self.effect.native(),
+                Data::new_copy(&self.uniforms).into_ptr(),
+                children.as_mut_ptr(),
+                children.len(),
+            )
+        })
+    }
+
+    // Children that are not set are passed as `null`, which Skia accepts for all child types.
+    fn native_children(&self) -> Vec<sb::SkRuntimeEffect_ChildPtr> {
+        self.children
+            .iter()
+            .map(|child| match child {
+                Some(child) => unsafe { child.native().release() },
+                None => sb::SkRuntimeEffect_ChildPtr {
+                    fChild: sb::sk_sp {
+                        fPtr: ptr::null_mut(),
+                        _phantom_0: PhantomData,
+                    },
+                },
+            })
+            .collect()
+    }
+}
 
 pub type RuntimeShaderBuilder = Handle<sb::SkRuntimeShaderBuilder>;
 unsafe_send_sync!(RuntimeShaderBuilder);
@@ -513,3 +1007,281 @@ impl RuntimeShaderBuilder {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{surfaces, Color, Paint};
+
+    const SKSL: &str = r#"
+        uniform shader child;
+        uniform half4 color;
+        uniform float3x3 transform;
+        uniform float weights[3];
+        uniform int mode;
+
+        half4 main(float2 coords) {
+            return mode == 1 ? color : child.eval(coords);
+        }
+    "#;
+
+    fn builder() -> RuntimeEffectBuilder {
+        RuntimeEffectBuilder::new(RuntimeEffect::make_for_shader(SKSL, None).unwrap())
+    }
+
+    #[test]
+    fn uniforms_are_written_at_their_offsets() {
+        let mut builder = builder();
+        builder
+            .set_uniform("color", Color4f::new(0.0, 0.0, 1.0, 1.0))
+            .unwrap()
+            .set_uniform("transform", Matrix::translate((2.0, 3.0)))
+            .unwrap()
+            .set_uniform_array("weights", &[1.0f32, 2.0, 3.0])
+            .unwrap()
+            .set_uniform("mode", 1)
+            .unwrap();
+
+        let effect = builder.effect().clone();
+        let read = |name: &str, index: usize| {
+            let offset = effect.find_uniform(name).unwrap().offset() + index * 4;
+            builder.uniforms()[offset..offset + 4].try_into().unwrap()
+        };
+        assert_eq!(f32::from_ne_bytes(read("color", 2)), 1.0);
+        // Column major: the translation is stored in the third column.
+        assert_eq!(f32::from_ne_bytes(read("transform", 6)), 2.0);
+        assert_eq!(f32::from_ne_bytes(read("transform", 7)), 3.0);
+        assert_eq!(f32::from_ne_bytes(read("weights", 2)), 3.0);
+        assert_eq!(i32::from_ne_bytes(read("mode", 0)), 1);
+    }
+
+    #[test]
+    fn mismatches_are_reported() {
+        let mut builder = builder();
+        assert_eq!(
+            builder.set_uniform("missing", 1.0f32).unwrap_err(),
+            BuilderError::UniformNotFound("missing".into())
+        );
+        assert!(matches!(
+            builder.set_uniform("color", 1.0f32).unwrap_err(),
+            BuilderError::UniformTypeMismatch {
+                ty: uniform::Type::Float4,
+                ..
+            }
+        ));
+        assert_eq!(
+            builder.set_uniform("weights", 1.0f32).unwrap_err(),
+            BuilderError::UniformCountMismatch {
+                name: "weights".into(),
+                count: 3,
+                value_count: 1
+            }
+        );
+        assert!(matches!(
+            builder
+                .set_child(
+                    "child",
+                    crate::color_filters::blend(Color::RED, crate::BlendMode::Src).unwrap()
+                )
+                .unwrap_err(),
+            BuilderError::ChildTypeMismatch {
+                ty: ChildType::Shader,
+                child_type: ChildType::ColorFilter,
+                ..
+            }
+        ));
+    }
+
+    struct ManualUniforms {
+        color: Color4f,
+        transform: Matrix,
+        weights: [f32; 3],
+        mode: i32,
+    }
+
+    impl Uniforms for ManualUniforms {
+        const DESCRIPTORS: &'static [UniformDescriptor] = &[
+            UniformDescriptor {
+                name: "color",
+                value_type: "Color4f",
+                count: 1,
+                is_array: false,
+                accepts: <Color4f as UniformValue>::accepts,
+            },
+            UniformDescriptor {
+                name: "transform",
+                value_type: "Matrix",
+                count: 1,
+                is_array: false,
+                accepts: <Matrix as UniformValue>::accepts,
+            },
+            UniformDescriptor {
+                name: "weights",
+                value_type: "f32",
+                count: 3,
+                is_array: true,
+                accepts: <f32 as UniformValue>::accepts,
+            },
+            UniformDescriptor {
+                name: "mode",
+                value_type: "i32",
+                count: 1,
+                is_array: false,
+                accepts: <i32 as UniformValue>::accepts,
+            },
+        ];
+
+        fn write_bytes(&self, bytes: &mut Vec<u8>) {
+            self.color.write_bytes(bytes);
+            self.transform.write_bytes(bytes);
+            self.weights.iter().for_each(|w| w.write_bytes(bytes));
+            self.mode.write_bytes(bytes);
+        }
+    }
+
+    #[test]
+    fn typed_effect_matches_builder() {
+        let effect = RuntimeEffect::make_for_shader(SKSL, None).unwrap();
+        let typed = TypedRuntimeEffect::<ManualUniforms>::new(effect.clone()).unwrap();
+        let uniforms = ManualUniforms {
+            color: Color4f::new(0.0, 0.0, 1.0, 1.0),
+            transform: Matrix::translate((2.0, 3.0)),
+            weights: [1.0, 2.0, 3.0],
+            mode: 1,
+        };
+
+        let mut builder = RuntimeEffectBuilder::new(effect);
+        builder
+            .set_uniform("color", uniforms.color)
+            .unwrap()
+            .set_uniform("transform", uniforms.transform)
+            .unwrap()
+            .set_uniform_array("weights", &uniforms.weights)
+            .unwrap()
+            .set_uniform("mode", uniforms.mode)
+            .unwrap();
+        assert_eq!(uniforms.to_data().as_bytes(), builder.uniforms());
+        assert!(typed
+            .make_shader(
+                &uniforms,
+                &[crate::shaders::color(Color::GREEN).into()],
+                None
+            )
+            .is_some());
+    }
+
+    #[test]
+    fn typed_effect_rejects_mismatching_struct() {
+        // `mode` and `weights` are swapped.
+        let effect = RuntimeEffect::make_for_shader(
+            r#"
+            uniform shader child;
+            uniform half4 color;
+            uniform float3x3 transform;
+            uniform int mode;
+            uniform float weights[3];
+
+            half4 main(float2 coords) { return color; }
+            "#,
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            TypedRuntimeEffect::<ManualUniforms>::new(effect).unwrap_err(),
+            BuilderError::UniformOrderMismatch {
+                name: "weights".into(),
+                index: 2,
+                expected_index: 3,
+            }
+        );
+
+        let effect = RuntimeEffect::make_for_shader(
+            "uniform half4 color; uniform float extra; half4 main(float2 c) { return color; }",
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            TypedRuntimeEffect::<ManualUniforms>::new(effect).unwrap_err(),
+            BuilderError::UniformNotFound("transform".into())
+        );
+    }
+
+    #[test]
+    fn typed_effect_rejects_duplicate_uniforms() {
+        struct DuplicateUniforms;
+
+        impl Uniforms for DuplicateUniforms {
+            const DESCRIPTORS: &'static [UniformDescriptor] = &[
+                ManualUniforms::DESCRIPTORS[0],
+                ManualUniforms::DESCRIPTORS[0],
+            ];
+
+            fn write_bytes(&self, _bytes: &mut Vec<u8>) {}
+        }
+
+        let effect = RuntimeEffect::make_for_shader(
+            "uniform half4 color; uniform half4 tint; half4 main(float2 c) { return color; }",
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            TypedRuntimeEffect::<DuplicateUniforms>::new(effect).unwrap_err(),
+            BuilderError::UniformDuplicate("color".into())
+        );
+    }
+
+    #[cfg(feature = "sksl-macros")]
+    #[test]
+    fn derived_uniforms() {
+        #[derive(Uniforms)]
+        struct DerivedUniforms {
+            color: Color4f,
+            #[uniform(name = "transform")]
+            matrix: Matrix,
+            #[uniform(array)]
+            weights: [f32; 3],
+            mode: i32,
+        }
+
+        let effect = RuntimeEffect::make_for_shader(SKSL, None).unwrap();
+        TypedRuntimeEffect::<DerivedUniforms>::new(effect).unwrap();
+        let derived = DerivedUniforms {
+            color: Color4f::new(0.0, 0.0, 1.0, 1.0),
+            matrix: Matrix::translate((2.0, 3.0)),
+            weights: [1.0, 2.0, 3.0],
+            mode: 1,
+        };
+        let manual = ManualUniforms {
+            color: derived.color,
+            transform: derived.matrix,
+            weights: derived.weights,
+            mode: derived.mode,
+        };
+        assert_eq!(derived.to_data().as_bytes(), manual.to_data().as_bytes());
+    }
+
+    #[test]
+    fn build_shader_with_uniforms_and_children() {
+        let render = |builder: &RuntimeEffectBuilder| {
+            let mut surface = surfaces::raster_n32_premul((4, 4)).unwrap();
+            let mut paint = Paint::default();
+            paint.set_shader(builder.make_shader(None).unwrap());
+            surface.canvas().draw_paint(&paint);
+            let pixmap = surface.peek_pixels().unwrap();
+            pixmap.get_color((2, 2))
+        };
+
+        let mut builder = builder();
+        builder
+            .set_child("child", crate::shaders::color(Color::GREEN))
+            .unwrap();
+        assert_eq!(render(&builder), Color::GREEN);
+
+        builder
+            .set_uniform("color", Color4f::from(Color::BLUE))
+            .unwrap()
+            .set_uniform("mode", 1)
+            .unwrap();
+        assert_eq!(render(&builder), Color::BLUE);
+    }
+}
@@ -12,3 +12,12 @@ pub use self::string::*;
 mod strings;
 #[cfg(feature = "textlayout")]
 pub use self::strings::*;
+
+#[cfg(feature = "euclid")]
+mod euclid_;
+#[cfg(feature = "image")]
+pub mod image_;
+#[cfg(feature = "glam")]
+mod glam_;
+#[cfg(feature = "mint")]
+mod mint_;
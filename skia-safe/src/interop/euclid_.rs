@@ -0,0 +1,247 @@
+//! Conversions from and to the types of the [`euclid`] crate.
+//!
+//! The unit parameters of the `euclid` types are not checked.
+
+use euclid::{
+    Box2D, Point2D, Point3D, Rect as EuclidRect, Size2D, Transform2D, Transform3D, Vector2D,
+    Vector3D,
+};
+
+use crate::{IPoint, IRect, ISize, Matrix, Point, Point3, Rect, Size, M44};
+
+impl<U> From<Point2D<f32, U>> for Point {
+    fn from(p: Point2D<f32, U>) -> Self {
+        Point::new(p.x, p.y)
+    }
+}
+
+impl<U> From<Point> for Point2D<f32, U> {
+    fn from(p: Point) -> Self {
+        Point2D::new(p.x, p.y)
+    }
+}
+
+impl<U> From<Vector2D<f32, U>> for Point {
+    fn from(v: Vector2D<f32, U>) -> Self {
+        Point::new(v.x, v.y)
+    }
+}
+
+impl<U> From<Point> for Vector2D<f32, U> {
+    fn from(v: Point) -> Self {
+        Vector2D::new(v.x, v.y)
+    }
+}
+
+impl<U> From<Point2D<i32, U>> for IPoint {
+    fn from(p: Point2D<i32, U>) -> Self {
+        IPoint::new(p.x, p.y)
+    }
+}
+
+impl<U> From<IPoint> for Point2D<i32, U> {
+    fn from(p: IPoint) -> Self {
+        Point2D::new(p.x, p.y)
+    }
+}
+
+impl<U> From<Point3D<f32, U>> for Point3 {
+    fn from(p: Point3D<f32, U>) -> Self {
+        Point3::new(p.x, p.y, p.z)
+    }
+}
+
+impl<U> From<Point3> for Point3D<f32, U> {
+    fn from(p: Point3) -> Self {
+        Point3D::new(p.x, p.y, p.z)
+    }
+}
+
+impl<U> From<Vector3D<f32, U>> for Point3 {
+    fn from(v: Vector3D<f32, U>) -> Self {
+        Point3::new(v.x, v.y, v.z)
+    }
+}
+
+impl<U> From<Point3> for Vector3D<f32, U> {
+    fn from(v: Point3) -> Self {
+        Vector3D::new(v.x, v.y, v.z)
+    }
+}
+
+impl<U> From<Size2D<f32, U>> for Size {
+    fn from(s: Size2D<f32, U>) -> Self {
+        Size::new(s.width, s.height)
+    }
+}
+
+impl<U> From<Size> for Size2D<f32, U> {
+    fn from(s: Size) -> Self {
+        Size2D::new(s.width, s.height)
+    }
+}
+
+impl<U> From<Size2D<i32, U>> for ISize {
+    fn from(s: Size2D<i32, U>) -> Self {
+        ISize::new(s.width, s.height)
+    }
+}
+
+impl<U> From<ISize> for Size2D<i32, U> {
+    fn from(s: ISize) -> Self {
+        Size2D::new(s.width, s.height)
+    }
+}
+
+impl<U> From<Box2D<f32, U>> for Rect {
+    fn from(b: Box2D<f32, U>) -> Self {
+        Rect::new(b.min.x, b.min.y, b.max.x, b.max.y)
+    }
+}
+
+impl<U> From<Rect> for Box2D<f32, U> {
+    fn from(r: Rect) -> Self {
+        Box2D::new(Point2D::new(r.left, r.top), Point2D::new(r.right, r.bottom))
+    }
+}
+
+impl<U> From<EuclidRect<f32, U>> for Rect {
+    fn from(r: EuclidRect<f32, U>) -> Self {
+        Rect::from_xywh(r.origin.x, r.origin.y, r.size.width, r.size.height)
+    }
+}
+
+impl<U> From<Rect> for EuclidRect<f32, U> {
+    fn from(r: Rect) -> Self {
+        EuclidRect::new(
+            Point2D::new(r.left, r.top),
+            Size2D::new(r.width(), r.height()),
+        )
+    }
+}
+
+impl<U> From<Box2D<i32, U>> for IRect {
+    fn from(b: Box2D<i32, U>) -> Self {
+        IRect::new(b.min.x, b.min.y, b.max.x, b.max.y)
+    }
+}
+
+impl<U> From<IRect> for Box2D<i32, U> {
+    fn from(r: IRect) -> Self {
+        Box2D::new(Point2D::new(r.left, r.top), Point2D::new(r.right, r.bottom))
+    }
+}
+
+impl<U> From<EuclidRect<i32, U>> for IRect {
+    fn from(r: EuclidRect<i32, U>) -> Self {
+        IRect::from_xywh(r.origin.x, r.origin.y, r.size.width, r.size.height)
+    }
+}
+
+impl<U> From<IRect> for EuclidRect<i32, U> {
+    fn from(r: IRect) -> Self {
+        EuclidRect::new(
+            Point2D::new(r.left, r.top),
+            Size2D::new(r.width(), r.height()),
+        )
+    }
+}
+
+impl<Src, Dst> From<Transform2D<f32, Src, Dst>> for Matrix {
+    fn from(t: Transform2D<f32, Src, Dst>) -> Self {
+        // `euclid` transforms row vectors, so its terms are the transposed affine terms, which
+        // are laid out as scale x, skew y, skew x, scale y, translate x, translate y.
+        Matrix::from_affine(&t.to_array())
+    }
+}
+
+/// Fails with the original matrix if it has perspective.
+impl<Src, Dst> TryFrom<Matrix> for Transform2D<f32, Src, Dst> {
+    type Error = Matrix;
+
+    fn try_from(m: Matrix) -> Result<Self, Self::Error> {
+        m.to_affine().map(Transform2D::from_array).ok_or(m)
+    }
+}
+
+impl<Src, Dst> From<Transform3D<f32, Src, Dst>> for M44 {
+    fn from(t: Transform3D<f32, Src, Dst>) -> Self {
+        // The row-major terms of a `euclid` transform are the column-major terms of the
+        // transposed, column vector transforming M44.
+        M44::col_major(&t.to_array())
+    }
+}
+
+impl<Src, Dst> From<M44> for Transform3D<f32, Src, Dst> {
+    fn from(m: M44) -> Self {
+        let mut c = [0.0; 16];
+        m.get_col_major(&mut c);
+        Transform3D::from_array(c)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use euclid::{
+        default::{Box2D, Point2D, Rect as EuclidRect, Transform2D, Transform3D},
+        Angle,
+    };
+
+    use crate::{Matrix, Point, Rect, M44};
+
+    #[test]
+    fn points_and_rects() {
+        let p: Point = Point2D::new(1.0, 2.0).into();
+        assert_eq!(p, Point::new(1.0, 2.0));
+
+        let r = Rect::new(1.0, 2.0, 4.0, 8.0);
+        assert_eq!(
+            Box2D::from(r),
+            Box2D::new((1.0, 2.0).into(), (4.0, 8.0).into())
+        );
+        assert_eq!(
+            EuclidRect::from(r),
+            EuclidRect::new((1.0, 2.0).into(), (3.0, 6.0).into())
+        );
+        assert_eq!(Rect::from(EuclidRect::from(r)), r);
+    }
+
+    #[test]
+    fn transform_2d_is_transposed() {
+        let t = Transform2D::scale(2.0, 3.0).then_translate((10.0, 20.0).into());
+        let m = Matrix::from(t);
+        assert_eq!(m.scale_x(), 2.0);
+        assert_eq!(m.scale_y(), 3.0);
+        assert_eq!((m.translate_x(), m.translate_y()), (10.0, 20.0));
+        let p = Point2D::new(1.0, 1.0);
+        assert_eq!(
+            m.map_point(Point::from(p)),
+            Point::from(t.transform_point(p))
+        );
+
+        assert_eq!(Transform2D::try_from(m), Ok(t));
+        let mut perspective = Matrix::default();
+        perspective.set_persp_y(0.5);
+        assert_eq!(Transform2D::<f32>::try_from(perspective), Err(perspective));
+    }
+
+    #[test]
+    fn transform_3d_is_transposed() {
+        let t = Transform3D::rotation(0.0, 0.0, 1.0, Angle::degrees(90.0))
+            .then_translate((1.0, 2.0, 3.0).into());
+        let m = M44::from(t);
+        let mut row_major = [0.0; 16];
+        m.get_row_major(&mut row_major);
+        // The translation is in the last column of M44 and in the last row of `euclid`.
+        assert_eq!(
+            &row_major[3..16]
+                .iter()
+                .step_by(4)
+                .copied()
+                .collect::<Vec<_>>(),
+            &[1.0, 2.0, 3.0, 1.0]
+        );
+        assert_eq!((t.m41, t.m42, t.m43), (1.0, 2.0, 3.0));
+        assert_eq!(Transform3D::from(m), t);
+    }
+}
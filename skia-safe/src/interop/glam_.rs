@@ -0,0 +1,169 @@
+//! Conversions from and to the types of the [`glam`] crate.
+
+use glam::{Affine2, IVec2, Mat3, Mat4, Vec2, Vec3, Vec4};
+
+use crate::{IPoint, Matrix, Point, Point3, M44, V2, V3, V4};
+
+impl From<Vec2> for Point {
+    fn from(v: Vec2) -> Self {
+        Point::new(v.x, v.y)
+    }
+}
+
+impl From<Point> for Vec2 {
+    fn from(p: Point) -> Self {
+        Vec2::new(p.x, p.y)
+    }
+}
+
+impl From<IVec2> for IPoint {
+    fn from(v: IVec2) -> Self {
+        IPoint::new(v.x, v.y)
+    }
+}
+
+impl From<IPoint> for IVec2 {
+    fn from(p: IPoint) -> Self {
+        IVec2::new(p.x, p.y)
+    }
+}
+
+impl From<Vec3> for Point3 {
+    fn from(v: Vec3) -> Self {
+        Point3::new(v.x, v.y, v.z)
+    }
+}
+
+impl From<Point3> for Vec3 {
+    fn from(p: Point3) -> Self {
+        Vec3::new(p.x, p.y, p.z)
+    }
+}
+
+impl From<Vec2> for V2 {
+    fn from(v: Vec2) -> Self {
+        V2::new(v.x, v.y)
+    }
+}
+
+impl From<V2> for Vec2 {
+    fn from(v: V2) -> Self {
+        Vec2::new(v.x, v.y)
+    }
+}
+
+impl From<Vec3> for V3 {
+    fn from(v: Vec3) -> Self {
+        V3::new(v.x, v.y, v.z)
+    }
+}
+
+impl From<V3> for Vec3 {
+    fn from(v: V3) -> Self {
+        Vec3::new(v.x, v.y, v.z)
+    }
+}
+
+impl From<Vec4> for V4 {
+    fn from(v: Vec4) -> Self {
+        V4::new(v.x, v.y, v.z, v.w)
+    }
+}
+
+impl From<V4> for Vec4 {
+    fn from(v: V4) -> Self {
+        Vec4::new(v.x, v.y, v.z, v.w)
+    }
+}
+
+impl From<Affine2> for Matrix {
+    fn from(a: Affine2) -> Self {
+        // Both are laid out as scale x, skew y, skew x, scale y, translate x, translate y.
+        Matrix::from_affine(&a.to_cols_array())
+    }
+}
+
+/// Fails with the original matrix if it has perspective.
+impl TryFrom<Matrix> for Affine2 {
+    type Error = Matrix;
+
+    fn try_from(m: Matrix) -> Result<Self, Self::Error> {
+        m.to_affine()
+            .map(|affine| Affine2::from_cols_array(&affine))
+            .ok_or(m)
+    }
+}
+
+impl From<Mat3> for Matrix {
+    fn from(m: Mat3) -> Self {
+        let c = m.to_cols_array();
+        Matrix::new_all(c[0], c[3], c[6], c[1], c[4], c[7], c[2], c[5], c[8])
+    }
+}
+
+impl From<Matrix> for Mat3 {
+    fn from(m: Matrix) -> Self {
+        let mut r = [0.0; 9];
+        m.get_9(&mut r);
+        Mat3::from_cols_array(&r).transpose()
+    }
+}
+
+impl From<Mat4> for M44 {
+    fn from(m: Mat4) -> Self {
+        M44::col_major(&m.to_cols_array())
+    }
+}
+
+impl From<M44> for Mat4 {
+    fn from(m: M44) -> Self {
+        let mut c = [0.0; 16];
+        m.get_col_major(&mut c);
+        Mat4::from_cols_array(&c)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use glam::{Affine2, Mat3, Mat4, Vec2, Vec3, Vec4};
+
+    use crate::{Matrix, Point, M44};
+
+    #[test]
+    fn points() {
+        let p: Point = Vec2::new(1.0, 2.0).into();
+        assert_eq!(p, Point::new(1.0, 2.0));
+        assert_eq!(Vec2::from(p), Vec2::new(1.0, 2.0));
+    }
+
+    #[test]
+    fn matrix_columns() {
+        let mut m = Matrix::translate((10.0, 20.0));
+        m.pre_scale((2.0, 3.0), None);
+        let mat3 = Mat3::from(m);
+        assert_eq!(mat3.x_axis, Vec3::new(2.0, 0.0, 0.0));
+        assert_eq!(mat3.z_axis, Vec3::new(10.0, 20.0, 1.0));
+        assert_eq!(Matrix::from(mat3), m);
+        assert_eq!(
+            mat3.transform_point2(Vec2::new(1.0, 1.0)),
+            Vec2::from(m.map_point((1.0, 1.0)))
+        );
+
+        let affine = Affine2::try_from(m).unwrap();
+        assert_eq!(affine.translation, Vec2::new(10.0, 20.0));
+        assert_eq!(Matrix::from(affine), m);
+
+        let mut perspective = Matrix::default();
+        perspective.set_persp_x(0.5);
+        assert_eq!(Affine2::try_from(perspective), Err(perspective));
+    }
+
+    #[test]
+    fn m44_columns() {
+        let m = M44::translate(1.0, 2.0, 3.0);
+        let mat4 = Mat4::from(m.clone());
+        assert_eq!(mat4.w_axis, Vec4::new(1.0, 2.0, 3.0, 1.0));
+        assert_eq!(mat4.x_axis, Vec4::new(1.0, 0.0, 0.0, 0.0));
+        assert_eq!(M44::from(mat4), m);
+    }
+}
@@ -0,0 +1,430 @@
+//! Conversions between Skia's pixel containers and the [`image`] crate.
+//!
+//! Pixels of the `image` crate are interpreted as unpremultiplied and in the sRGB color space.
+//! [`Pixmap`]s and [`ImageBuffer`]s borrow each other's pixels if their layouts match, all other
+//! conversions copy and convert the pixels.
+
+use std::{borrow::Cow, error, fmt, mem, ops::DerefMut, slice};
+
+use image::{DynamicImage, ImageBuffer, Luma, Rgba};
+
+use crate::{
+    images, AlphaType, Bitmap, CachingHint, ColorSpace, ColorType, Data, Image, ImageInfo, Pixmap,
+};
+
+/// The error returned by conversions from and to the `image` crate.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ConversionError {
+    /// Pixels of this Skia color type can not be converted.
+    UnsupportedColorType(ColorType),
+    /// Pixels of this `image` crate color type can not be converted.
+    UnsupportedImageColorType(image::ColorType),
+    /// The pixels can not be borrowed, because their layouts differ.
+    LayoutMismatch,
+    /// The dimensions are not supported by Skia or the `image` crate.
+    InvalidDimensions,
+    /// Reading the pixels failed, for example because an [`Image`] is texture backed.
+    ReadPixelsFailed,
+}
+
+impl fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnsupportedColorType(color_type) => {
+                write!(f, "unsupported color type {color_type:?}")
+            }
+            Self::UnsupportedImageColorType(color_type) => {
+                write!(f, "unsupported image crate color type {color_type:?}")
+            }
+            Self::LayoutMismatch => write!(f, "the pixel layouts do not match"),
+            Self::InvalidDimensions => write!(f, "invalid dimensions"),
+            Self::ReadPixelsFailed => write!(f, "failed to read the pixels"),
+        }
+    }
+}
+
+impl error::Error for ConversionError {}
+
+/// `image` crate pixels that have a Skia equivalent and can be borrowed.
+pub trait ImagePixel: image::Pixel {
+    const COLOR_TYPE: ColorType;
+    const ALPHA_TYPE: AlphaType;
+}
+
+impl ImagePixel for Luma<u8> {
+    const COLOR_TYPE: ColorType = ColorType::Gray8;
+    const ALPHA_TYPE: AlphaType = AlphaType::Opaque;
+}
+
+impl ImagePixel for Rgba<u8> {
+    const COLOR_TYPE: ColorType = ColorType::RGBA8888;
+    const ALPHA_TYPE: AlphaType = AlphaType::Unpremul;
+}
+
+impl ImagePixel for Rgba<u16> {
+    const COLOR_TYPE: ColorType = ColorType::R16G16B16A16UNorm;
+    const ALPHA_TYPE: AlphaType = AlphaType::Unpremul;
+}
+
+impl ImagePixel for Rgba<f32> {
+    const COLOR_TYPE: ColorType = ColorType::RGBAF32;
+    const ALPHA_TYPE: AlphaType = AlphaType::Unpremul;
+}
+
+/// Maps the `image` crate color types that have the same layout in Skia.
+impl TryFrom<image::ColorType> for ColorType {
+    type Error = ConversionError;
+
+    fn try_from(color_type: image::ColorType) -> Result<Self, Self::Error> {
+        match color_type {
+            image::ColorType::L8 => Ok(ColorType::Gray8),
+            image::ColorType::Rgba8 => Ok(ColorType::RGBA8888),
+            image::ColorType::Rgba16 => Ok(ColorType::R16G16B16A16UNorm),
+            image::ColorType::Rgba32F => Ok(ColorType::RGBAF32),
+            color_type => Err(ConversionError::UnsupportedImageColorType(color_type)),
+        }
+    }
+}
+
+/// Maps the Skia color types that have the same layout in the `image` crate.
+impl TryFrom<ColorType> for image::ColorType {
+    type Error = ConversionError;
+
+    fn try_from(color_type: ColorType) -> Result<Self, Self::Error> {
+        match color_type {
+            ColorType::Gray8 => Ok(image::ColorType::L8),
+            ColorType::RGBA8888 => Ok(image::ColorType::Rgba8),
+            ColorType::R16G16B16A16UNorm => Ok(image::ColorType::Rgba16),
+            ColorType::RGBAF32 => Ok(image::ColorType::Rgba32F),
+            color_type => Err(ConversionError::UnsupportedColorType(color_type)),
+        }
+    }
+}
+
+impl<'pixels> Pixmap<'pixels> {
+    /// Borrows the pixels of `buffer` without copying them.
+    pub fn from_image_buffer<P, C>(
+        buffer: &'pixels mut ImageBuffer<P, C>,
+    ) -> Result<Self, ConversionError>
+    where
+        P: ImagePixel,
+        C: DerefMut<Target = [P::Subpixel]>,
+    {
+        let info = image_info(buffer.width(), buffer.height(), P::COLOR_TYPE)?;
+        let subpixels: &'pixels mut [P::Subpixel] = buffer;
+        let bytes = unsafe {
+            slice::from_raw_parts_mut(
+                subpixels.as_mut_ptr() as *mut u8,
+                mem::size_of_val(subpixels),
+            )
+        };
+        Pixmap::new(&info, bytes, info.min_row_bytes()).ok_or(ConversionError::InvalidDimensions)
+    }
+
+    /// Borrows the pixels of `image` without copying them.
+    ///
+    /// Only images with the color types `L8`, `Rgba8`, `Rgba16` and `Rgba32F` can be borrowed.
+    pub fn from_dynamic_image(image: &'pixels mut DynamicImage) -> Result<Self, ConversionError> {
+        match image {
+            DynamicImage::ImageLuma8(buffer) => Self::from_image_buffer(buffer),
+            DynamicImage::ImageRgba8(buffer) => Self::from_image_buffer(buffer),
+            DynamicImage::ImageRgba16(buffer) => Self::from_image_buffer(buffer),
+            DynamicImage::ImageRgba32F(buffer) => Self::from_image_buffer(buffer),
+            image => Err(ConversionError::UnsupportedImageColorType(image.color())),
+        }
+    }
+
+    /// Borrows the pixels as an [`ImageBuffer`] without copying them.
+    ///
+    /// The pixmap's color type must match the pixel type `P`, its pixels must be unpremultiplied
+    /// or opaque, and its rows must not be padded.
+    pub fn as_image_buffer<P: ImagePixel>(
+        &self,
+    ) -> Result<ImageBuffer<P, &'pixels [P::Subpixel]>, ConversionError> {
+        let info = self.info();
+        let alpha_matches =
+            self.alpha_type() == P::ALPHA_TYPE || self.alpha_type() == AlphaType::Opaque;
+        if info.color_type() != P::COLOR_TYPE
+            || !alpha_matches
+            || self.row_bytes() != info.min_row_bytes()
+        {
+            return Err(ConversionError::LayoutMismatch);
+        }
+        let bytes = self.bytes().ok_or(ConversionError::ReadPixelsFailed)?;
+        if bytes.as_ptr() as usize % mem::align_of::<P::Subpixel>() != 0 {
+            return Err(ConversionError::LayoutMismatch);
+        }
+        let subpixels = unsafe {
+            slice::from_raw_parts(
+                bytes.as_ptr() as *const P::Subpixel,
+                bytes.len() / mem::size_of::<P::Subpixel>(),
+            )
+        };
+        ImageBuffer::from_raw(self.width() as u32, self.height() as u32, subpixels)
+            .ok_or(ConversionError::InvalidDimensions)
+    }
+
+    /// Copies the pixels into a [`DynamicImage`].
+    ///
+    /// 8 bit and smaller color types are converted to `Rgba8`, color types with up to 16 bit
+    /// integer channels to `Rgba16` and floating point color types to `Rgba32F`. `Gray8` is kept
+    /// as `L8`. If the pixmap has a color space, the pixels are converted to sRGB.
+    pub fn to_dynamic_image(&self) -> Result<DynamicImage, ConversionError> {
+        read_dynamic_image(self.info(), |dst| self.read_pixels_to_pixmap(dst, (0, 0)))
+    }
+}
+
+impl Bitmap {
+    /// Copies the pixels of `image` into a new [`Bitmap`].
+    ///
+    /// See [`Image::from_dynamic_image()`] for how the color types are converted.
+    pub fn from_dynamic_image(image: &DynamicImage) -> Result<Self, ConversionError> {
+        let image = Image::from_dynamic_image(image)?;
+        let mut bitmap = Bitmap::new();
+        if !bitmap.try_alloc_pixels_info(image.image_info(), None) {
+            return Err(ConversionError::InvalidDimensions);
+        }
+        let mut pixmap = bitmap
+            .peek_pixels()
+            .ok_or(ConversionError::ReadPixelsFailed)?;
+        let row_bytes = pixmap.row_bytes();
+        let pixels = pixmap
+            .bytes_mut()
+            .ok_or(ConversionError::ReadPixelsFailed)?;
+        if !image.read_pixels(
+            bitmap.info(),
+            pixels,
+            row_bytes,
+            (0, 0),
+            CachingHint::Disallow,
+        ) {
+            return Err(ConversionError::ReadPixelsFailed);
+        }
+        Ok(bitmap)
+    }
+
+    /// Copies the pixels into a [`DynamicImage`].
+    ///
+    /// See [`Pixmap::to_dynamic_image()`] for how the color types are converted.
+    pub fn to_dynamic_image(&self) -> Result<DynamicImage, ConversionError> {
+        self.pixmap().to_dynamic_image()
+    }
+}
+
+impl Image {
+    /// Copies the pixels of `image` into a new raster [`Image`].
+    ///
+    /// `La8` and `Rgb8` pixels are converted to `RGBA8888`, `L16`, `La16` and `Rgb16` pixels to
+    /// `R16G16B16A16UNorm` and `Rgb32F` pixels to `RGBAF32`.
+    pub fn from_dynamic_image(image: &DynamicImage) -> Result<Self, ConversionError> {
+        let image = match image.color() {
+            image::ColorType::L8
+            | image::ColorType::Rgba8
+            | image::ColorType::Rgba16
+            | image::ColorType::Rgba32F => Cow::Borrowed(image),
+            image::ColorType::La8 | image::ColorType::Rgb8 => {
+                Cow::Owned(DynamicImage::ImageRgba8(image.to_rgba8()))
+            }
+            image::ColorType::L16 | image::ColorType::La16 | image::ColorType::Rgb16 => {
+                Cow::Owned(DynamicImage::ImageRgba16(image.to_rgba16()))
+            }
+            image::ColorType::Rgb32F => Cow::Owned(DynamicImage::ImageRgba32F(image.to_rgba32f())),
+            color_type => return Err(ConversionError::UnsupportedImageColorType(color_type)),
+        };
+        let info = image_info(image.width(), image.height(), image.color().try_into()?)?;
+        images::raster_from_data(
+            &info,
+            Data::new_copy(image.as_bytes()),
+            info.min_row_bytes(),
+        )
+        .ok_or(ConversionError::InvalidDimensions)
+    }
+
+    /// Copies the pixels into a [`DynamicImage`].
+    ///
+    /// See [`Pixmap::to_dynamic_image()`] for how the color types are converted. Texture backed
+    /// images can not be read without a context and return [`ConversionError::ReadPixelsFailed`].
+    pub fn to_dynamic_image(&self) -> Result<DynamicImage, ConversionError> {
+        read_dynamic_image(self.image_info(), |dst| {
+            let info = dst.info().clone();
+            let row_bytes = dst.row_bytes();
+            dst.bytes_mut().is_some_and(|pixels| {
+                self.read_pixels(&info, pixels, row_bytes, (0, 0), CachingHint::Allow)
+            })
+        })
+    }
+}
+
+fn image_info(
+    width: u32,
+    height: u32,
+    color_type: ColorType,
+) -> Result<ImageInfo, ConversionError> {
+    let (Ok(width), Ok(height)) = (i32::try_from(width), i32::try_from(height)) else {
+        return Err(ConversionError::InvalidDimensions);
+    };
+    let alpha_type = if color_type == ColorType::Gray8 {
+        AlphaType::Opaque
+    } else {
+        AlphaType::Unpremul
+    };
+    Ok(ImageInfo::new(
+        (width, height),
+        color_type,
+        alpha_type,
+        ColorSpace::new_srgb(),
+    ))
+}
+
+/// The color type of the [`DynamicImage`] Skia pixels of `color_type` are converted to.
+fn dynamic_image_color_type(color_type: ColorType) -> Result<image::ColorType, ConversionError> {
+    use ColorType::*;
+    match color_type {
+        Gray8 => Ok(image::ColorType::L8),
+        Alpha8 | RGB565 | ARGB4444 | RGBA8888 | RGB888x | BGRA8888 | SRGBA8888 | R8UNorm
+        | R8G8UNorm => Ok(image::ColorType::Rgba8),
+        RGBA1010102 | BGRA1010102 | RGB101010x | BGR101010x | RGBA10x6 | A16UNorm | R16G16UNorm
+        | R16G16B16A16UNorm => Ok(image::ColorType::Rgba16),
+        BGR101010xXR | BGRA10101010XR | RGBAF16Norm | RGBAF16 | RGBF16F16F16x | RGBAF32
+        | A16Float | R16G16Float => Ok(image::ColorType::Rgba32F),
+        Unknown => Err(ConversionError::UnsupportedColorType(color_type)),
+    }
+}
+
+fn read_dynamic_image(
+    src_info: &ImageInfo,
+    read: impl FnOnce(&mut Pixmap) -> bool,
+) -> Result<DynamicImage, ConversionError> {
+    let color_type = dynamic_image_color_type(src_info.color_type())?;
+    let (Ok(width), Ok(height)) = (
+        u32::try_from(src_info.width()),
+        u32::try_from(src_info.height()),
+    ) else {
+        return Err(ConversionError::InvalidDimensions);
+    };
+    let mut image = DynamicImage::new(width, height, color_type);
+    {
+        let mut dst = Pixmap::from_dynamic_image(&mut image)?;
+        // Pixels without a color space are copied as is.
+        if src_info.color_space().is_none() {
+            dst.set_color_space(None);
+        }
+        if !read(&mut dst) {
+            return Err(ConversionError::ReadPixelsFailed);
+        }
+    }
+    Ok(image)
+}
+
+#[cfg(test)]
+mod tests {
+    use image::{DynamicImage, ImageBuffer, Rgb, Rgba, RgbaImage};
+
+    use super::ConversionError;
+    use crate::{surfaces, AlphaType, Bitmap, Color, ColorType, Image, Pixmap};
+
+    #[test]
+    fn borrow_pixels_without_copying() {
+        let mut buffer = RgbaImage::from_pixel(3, 2, Rgba([255, 0, 0, 128]));
+        let addr = buffer.as_ptr();
+        let pixmap = Pixmap::from_image_buffer(&mut buffer).unwrap();
+        assert_eq!(pixmap.addr() as *const u8, addr);
+        assert_eq!(pixmap.color_type(), ColorType::RGBA8888);
+        assert_eq!(pixmap.alpha_type(), AlphaType::Unpremul);
+        assert_eq!(pixmap.get_color((2, 1)), Color::from_argb(128, 255, 0, 0));
+
+        let borrowed = pixmap.as_image_buffer::<Rgba<u8>>().unwrap();
+        assert_eq!(borrowed.as_ptr(), addr);
+        assert_eq!(borrowed.get_pixel(2, 1), &Rgba([255, 0, 0, 128]));
+        assert_eq!(
+            pixmap.as_image_buffer::<Rgba<u16>>().unwrap_err(),
+            ConversionError::LayoutMismatch
+        );
+
+        let mut rgb = DynamicImage::new_rgb8(1, 1);
+        assert_eq!(
+            Pixmap::from_dynamic_image(&mut rgb).unwrap_err(),
+            ConversionError::UnsupportedImageColorType(image::ColorType::Rgb8)
+        );
+    }
+
+    #[test]
+    fn premultiplied_pixels_are_converted() {
+        let mut surface = surfaces::raster_n32_premul((2, 2)).unwrap();
+        surface.canvas().clear(Color::from_argb(128, 255, 0, 0));
+        let pixmap = surface.peek_pixels().unwrap();
+        assert_eq!(
+            pixmap.as_image_buffer::<Rgba<u8>>().unwrap_err(),
+            ConversionError::LayoutMismatch
+        );
+
+        let image = pixmap.to_dynamic_image().unwrap();
+        let DynamicImage::ImageRgba8(buffer) = image else {
+            panic!("expected Rgba8");
+        };
+        assert_eq!(buffer.get_pixel(1, 1), &Rgba([255, 0, 0, 128]));
+    }
+
+    #[test]
+    fn rgb_images_are_converted_to_rgba() {
+        let buffer = ImageBuffer::from_pixel(4, 3, Rgb([10u8, 20, 30]));
+        let image = Image::from_dynamic_image(&DynamicImage::ImageRgb8(buffer)).unwrap();
+        assert_eq!(image.color_type(), ColorType::RGBA8888);
+        assert_eq!((image.width(), image.height()), (4, 3));
+
+        let DynamicImage::ImageRgba8(buffer) = image.to_dynamic_image().unwrap() else {
+            panic!("expected Rgba8");
+        };
+        assert_eq!(buffer.get_pixel(3, 2), &Rgba([10, 20, 30, 255]));
+    }
+
+    #[test]
+    fn sixteen_bit_and_float_pixels_round_trip() {
+        let rgba16 = DynamicImage::ImageRgba16(ImageBuffer::from_pixel(
+            2,
+            2,
+            Rgba([0x1234u16, 0x5678, 0x9abc, 0xffff]),
+        ));
+        let image = Image::from_dynamic_image(&rgba16).unwrap();
+        assert_eq!(image.color_type(), ColorType::R16G16B16A16UNorm);
+        assert_eq!(image.to_dynamic_image().unwrap(), rgba16);
+        let bitmap = Bitmap::from_dynamic_image(&rgba16).unwrap();
+        assert_eq!(bitmap.to_dynamic_image().unwrap(), rgba16);
+
+        let rgba32f = DynamicImage::ImageRgba32F(ImageBuffer::from_pixel(
+            2,
+            2,
+            Rgba([0.25f32, 0.5, 0.75, 0.5]),
+        ));
+        let image = Image::from_dynamic_image(&rgba32f).unwrap();
+        assert_eq!(image.color_type(), ColorType::RGBAF32);
+        assert_eq!(image.to_dynamic_image().unwrap(), rgba32f);
+    }
+
+    #[test]
+    fn color_types_map_to_the_same_layout() {
+        for color_type in [
+            image::ColorType::L8,
+            image::ColorType::Rgba8,
+            image::ColorType::Rgba16,
+            image::ColorType::Rgba32F,
+        ] {
+            let skia = ColorType::try_from(color_type).unwrap();
+            assert_eq!(image::ColorType::try_from(skia), Ok(color_type));
+            assert_eq!(
+                skia.bytes_per_pixel(),
+                color_type.bytes_per_pixel() as usize
+            );
+        }
+        assert_eq!(
+            ColorType::try_from(image::ColorType::Rgb8),
+            Err(ConversionError::UnsupportedImageColorType(
+                image::ColorType::Rgb8
+            ))
+        );
+        assert_eq!(
+            image::ColorType::try_from(ColorType::BGRA8888),
+            Err(ConversionError::UnsupportedColorType(ColorType::BGRA8888))
+        );
+    }
+}
@@ -0,0 +1,257 @@
+//! Conversions from and to the types of the [`mint`] crate.
+
+use mint::{
+    ColumnMatrix3, ColumnMatrix4, Point2, Point3 as MintPoint3, RowMatrix3, RowMatrix4, Vector2,
+    Vector3, Vector4,
+};
+
+use crate::{IPoint, Matrix, Point, Point3, M44, V2, V3, V4};
+
+impl From<Point2<f32>> for Point {
+    fn from(p: Point2<f32>) -> Self {
+        Point::new(p.x, p.y)
+    }
+}
+
+impl From<Point> for Point2<f32> {
+    fn from(p: Point) -> Self {
+        Point2 { x: p.x, y: p.y }
+    }
+}
+
+impl From<Vector2<f32>> for Point {
+    fn from(v: Vector2<f32>) -> Self {
+        Point::new(v.x, v.y)
+    }
+}
+
+impl From<Point> for Vector2<f32> {
+    fn from(v: Point) -> Self {
+        Vector2 { x: v.x, y: v.y }
+    }
+}
+
+impl From<Point2<i32>> for IPoint {
+    fn from(p: Point2<i32>) -> Self {
+        IPoint::new(p.x, p.y)
+    }
+}
+
+impl From<IPoint> for Point2<i32> {
+    fn from(p: IPoint) -> Self {
+        Point2 { x: p.x, y: p.y }
+    }
+}
+
+impl From<MintPoint3<f32>> for Point3 {
+    fn from(p: MintPoint3<f32>) -> Self {
+        Point3::new(p.x, p.y, p.z)
+    }
+}
+
+impl From<Point3> for MintPoint3<f32> {
+    fn from(p: Point3) -> Self {
+        MintPoint3 {
+            x: p.x,
+            y: p.y,
+            z: p.z,
+        }
+    }
+}
+
+impl From<Vector3<f32>> for Point3 {
+    fn from(v: Vector3<f32>) -> Self {
+        Point3::new(v.x, v.y, v.z)
+    }
+}
+
+impl From<Point3> for Vector3<f32> {
+    fn from(v: Point3) -> Self {
+        Vector3 {
+            x: v.x,
+            y: v.y,
+            z: v.z,
+        }
+    }
+}
+
+impl From<Vector2<f32>> for V2 {
+    fn from(v: Vector2<f32>) -> Self {
+        V2::new(v.x, v.y)
+    }
+}
+
+impl From<V2> for Vector2<f32> {
+    fn from(v: V2) -> Self {
+        Vector2 { x: v.x, y: v.y }
+    }
+}
+
+impl From<Vector3<f32>> for V3 {
+    fn from(v: Vector3<f32>) -> Self {
+        V3::new(v.x, v.y, v.z)
+    }
+}
+
+impl From<V3> for Vector3<f32> {
+    fn from(v: V3) -> Self {
+        Vector3 {
+            x: v.x,
+            y: v.y,
+            z: v.z,
+        }
+    }
+}
+
+impl From<Vector4<f32>> for V4 {
+    fn from(v: Vector4<f32>) -> Self {
+        V4::new(v.x, v.y, v.z, v.w)
+    }
+}
+
+impl From<V4> for Vector4<f32> {
+    fn from(v: V4) -> Self {
+        Vector4 {
+            x: v.x,
+            y: v.y,
+            z: v.z,
+            w: v.w,
+        }
+    }
+}
+
+impl From<RowMatrix3<f32>> for Matrix {
+    fn from(m: RowMatrix3<f32>) -> Self {
+        let [r0, r1, r2]: [[f32; 3]; 3] = m.into();
+        Matrix::new_all(
+            r0[0], r0[1], r0[2], r1[0], r1[1], r1[2], r2[0], r2[1], r2[2],
+        )
+    }
+}
+
+impl From<Matrix> for RowMatrix3<f32> {
+    fn from(m: Matrix) -> Self {
+        let mut r = [0.0; 9];
+        m.get_9(&mut r);
+        [[r[0], r[1], r[2]], [r[3], r[4], r[5]], [r[6], r[7], r[8]]].into()
+    }
+}
+
+impl From<ColumnMatrix3<f32>> for Matrix {
+    fn from(m: ColumnMatrix3<f32>) -> Self {
+        let [c0, c1, c2]: [[f32; 3]; 3] = m.into();
+        Matrix::new_all(
+            c0[0], c1[0], c2[0], c0[1], c1[1], c2[1], c0[2], c1[2], c2[2],
+        )
+    }
+}
+
+impl From<Matrix> for ColumnMatrix3<f32> {
+    fn from(m: Matrix) -> Self {
+        let mut r = [0.0; 9];
+        m.get_9(&mut r);
+        [[r[0], r[3], r[6]], [r[1], r[4], r[7]], [r[2], r[5], r[8]]].into()
+    }
+}
+
+impl From<RowMatrix4<f32>> for M44 {
+    fn from(m: RowMatrix4<f32>) -> Self {
+        M44::row_major(&join_4x4(m.into()))
+    }
+}
+
+impl From<M44> for RowMatrix4<f32> {
+    fn from(m: M44) -> Self {
+        let mut r = [0.0; 16];
+        m.get_row_major(&mut r);
+        split_4x4(&r).into()
+    }
+}
+
+impl From<ColumnMatrix4<f32>> for M44 {
+    fn from(m: ColumnMatrix4<f32>) -> Self {
+        M44::col_major(&join_4x4(m.into()))
+    }
+}
+
+impl From<M44> for ColumnMatrix4<f32> {
+    fn from(m: M44) -> Self {
+        let mut c = [0.0; 16];
+        m.get_col_major(&mut c);
+        split_4x4(&c).into()
+    }
+}
+
+fn join_4x4(m: [[f32; 4]; 4]) -> [f32; 16] {
+    std::array::from_fn(|i| m[i / 4][i % 4])
+}
+
+fn split_4x4(m: &[f32; 16]) -> [[f32; 4]; 4] {
+    std::array::from_fn(|i| std::array::from_fn(|j| m[i * 4 + j]))
+}
+
+#[cfg(test)]
+mod tests {
+    use mint::{ColumnMatrix3, ColumnMatrix4, Point2, RowMatrix3, RowMatrix4, Vector3, Vector4};
+
+    use crate::{Matrix, Point, M44};
+
+    #[test]
+    fn points() {
+        let p: Point = Point2 { x: 1.0, y: 2.0 }.into();
+        assert_eq!(p, Point::new(1.0, 2.0));
+        assert_eq!(Point2::from(p), Point2 { x: 1.0, y: 2.0 });
+    }
+
+    #[test]
+    fn matrix_rows_and_columns() {
+        let m = Matrix::translate((10.0, 20.0));
+        let rows = RowMatrix3::from(m);
+        assert_eq!(
+            rows.x,
+            Vector3 {
+                x: 1.0,
+                y: 0.0,
+                z: 10.0
+            }
+        );
+        let columns = ColumnMatrix3::from(m);
+        assert_eq!(
+            columns.z,
+            Vector3 {
+                x: 10.0,
+                y: 20.0,
+                z: 1.0
+            }
+        );
+        assert_eq!(Matrix::from(rows), m);
+        assert_eq!(Matrix::from(columns), m);
+    }
+
+    #[test]
+    fn m44_rows_and_columns() {
+        let m = M44::translate(1.0, 2.0, 3.0);
+        let rows = RowMatrix4::from(m.clone());
+        assert_eq!(
+            rows.x,
+            Vector4 {
+                x: 1.0,
+                y: 0.0,
+                z: 0.0,
+                w: 1.0
+            }
+        );
+        let columns = ColumnMatrix4::from(m.clone());
+        assert_eq!(
+            columns.w,
+            Vector4 {
+                x: 1.0,
+                y: 2.0,
+                z: 3.0,
+                w: 1.0
+            }
+        );
+        assert_eq!(M44::from(rows), m);
+        assert_eq!(M44::from(columns), m);
+    }
+}
@@ -23,8 +23,13 @@ mod modules;
 mod pathops;
 mod prelude;
 pub(crate) mod private;
+#[cfg(feature = "serde")]
+pub mod serde_support;
+pub mod sksl;
 pub mod svg;
 pub mod wrapper;
+#[cfg(feature = "image")]
+pub use interop::image_ as image_crate;
 // TODO: We don't export utils/* into the crate's root yet. Should we?
 pub mod utils;
 
@@ -33,6 +38,10 @@ extern crate bitflags;
 #[macro_use]
 extern crate lazy_static;
 
+// Lets code generated by `skia-sksl-macros` refer to `::skia_safe` from inside this crate.
+#[cfg(all(test, feature = "sksl-macros"))]
+extern crate self as skia_safe;
+
 // Prelude re-exports
 pub use crate::prelude::{Borrows, ConditionallySend, Handle, RCHandle, RefHandle, Sendable};
 
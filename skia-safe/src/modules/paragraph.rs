@@ -2,6 +2,7 @@ use crate::interop::AsStr;
 use std::ops::Index;
 
 mod dart_types;
+pub mod editing;
 mod font_arguments;
 mod font_collection;
 mod metrics;
@@ -0,0 +1,722 @@
+//! Caret movement, index mapping and selection geometry for editing the text of a [`Paragraph`].
+//!
+//! [`Paragraph`] identifies text positions with UTF-16 indices. The types in this module use
+//! UTF-8 indices into the text the paragraph was built from and map them with a
+//! [`TextIndexMap`].
+
+use std::ops::Range;
+
+use super::{
+    paragraph::GlyphInfo, Affinity, LineMetrics, Paragraph, RectHeightStyle, RectWidthStyle,
+    TextBox, TextDirection,
+};
+use crate::{prelude::*, scalar, Point, Rect};
+
+/// Maps between the UTF-8 and UTF-16 indices of a text.
+///
+/// Indices inside of a character are mapped to the start of that character, and indices beyond
+/// the end of the text are mapped to its end.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TextIndexMap {
+    // The UTF-8 and UTF-16 index of every character, followed by the lengths of the text.
+    starts: Vec<(usize, usize)>,
+}
+
+impl TextIndexMap {
+    pub fn new(text: &str) -> Self {
+        let mut starts = Vec::with_capacity(text.len() + 1);
+        let mut utf16 = 0;
+        for (utf8, c) in text.char_indices() {
+            starts.push((utf8, utf16));
+            utf16 += c.len_utf16();
+        }
+        starts.push((text.len(), utf16));
+        Self { starts }
+    }
+
+    pub fn utf8_len(&self) -> usize {
+        self.starts[self.starts.len() - 1].0
+    }
+
+    pub fn utf16_len(&self) -> usize {
+        self.starts[self.starts.len() - 1].1
+    }
+
+    pub fn utf8_to_utf16(&self, index: usize) -> usize {
+        let i = self.starts.partition_point(|&(utf8, _)| utf8 <= index);
+        self.starts[i - 1].1
+    }
+
+    pub fn utf16_to_utf8(&self, index: usize) -> usize {
+        let i = self.starts.partition_point(|&(_, utf16)| utf16 <= index);
+        self.starts[i - 1].0
+    }
+
+    pub fn utf8_range_to_utf16(&self, range: Range<usize>) -> Range<usize> {
+        self.utf8_to_utf16(range.start)..self.utf8_to_utf16(range.end)
+    }
+
+    pub fn utf16_range_to_utf8(&self, range: Range<usize>) -> Range<usize> {
+        self.utf16_to_utf8(range.start)..self.utf16_to_utf8(range.end)
+    }
+}
+
+/// A caret position, a UTF-8 index into the text and the side of the index the caret is
+/// attached to.
+///
+/// The affinity matters where an index is displayed at two places: at a soft line break, the
+/// index is at the end of one line and at the start of the next one, and at the boundary of two
+/// runs with different directions, the index is at the end of one run and at the start of the
+/// other. [`Affinity::Upstream`] attaches the caret to the text before the index,
+/// [`Affinity::Downstream`] to the text after it.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct TextPosition {
+    pub index: usize,
+    pub affinity: Affinity,
+}
+
+impl TextPosition {
+    pub fn new(index: usize, affinity: Affinity) -> Self {
+        Self { index, affinity }
+    }
+}
+
+/// A caret that remembers its horizontal position while it is moved up and down.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Caret {
+    pub position: TextPosition,
+    /// The x coordinate [`Movement::Up`] and [`Movement::Down`] aim for. Set by vertical
+    /// movements and reset by all others.
+    pub preferred_x: Option<scalar>,
+}
+
+impl Caret {
+    pub fn new(position: TextPosition) -> Self {
+        Self {
+            position,
+            preferred_x: None,
+        }
+    }
+}
+
+impl From<TextPosition> for Caret {
+    fn from(position: TextPosition) -> Self {
+        Self::new(position)
+    }
+}
+
+/// The ways a [`Caret`] can be moved with [`CaretNavigator::move_caret()`].
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Movement {
+    /// One grapheme cluster to the left, in visual order. Continues at the adjacent line at the
+    /// start or the end of a line.
+    Left,
+    /// One grapheme cluster to the right, in visual order. Continues at the adjacent line at the
+    /// start or the end of a line.
+    Right,
+    /// To the previous line, as close as possible to the preferred x coordinate, or to the start
+    /// of the text on the first line.
+    Up,
+    /// To the next line, as close as possible to the preferred x coordinate, or to the end of the
+    /// text on the last line.
+    Down,
+    /// To the start of the previous word in a left-to-right paragraph, or to the end of the next
+    /// word in a right-to-left paragraph.
+    WordLeft,
+    /// To the end of the next word in a left-to-right paragraph, or to the start of the previous
+    /// word in a right-to-left paragraph.
+    WordRight,
+    /// To the logical start of the line.
+    LineStart,
+    /// To the logical end of the line, before a trailing line break.
+    LineEnd,
+    /// To the start of the text.
+    TextStart,
+    /// To the end of the text.
+    TextEnd,
+}
+
+/// Moves carets through the text of a laid out [`Paragraph`] and computes caret and selection
+/// rectangles.
+///
+/// All indices are UTF-8 indices into the text the paragraph was built from, which includes the
+/// replacement characters of placeholders, see [`super::ParagraphBuilder::get_text()`]. The
+/// navigator must be recreated when the paragraph is laid out again.
+#[derive(Debug)]
+pub struct CaretNavigator<'a> {
+    paragraph: &'a Paragraph,
+    text: &'a str,
+    indices: TextIndexMap,
+    lines: Vec<LineMetrics<'a>>,
+}
+
+// A grapheme cluster of a line, with its range in UTF-16.
+#[derive(Clone, Debug)]
+struct Grapheme {
+    range: Range<usize>,
+    bounds: Rect,
+    direction: TextDirection,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum Side {
+    Left,
+    Right,
+}
+
+// A caret position resolved to a line and an edge of one of its graphemes.
+#[derive(Debug)]
+struct LineCaret {
+    line: usize,
+    // In visual order.
+    graphemes: Vec<Grapheme>,
+    edge: Option<(usize, Side)>,
+}
+
+impl<'a> CaretNavigator<'a> {
+    pub fn new(paragraph: &'a Paragraph, text: &'a str) -> Self {
+        Self {
+            paragraph,
+            text,
+            indices: TextIndexMap::new(text),
+            lines: paragraph.get_line_metrics(),
+        }
+    }
+
+    pub fn text(&self) -> &'a str {
+        self.text
+    }
+
+    pub fn index_map(&self) -> &TextIndexMap {
+        &self.indices
+    }
+
+    /// The base direction of the paragraph.
+    pub fn text_direction(&self) -> TextDirection {
+        self.paragraph.native().fParagraphStyle.fTextDirection
+    }
+
+    /// Returns the end of the grapheme cluster that contains `index`, or the end of the text.
+    pub fn next_grapheme_boundary(&self, index: usize) -> usize {
+        let index16 = self.indices.utf8_to_utf16(index);
+        match self.grapheme_at(index16) {
+            Some(grapheme) => self.indices.utf16_to_utf8(grapheme.range.end),
+            None => self.next_char_boundary(self.indices.utf16_to_utf8(index16)),
+        }
+    }
+
+    /// Returns the start of the grapheme cluster that ends at or contains `index`, or `0`.
+    pub fn previous_grapheme_boundary(&self, index: usize) -> usize {
+        let index16 = self.indices.utf8_to_utf16(index);
+        if index16 == 0 {
+            return 0;
+        }
+        match self.grapheme_at(index16 - 1) {
+            Some(grapheme) => self.indices.utf16_to_utf8(grapheme.range.start),
+            None => self.previous_char_boundary(self.indices.utf16_to_utf8(index16)),
+        }
+    }
+
+    /// Returns the range of the word that contains `index`.
+    pub fn word_boundary(&self, index: usize) -> Range<usize> {
+        let index16 = self.indices.utf8_to_utf16(index);
+        let range = self.paragraph.get_word_boundary(index16 as u32);
+        self.indices.utf16_range_to_utf8(range)
+    }
+
+    /// Returns the text position closest to `point`.
+    pub fn position_at_point(&self, point: impl Into<Point>) -> TextPosition {
+        let position = self.paragraph.get_glyph_position_at_coordinate(point);
+        TextPosition::new(
+            self.indices
+                .utf16_to_utf8(position.position.max(0) as usize),
+            position.affinity,
+        )
+    }
+
+    /// Returns the caret after applying `movement` to `caret`.
+    pub fn move_caret(&self, caret: Caret, movement: Movement) -> Caret {
+        let position = caret.position;
+        let forward = self.text_direction() == TextDirection::LTR;
+        match movement {
+            Movement::Up => return self.move_vertically(caret, false),
+            Movement::Down => return self.move_vertically(caret, true),
+            Movement::Left => self.move_horizontally(position, false),
+            Movement::Right => self.move_horizontally(position, true),
+            Movement::WordLeft => self.move_by_word(position, !forward),
+            Movement::WordRight => self.move_by_word(position, forward),
+            Movement::LineStart => self.line_start(position),
+            Movement::LineEnd => self.line_end(position),
+            Movement::TextStart => TextPosition::new(0, Affinity::Downstream),
+            Movement::TextEnd => TextPosition::new(self.text.len(), Affinity::Downstream),
+        }
+        .into()
+    }
+
+    /// Returns the zero width rectangle the caret at `position` is drawn in, which spans from
+    /// the top of the line's ascent to the bottom of its descent.
+    ///
+    /// Returns `None` if the paragraph has no lines.
+    pub fn caret_rect(&self, position: TextPosition) -> Option<Rect> {
+        let caret = self.locate(position)?;
+        let line = &self.lines[caret.line];
+        let x = self.caret_x(&caret);
+        Some(Rect::new(
+            x,
+            (line.baseline - line.ascent) as scalar,
+            x,
+            (line.baseline + line.descent) as scalar,
+        ))
+    }
+
+    /// Returns the rectangles that cover the text in `range`. The ends of the range may be in any
+    /// order. In bidirectional text, a range usually maps to more than one rectangle per line.
+    pub fn selection_rects(
+        &self,
+        range: Range<usize>,
+        height_style: RectHeightStyle,
+    ) -> Vec<TextBox> {
+        let range = range.start.min(range.end)..range.start.max(range.end);
+        self.paragraph.get_rects_for_range(
+            self.indices.utf8_range_to_utf16(range),
+            height_style,
+            RectWidthStyle::Tight,
+        )
+    }
+
+    fn next_char_boundary(&self, index: usize) -> usize {
+        self.text[index..]
+            .chars()
+            .next()
+            .map_or(index, |c| index + c.len_utf8())
+    }
+
+    fn previous_char_boundary(&self, index: usize) -> usize {
+        self.text[..index]
+            .chars()
+            .next_back()
+            .map_or(index, |c| index - c.len_utf8())
+    }
+
+    fn grapheme_at(&self, index16: usize) -> Option<Grapheme> {
+        let GlyphInfo {
+            grapheme_layout_bounds,
+            grapheme_cluster_text_range,
+            text_direction,
+            is_ellipsis,
+        } = self.paragraph.glyph_info_at_utf16_offset(index16)?;
+        (!is_ellipsis && grapheme_cluster_text_range.contains(&index16)).then_some(Grapheme {
+            range: grapheme_cluster_text_range,
+            bounds: grapheme_layout_bounds,
+            direction: text_direction,
+        })
+    }
+
+    // The line a UTF-16 index is displayed on. Upstream positions at the start of a soft wrapped
+    // line belong to the end of the previous line.
+    fn line_of(&self, index16: usize, affinity: Affinity) -> Option<usize> {
+        let line = match self
+            .lines
+            .iter()
+            .position(|line| index16 < line.end_including_newline)
+        {
+            Some(line) => line,
+            None => self.lines.len().checked_sub(1)?,
+        };
+        if affinity == Affinity::Upstream
+            && line > 0
+            && index16 == self.lines[line].start_index
+            && !self.lines[line - 1].hard_break
+        {
+            return Some(line - 1);
+        }
+        Some(line)
+    }
+
+    // The graphemes of a line, excluding a trailing line break, in visual order.
+    fn line_graphemes(&self, line: &LineMetrics) -> Vec<Grapheme> {
+        let mut graphemes = Vec::new();
+        let mut index16 = line.start_index;
+        while index16 < line.end_index {
+            match self.grapheme_at(index16) {
+                Some(grapheme) => {
+                    index16 = grapheme.range.end;
+                    graphemes.push(grapheme);
+                }
+                None => {
+                    let index = self.indices.utf16_to_utf8(index16);
+                    index16 = self.indices.utf8_to_utf16(self.next_char_boundary(index));
+                }
+            }
+        }
+        graphemes.sort_by(|a, b| a.bounds.left.total_cmp(&b.bounds.left));
+        graphemes
+    }
+
+    fn locate(&self, position: TextPosition) -> Option<LineCaret> {
+        let index16 = self.indices.utf8_to_utf16(position.index);
+        let line = self.line_of(index16, position.affinity)?;
+        let graphemes = self.line_graphemes(&self.lines[line]);
+
+        let edge_at = |at_start: bool| {
+            graphemes
+                .iter()
+                .position(|g| {
+                    if at_start {
+                        g.range.start == index16
+                    } else {
+                        g.range.end == index16
+                    }
+                })
+                .map(|i| (i, attached_side(&graphemes[i], at_start)))
+        };
+        let (before, after) = (edge_at(false), edge_at(true));
+        let edge = if position.affinity == Affinity::Upstream {
+            before.or(after)
+        } else {
+            after.or(before)
+        }
+        .or_else(|| {
+            // Inside of a grapheme cluster, the caret is placed before it.
+            graphemes
+                .iter()
+                .position(|g| g.range.contains(&index16))
+                .map(|i| (i, attached_side(&graphemes[i], true)))
+        });
+
+        Some(LineCaret {
+            line,
+            graphemes,
+            edge,
+        })
+    }
+
+    fn caret_x(&self, caret: &LineCaret) -> scalar {
+        match caret.edge {
+            Some((i, side)) => edge_x(&caret.graphemes[i], side),
+            None => {
+                let line = &self.lines[caret.line];
+                match self.text_direction() {
+                    TextDirection::RTL => (line.left + line.width) as scalar,
+                    TextDirection::LTR => line.left as scalar,
+                }
+            }
+        }
+    }
+
+    fn position_at_edge(&self, grapheme: &Grapheme, side: Side) -> TextPosition {
+        if attached_side(grapheme, true) == side {
+            TextPosition::new(
+                self.indices.utf16_to_utf8(grapheme.range.start),
+                Affinity::Downstream,
+            )
+        } else {
+            TextPosition::new(
+                self.indices.utf16_to_utf8(grapheme.range.end),
+                Affinity::Upstream,
+            )
+        }
+    }
+
+    // Moves one grapheme edge to the left or right, or returns `None` at the end of the line.
+    fn step_in_line(&self, caret: &LineCaret, right: bool) -> Option<TextPosition> {
+        let (i, side) = caret.edge?;
+        let (i, side) = match (right, side) {
+            (true, Side::Left) => (i, Side::Right),
+            (true, Side::Right) => (i + 1, Side::Right),
+            (false, Side::Right) => (i, Side::Left),
+            (false, Side::Left) => (i.checked_sub(1)?, Side::Left),
+        };
+        let grapheme = caret.graphemes.get(i)?;
+        Some(self.position_at_edge(grapheme, side))
+    }
+
+    fn move_horizontally(&self, position: TextPosition, right: bool) -> TextPosition {
+        let Some(caret) = self.locate(position) else {
+            return position;
+        };
+        if let Some(moved) = self.step_in_line(&caret, right) {
+            return moved;
+        }
+
+        // Continue at the logical start of the next line or the logical end of the previous one.
+        let forward = right == (self.text_direction() == TextDirection::LTR);
+        let line = if forward {
+            caret.line + 1
+        } else {
+            match caret.line.checked_sub(1) {
+                Some(line) => line,
+                None => return position,
+            }
+        };
+        let Some(metrics) = self.lines.get(line) else {
+            return position;
+        };
+        let graphemes = self.line_graphemes(metrics);
+        let entry = match (graphemes.first(), graphemes.last()) {
+            (Some(leftmost), Some(rightmost)) => {
+                if right {
+                    self.position_at_edge(leftmost, Side::Left)
+                } else {
+                    self.position_at_edge(rightmost, Side::Right)
+                }
+            }
+            _ => TextPosition::new(
+                self.indices.utf16_to_utf8(metrics.start_index),
+                Affinity::Downstream,
+            ),
+        };
+
+        // Soft wrapped lines share an index, which would make the caret stop twice.
+        if entry.index == position.index {
+            if let Some(moved) = self
+                .locate(entry)
+                .and_then(|c| self.step_in_line(&c, right))
+            {
+                return moved;
+            }
+        }
+        entry
+    }
+
+    fn move_vertically(&self, caret: Caret, down: bool) -> Caret {
+        let Some(location) = self.locate(caret.position) else {
+            return caret;
+        };
+        let x = caret.preferred_x.unwrap_or_else(|| self.caret_x(&location));
+        let line = if down {
+            Some(location.line + 1).filter(|&line| line < self.lines.len())
+        } else {
+            location.line.checked_sub(1)
+        };
+
+        let position = match line {
+            Some(line) => {
+                let metrics = &self.lines[line];
+                let y = metrics.baseline + (metrics.descent - metrics.ascent) / 2.0;
+                let mut position = self.position_at_point((x, y as scalar));
+                // Keep the caret on the target line at soft line breaks.
+                let index16 = self.indices.utf8_to_utf16(position.index);
+                if self.line_of(index16, position.affinity) != Some(line) {
+                    position.affinity = match position.affinity {
+                        Affinity::Upstream => Affinity::Downstream,
+                        Affinity::Downstream => Affinity::Upstream,
+                    };
+                }
+                position
+            }
+            None if down => TextPosition::new(self.text.len(), Affinity::Downstream),
+            None => TextPosition::new(0, Affinity::Downstream),
+        };
+
+        Caret {
+            position,
+            preferred_x: Some(x),
+        }
+    }
+
+    fn move_by_word(&self, position: TextPosition, forward: bool) -> TextPosition {
+        let is_blank = |range: &Range<usize>| self.text[range.clone()].trim().is_empty();
+        let mut index = position.index.min(self.text.len());
+        if forward {
+            while index < self.text.len() {
+                let word = self.word_boundary(index);
+                index = word.end.max(self.next_char_boundary(index));
+                if !is_blank(&word) {
+                    break;
+                }
+            }
+            TextPosition::new(index, Affinity::Upstream)
+        } else {
+            while index > 0 {
+                let word = self.word_boundary(self.previous_char_boundary(index));
+                index = word.start.min(self.previous_char_boundary(index));
+                if !is_blank(&word) {
+                    break;
+                }
+            }
+            TextPosition::new(index, Affinity::Downstream)
+        }
+    }
+
+    fn line_start(&self, position: TextPosition) -> TextPosition {
+        let index16 = self.indices.utf8_to_utf16(position.index);
+        match self.line_of(index16, position.affinity) {
+            Some(line) => TextPosition::new(
+                self.indices.utf16_to_utf8(self.lines[line].start_index),
+                Affinity::Downstream,
+            ),
+            None => position,
+        }
+    }
+
+    fn line_end(&self, position: TextPosition) -> TextPosition {
+        let index16 = self.indices.utf8_to_utf16(position.index);
+        match self.line_of(index16, position.affinity) {
+            Some(line) => TextPosition::new(
+                self.indices.utf16_to_utf8(self.lines[line].end_index),
+                Affinity::Upstream,
+            ),
+            None => position,
+        }
+    }
+}
+
+// The side of a grapheme its logical start or end is displayed at.
+fn attached_side(grapheme: &Grapheme, at_start: bool) -> Side {
+    match (grapheme.direction == TextDirection::LTR, at_start) {
+        (true, true) | (false, false) => Side::Left,
+        (true, false) | (false, true) => Side::Right,
+    }
+}
+
+fn edge_x(grapheme: &Grapheme, side: Side) -> scalar {
+    match side {
+        Side::Left => grapheme.bounds.left,
+        Side::Right => grapheme.bounds.right,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Caret, Movement, TextIndexMap, TextPosition};
+    use crate::{
+        icu,
+        textlayout::{
+            Affinity, FontCollection, Paragraph, ParagraphBuilder, ParagraphStyle, RectHeightStyle,
+            TextDirection, TextStyle,
+        },
+        FontMgr,
+    };
+
+    fn mk_paragraph(text: &str, width: f32) -> Paragraph {
+        mk_paragraph_with_direction(text, width, TextDirection::LTR)
+    }
+
+    fn mk_paragraph_with_direction(text: &str, width: f32, direction: TextDirection) -> Paragraph {
+        icu::init();
+
+        let mut style = ParagraphStyle::new();
+        style.set_text_direction(direction);
+        let mut text_style = TextStyle::new();
+        text_style.set_font_size(20.0);
+        style.set_text_style(&text_style);
+        let mut font_collection = FontCollection::new();
+        font_collection.set_default_font_manager(FontMgr::new(), None);
+        let mut builder = ParagraphBuilder::new(&style, font_collection);
+        builder.add_text(text);
+        let mut paragraph = builder.build();
+        paragraph.layout(width);
+        paragraph
+    }
+
+    fn moves(text: &str, paragraph: &Paragraph, from: usize, movement: Movement) -> Vec<usize> {
+        let navigator = paragraph.caret_navigator(text);
+        let mut caret = Caret::new(TextPosition::new(from, Affinity::Downstream));
+        let mut indices = Vec::new();
+        loop {
+            let moved = navigator.move_caret(caret, movement);
+            if moved.position == caret.position {
+                return indices;
+            }
+            indices.push(moved.position.index);
+            caret = moved;
+        }
+    }
+
+    #[test]
+    fn index_map() {
+        // 'ä' takes two bytes in UTF-8, the emoji four bytes and two UTF-16 code units.
+        let map = TextIndexMap::new("aä😀b");
+        assert_eq!(map.utf8_len(), 8);
+        assert_eq!(map.utf16_len(), 5);
+        let utf8_to_utf16: Vec<_> = (0..=9).map(|i| map.utf8_to_utf16(i)).collect();
+        assert_eq!(utf8_to_utf16, [0, 1, 1, 2, 2, 2, 2, 4, 5, 5]);
+        let utf16_to_utf8: Vec<_> = (0..=6).map(|i| map.utf16_to_utf8(i)).collect();
+        assert_eq!(utf16_to_utf8, [0, 1, 3, 3, 7, 8, 8]);
+        assert_eq!(map.utf8_range_to_utf16(1..7), 1..4);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn moves_by_grapheme_and_word() {
+        // The combining acute accent forms one grapheme cluster with the 'e'.
+        let text = "ne\u{301}e word";
+        let paragraph = mk_paragraph(text, 1000.0);
+        assert_eq!(
+            moves(text, &paragraph, 0, Movement::Right),
+            [1, 4, 5, 6, 7, 8, 9, 10]
+        );
+        assert_eq!(
+            moves(text, &paragraph, 10, Movement::Left),
+            [9, 8, 7, 6, 5, 4, 1, 0]
+        );
+        assert_eq!(moves(text, &paragraph, 0, Movement::WordRight), [5, 10]);
+        assert_eq!(moves(text, &paragraph, 10, Movement::WordLeft), [6, 0]);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn moves_between_lines() {
+        let text = "ab\nabcd";
+        let paragraph = mk_paragraph(text, 1000.0);
+        let navigator = paragraph.caret_navigator(text);
+        assert_eq!(
+            moves(text, &paragraph, 0, Movement::Right),
+            [1, 2, 3, 4, 5, 6, 7]
+        );
+        assert_eq!(moves(text, &paragraph, 3, Movement::Left), [2, 1, 0]);
+
+        let caret = Caret::new(TextPosition::new(1, Affinity::Downstream));
+        let line_end = navigator.move_caret(caret, Movement::LineEnd);
+        assert_eq!(line_end.position.index, 2);
+
+        let down = navigator.move_caret(line_end, Movement::Down);
+        assert_eq!(down.position.index, 5);
+        let down = navigator.move_caret(down, Movement::Down);
+        assert_eq!(down.position.index, text.len());
+        // The preferred x coordinate is kept on the way back.
+        let up = navigator.move_caret(down, Movement::Up);
+        assert_eq!(up.position.index, 2);
+
+        let caret_rect = navigator.caret_rect(up.position).unwrap();
+        assert_eq!(caret_rect.width(), 0.0);
+        assert!(caret_rect.height() > 0.0);
+        let selection = navigator.selection_rects(5..0, RectHeightStyle::Tight);
+        assert!(selection.len() >= 2);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn moves_between_lines_of_right_to_left_paragraphs() {
+        // Moving left moves forward in the text of a right-to-left paragraph.
+        let text = "\u{5d0}\u{5d1}\n\u{5d2}\u{5d3}";
+        let paragraph = mk_paragraph_with_direction(text, 1000.0, TextDirection::RTL);
+        assert_eq!(moves(text, &paragraph, 0, Movement::Left), [2, 4, 5, 7, 9]);
+        assert_eq!(moves(text, &paragraph, 9, Movement::Right), [7, 5, 4, 2, 0]);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn moves_visually_through_bidi_text() {
+        // The Hebrew letters are displayed right-to-left in a left-to-right paragraph.
+        let text = "ab \u{5d0}\u{5d1}\u{5d2}";
+        let paragraph = mk_paragraph(text, 1000.0);
+        let navigator = paragraph.caret_navigator(text);
+
+        let mut caret = Caret::new(TextPosition::new(3, Affinity::Upstream));
+        let mut x = navigator.caret_rect(caret.position).unwrap().left;
+        let mut indices = Vec::new();
+        loop {
+            let moved = navigator.move_caret(caret, Movement::Right);
+            if moved.position == caret.position {
+                break;
+            }
+            let moved_x = navigator.caret_rect(moved.position).unwrap().left;
+            assert!(moved_x > x);
+            indices.push(moved.position.index);
+            (caret, x) = (moved, moved_x);
+        }
+        assert_eq!(indices, [7, 5, 3]);
+    }
+}
@@ -3,8 +3,8 @@ use std::{ffi, fmt, ops::Range};
 use skia_bindings as sb;
 
 use super::{
-    LineMetrics, PositionWithAffinity, RectHeightStyle, RectWidthStyle, TextBox, TextDirection,
-    TextIndex, TextRange,
+    editing::CaretNavigator, LineMetrics, PositionWithAffinity, RectHeightStyle, RectWidthStyle,
+    TextBox, TextDirection, TextIndex, TextRange,
 };
 use crate::{
     interop::{Sink, VecSink},
@@ -432,6 +432,19 @@ impl Paragraph {
         })
     }
 
+    /// [`Self::get_glyph_info_at_utf16_offset()`] for shared references.
+    pub(crate) fn glyph_info_at_utf16_offset(&self, code_unit_index: usize) -> Option<GlyphInfo> {
+        GlyphInfo::try_construct(|gi| unsafe {
+            sb::C_Paragraph_getGlyphInfoAtUTF16Offset(self.native_mut_force(), code_unit_index, gi)
+        })
+    }
+
+    /// Returns a [`CaretNavigator`] for editing `text`, which must be the text the paragraph was
+    /// built from, see [`super::ParagraphBuilder::get_text()`].
+    pub fn caret_navigator<'a>(&'a self, text: &'a str) -> CaretNavigator<'a> {
+        CaretNavigator::new(self, text)
+    }
+
     /// Finds the information associated with the closest glyph to the given
     /// paragraph coordinates.
     ///
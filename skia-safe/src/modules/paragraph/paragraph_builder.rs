@@ -3,7 +3,7 @@ use std::{fmt, os::raw, ptr, str};
 use skia_bindings as sb;
 
 use super::{FontCollection, Paragraph, ParagraphStyle, PlaceholderStyle, TextStyle};
-use crate::prelude::*;
+use crate::{interop::VecSink, prelude::*};
 
 pub type ParagraphBuilder = RefHandle<sb::skia_textlayout_ParagraphBuilder>;
 unsafe_send_sync!(ParagraphBuilder);
@@ -56,6 +56,12 @@ impl ParagraphBuilder {
         self
     }
 
+    /// Builds the paragraph.
+    ///
+    /// If the builder was created with [`Self::new_with_client_unicode()`], the paragraph uses the
+    /// text segmentation that was set with [`Self::set_words_utf8()`],
+    /// [`Self::set_grapheme_breaks_utf8()`], [`Self::set_line_breaks_utf8()`], or their UTF-16
+    /// variants.
     pub fn build(&mut self) -> Paragraph {
         Paragraph::from_ptr(unsafe { sb::C_ParagraphBuilder_Build(self.native_mut()) }).unwrap()
     }
@@ -75,12 +81,169 @@ impl ParagraphBuilder {
             .unwrap()
     }
 
-    // TODO: Wrap SetWords*, SetGraphemeBreaks*, setLineBreaks*, getClientICUData, setUnicode.
+    /// Sets the positions where words start and end, as byte indices into the UTF-8 text.
+    ///
+    /// The text segmentation must be set after all text was added. The positions are expected to
+    /// be sorted and to include `0` and the length of the text.
+    pub fn set_words_utf8(&mut self, words: &[usize]) -> &mut Self {
+        unsafe {
+            sb::C_ParagraphBuilder_SetWordsUtf8(self.native_mut(), words.as_ptr(), words.len())
+        }
+        self
+    }
+
+    pub fn words_utf8(&mut self) -> Vec<usize> {
+        self.positions(sb::C_ParagraphBuilder_getWordsUtf8)
+    }
+
+    /// Sets the positions where words start and end, as indices into the UTF-16 representation
+    /// of the text.
+    pub fn set_words_utf16(&mut self, words: &[usize]) -> &mut Self {
+        unsafe {
+            sb::C_ParagraphBuilder_SetWordsUtf16(self.native_mut(), words.as_ptr(), words.len())
+        }
+        self
+    }
+
+    pub fn words_utf16(&mut self) -> Vec<usize> {
+        self.positions(sb::C_ParagraphBuilder_getWordsUtf16)
+    }
+
+    /// Sets the positions where grapheme clusters start, as byte indices into the UTF-8 text.
+    pub fn set_grapheme_breaks_utf8(&mut self, grapheme_breaks: &[usize]) -> &mut Self {
+        unsafe {
+            sb::C_ParagraphBuilder_SetGraphemeBreaksUtf8(
+                self.native_mut(),
+                grapheme_breaks.as_ptr(),
+                grapheme_breaks.len(),
+            )
+        }
+        self
+    }
+
+    pub fn grapheme_breaks_utf8(&mut self) -> Vec<usize> {
+        self.positions(sb::C_ParagraphBuilder_getGraphemeBreaksUtf8)
+    }
+
+    /// Sets the positions where grapheme clusters start, as indices into the UTF-16
+    /// representation of the text.
+    pub fn set_grapheme_breaks_utf16(&mut self, grapheme_breaks: &[usize]) -> &mut Self {
+        unsafe {
+            sb::C_ParagraphBuilder_SetGraphemeBreaksUtf16(
+                self.native_mut(),
+                grapheme_breaks.as_ptr(),
+                grapheme_breaks.len(),
+            )
+        }
+        self
+    }
+
+    pub fn grapheme_breaks_utf16(&mut self) -> Vec<usize> {
+        self.positions(sb::C_ParagraphBuilder_getGraphemeBreaksUtf16)
+    }
+
+    /// Sets the line break opportunities, as byte indices into the UTF-8 text.
+    pub fn set_line_breaks_utf8(&mut self, line_breaks: &[LineBreakBefore]) -> &mut Self {
+        let line_breaks: Vec<_> = line_breaks.iter().map(|lb| lb.into_native()).collect();
+        unsafe {
+            sb::C_ParagraphBuilder_SetLineBreaksUtf8(
+                self.native_mut(),
+                line_breaks.as_ptr(),
+                line_breaks.len(),
+            )
+        }
+        self
+    }
+
+    pub fn line_breaks_utf8(&mut self) -> Vec<LineBreakBefore> {
+        self.line_breaks(sb::C_ParagraphBuilder_getLineBreaksUtf8)
+    }
+
+    /// Sets the line break opportunities, as indices into the UTF-16 representation of the text.
+    pub fn set_line_breaks_utf16(&mut self, line_breaks: &[LineBreakBefore]) -> &mut Self {
+        let line_breaks: Vec<_> = line_breaks.iter().map(|lb| lb.into_native()).collect();
+        unsafe {
+            sb::C_ParagraphBuilder_SetLineBreaksUtf16(
+                self.native_mut(),
+                line_breaks.as_ptr(),
+                line_breaks.len(),
+            )
+        }
+        self
+    }
+
+    pub fn line_breaks_utf16(&mut self) -> Vec<LineBreakBefore> {
+        self.line_breaks(sb::C_ParagraphBuilder_getLineBreaksUtf16)
+    }
+
+    /// Returns the text segmentation in the form it is passed to the client `SkUnicode`
+    /// implementation.
+    pub fn client_icu_data(&self) -> ClientICUData {
+        let mut data = ClientICUData::default();
+        unsafe {
+            sb::C_ParagraphBuilder_getClientICUData(
+                self.native(),
+                VecSink::new(&mut |words: &[usize]| data.words_utf16 = words.to_vec()).native_mut(),
+                VecSink::new(&mut |breaks: &[usize]| data.grapheme_breaks_utf8 = breaks.to_vec())
+                    .native_mut(),
+                VecSink::new(&mut |breaks: &[sb::LineBreakBefore]| {
+                    data.line_breaks_utf8 =
+                        breaks.iter().map(LineBreakBefore::from_native).collect()
+                })
+                .native_mut(),
+            )
+        }
+        data
+    }
+
+    // TODO: Wrap SetUnicode (needs a wrapper for SkUnicode).
+
+    fn positions(
+        &mut self,
+        get: unsafe extern "C" fn(
+            *mut sb::skia_textlayout_ParagraphBuilder,
+            *mut sb::VecSink<usize>,
+        ),
+    ) -> Vec<usize> {
+        let mut positions = Vec::new();
+        let mut set_positions = |p: &[usize]| positions = p.to_vec();
+        unsafe {
+            get(
+                self.native_mut(),
+                VecSink::new(&mut set_positions).native_mut(),
+            )
+        }
+        positions
+    }
+
+    fn line_breaks(
+        &mut self,
+        get: unsafe extern "C" fn(
+            *mut sb::skia_textlayout_ParagraphBuilder,
+            *mut sb::VecSink<sb::LineBreakBefore>,
+        ),
+    ) -> Vec<LineBreakBefore> {
+        let mut line_breaks = Vec::new();
+        let mut set_line_breaks = |lb: &[sb::LineBreakBefore]| {
+            line_breaks = lb.iter().map(LineBreakBefore::from_native).collect()
+        };
+        unsafe {
+            get(
+                self.native_mut(),
+                VecSink::new(&mut set_line_breaks).native_mut(),
+            )
+        }
+        line_breaks
+    }
 
     pub fn reset(&mut self) {
         unsafe { sb::C_ParagraphBuilder_Reset(self.native_mut()) }
     }
 
+    /// Creates a paragraph builder that uses ICU for text segmentation.
+    ///
+    /// Panics if the `textlayout-client-unicode` feature is enabled, use
+    /// [`Self::new_with_client_unicode()`] instead.
     pub fn new(style: &ParagraphStyle, font_collection: impl Into<FontCollection>) -> Self {
         #[cfg(feature = "embed-icudtl")]
         crate::icu::init();
@@ -90,4 +253,142 @@ impl ParagraphBuilder {
         })
         .expect("Unicode initialization error")
     }
+
+    /// Creates a paragraph builder that does not use ICU for text segmentation.
+    ///
+    /// The word, grapheme, and line boundaries must be set with [`Self::set_words_utf8()`],
+    /// [`Self::set_grapheme_breaks_utf8()`], and [`Self::set_line_breaks_utf8()`], or their UTF-16
+    /// variants, after all text was added. They can be computed with crates like
+    /// `unicode-segmentation` or `icu_segmenter`. In contrast to [`Self::new()`], the ICU data
+    /// is not loaded.
+    ///
+    /// Only available with the `textlayout-client-unicode` feature, which builds Skia without ICU.
+    #[cfg(feature = "textlayout-client-unicode")]
+    pub fn new_with_client_unicode(
+        style: &ParagraphStyle,
+        font_collection: impl Into<FontCollection>,
+    ) -> Self {
+        Self::from_ptr(unsafe {
+            sb::C_ParagraphBuilder_makeWithClientUnicode(
+                style.native(),
+                font_collection.into().into_ptr(),
+            )
+        })
+        .expect("Client unicode is not available")
+    }
+}
+
+/// The kind of a line break opportunity.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum LineBreakType {
+    /// Lines may be broken here.
+    SoftLineBreak,
+    /// Lines must be broken here, for example after a newline character.
+    HardLineBreak,
+}
+
+/// A line break opportunity before the text position `pos`.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct LineBreakBefore {
+    pub pos: usize,
+    pub break_type: LineBreakType,
+}
+
+impl LineBreakBefore {
+    pub fn new(pos: usize, break_type: LineBreakType) -> Self {
+        Self { pos, break_type }
+    }
+
+    fn from_native(lb: &sb::LineBreakBefore) -> Self {
+        let break_type = if lb.hard {
+            LineBreakType::HardLineBreak
+        } else {
+            LineBreakType::SoftLineBreak
+        };
+        Self::new(lb.pos, break_type)
+    }
+
+    fn into_native(self) -> sb::LineBreakBefore {
+        sb::LineBreakBefore {
+            pos: self.pos,
+            hard: self.break_type == LineBreakType::HardLineBreak,
+        }
+    }
+}
+
+/// The text segmentation of a [`ParagraphBuilder`], see [`ParagraphBuilder::client_icu_data()`].
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+pub struct ClientICUData {
+    pub words_utf16: Vec<usize>,
+    pub grapheme_breaks_utf8: Vec<usize>,
+    pub line_breaks_utf8: Vec<LineBreakBefore>,
+}
+
+#[cfg(all(test, feature = "textlayout-client-unicode"))]
+mod tests {
+    use super::{LineBreakBefore, LineBreakType, ParagraphBuilder};
+    use crate::{
+        textlayout::{FontCollection, ParagraphStyle, TextStyle},
+        FontMgr,
+    };
+
+    const TEXT: &str = "hello world";
+
+    fn client_builder() -> ParagraphBuilder {
+        let mut font_collection = FontCollection::new();
+        font_collection.set_default_font_manager(FontMgr::new(), None);
+        let mut builder =
+            ParagraphBuilder::new_with_client_unicode(&ParagraphStyle::new(), font_collection);
+        builder.push_style(&TextStyle::new());
+        builder.add_text(TEXT);
+        builder
+            .set_words_utf8(&[0, 5, 6, 11])
+            .set_grapheme_breaks_utf8(&(0..=TEXT.len()).collect::<Vec<_>>())
+            .set_line_breaks_utf8(&[
+                LineBreakBefore::new(6, LineBreakType::SoftLineBreak),
+                LineBreakBefore::new(11, LineBreakType::SoftLineBreak),
+            ]);
+        builder
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn client_segmentation_is_converted_between_encodings() {
+        // `ñ` takes two bytes in UTF-8, and `😀` takes four bytes in UTF-8 and a surrogate pair in
+        // UTF-16.
+        const TEXT: &str = "añb 😀c";
+        let soft = |pos| LineBreakBefore::new(pos, LineBreakType::SoftLineBreak);
+
+        let mut font_collection = FontCollection::new();
+        font_collection.set_default_font_manager(FontMgr::new(), None);
+        let mut builder =
+            ParagraphBuilder::new_with_client_unicode(&ParagraphStyle::new(), font_collection);
+        builder.push_style(&TextStyle::new());
+        builder.add_text(TEXT);
+        builder
+            .set_words_utf8(&[0, 4, 5, 9, 10])
+            .set_grapheme_breaks_utf8(&[0, 1, 3, 4, 5, 9, 10])
+            .set_line_breaks_utf8(&[soft(5), soft(10)]);
+
+        assert_eq!(builder.words_utf16(), [0, 3, 4, 6, 7]);
+        assert_eq!(builder.grapheme_breaks_utf16(), [0, 1, 2, 3, 4, 6, 7]);
+        assert_eq!(builder.line_breaks_utf16(), [soft(4), soft(7)]);
+
+        let data = builder.client_icu_data();
+        assert_eq!(data.words_utf16, [0, 3, 4, 6, 7]);
+        assert_eq!(data.grapheme_breaks_utf8, [0, 1, 3, 4, 5, 9, 10]);
+        assert_eq!(data.line_breaks_utf8, [soft(5), soft(10)]);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn layout_with_client_line_breaks() {
+        let mut paragraph = client_builder().build();
+        paragraph.layout(1000.0);
+        assert_eq!(paragraph.line_number(), 1);
+
+        let width = paragraph.max_intrinsic_width();
+        paragraph.layout(width * 0.75);
+        assert_eq!(paragraph.line_number(), 2);
+    }
 }
@@ -2,11 +2,15 @@ use std::{borrow::Cow, ffi::CStr, mem, os::raw, ptr};
 
 use helpers::ResourceKind;
 use skia_bindings::{
-    self as sb, skresources_ImageAsset, RustResourceProvider, RustResourceProvider_Param, SkData,
-    SkFontMgr, SkRefCnt, SkRefCntBase, SkTypeface, TraitObject,
+    self as sb, skresources_ImageAsset, RustImageAsset_Param, RustResourceProvider,
+    RustResourceProvider_Param, SkData, SkFontMgr, SkImage, SkMatrix, SkRefCnt, SkRefCntBase,
+    SkSamplingOptions, SkTypeface, TraitObject,
 };
 
-use crate::{prelude::*, Data, FontMgr, Typeface};
+use crate::{prelude::*, Data, FontMgr, Image, Matrix, SamplingOptions, Typeface};
+
+mod sandboxed;
+pub use sandboxed::*;
 
 pub type ImageAsset = RCHandle<skresources_ImageAsset>;
 require_base_type!(skresources_ImageAsset, SkRefCnt);
@@ -16,11 +20,69 @@ impl NativeRefCountedBase for skresources_ImageAsset {
 }
 
 impl ImageAsset {
+    /// Creates an [`ImageAsset`] that is implemented in Rust.
+    pub fn new(asset: impl ImageAssetImpl + 'static) -> Self {
+        let asset: Box<dyn ImageAssetImpl> = Box::new(asset);
+        let param = RustImageAsset_Param {
+            trait_: unsafe { mem::transmute::<Box<dyn ImageAssetImpl>, TraitObject>(asset) },
+            drop: Some(drop),
+            isMultiFrame: Some(is_multi_frame),
+            getFrameData: Some(get_frame_data),
+        };
+
+        return ImageAsset::from_ptr(unsafe { sb::C_RustImageAsset_New(&param) }).unwrap();
+
+        extern "C" fn drop(asset: TraitObject) {
+            mem::drop(unsafe { mem::transmute::<TraitObject, Box<dyn ImageAssetImpl>>(asset) });
+        }
+
+        extern "C" fn is_multi_frame(asset: TraitObject) -> bool {
+            unsafe { asset_mut(asset) }.is_multi_frame()
+        }
+
+        extern "C" fn get_frame_data(
+            asset: TraitObject,
+            t: f32,
+            image: *mut *mut SkImage,
+            sampling: *mut SkSamplingOptions,
+            matrix: *mut SkMatrix,
+            scaling: *mut SizeFit,
+        ) {
+            let frame_data = unsafe { asset_mut(asset) }.frame_data(t);
+            unsafe {
+                *image = frame_data.image.into_ptr_or_null();
+                *sampling = frame_data.sampling.into_native();
+                *matrix = frame_data.matrix.into_native();
+                *scaling = frame_data.scaling;
+            }
+        }
+
+        unsafe fn asset_mut<'a>(asset: TraitObject) -> &'a mut dyn ImageAssetImpl {
+            mem::transmute(asset)
+        }
+    }
+
     pub fn is_multi_frame(&self) -> bool {
         unsafe { sb::C_ImageAsset_isMultiFrame(self.native_mut_force()) }
     }
 
-    // TODO: wrap getFrameData()
+    /// Returns the frame to draw at time `t`, in seconds.
+    pub fn frame_data(&self, t: f32) -> FrameData {
+        let mut image = ptr::null_mut();
+        let mut frame_data = FrameData::default();
+        unsafe {
+            sb::C_ImageAsset_getFrameData(
+                self.native_mut_force(),
+                t,
+                &mut image,
+                frame_data.sampling.native_mut(),
+                frame_data.matrix.native_mut(),
+                &mut frame_data.scaling,
+            )
+        };
+        frame_data.image = Image::from_ptr(image);
+        frame_data
+    }
 
     pub fn from_data(
         data: impl Into<Data>,
@@ -41,6 +103,53 @@ impl ImageAsset {
 pub use sb::skresources_ImageDecodeStrategy as ImageDecodeStrategy;
 variant_name!(ImageDecodeStrategy::LazyDecode);
 
+/// How the image of a [`FrameData`] is fitted into the bounds it is drawn to.
+pub use sb::skresources_ImageAsset_SizeFit as SizeFit;
+variant_name!(SizeFit::Center);
+
+/// A frame of an [`ImageAsset`].
+#[derive(Clone, Debug)]
+pub struct FrameData {
+    /// The image of the frame, `None` if there is nothing to draw.
+    pub image: Option<Image>,
+    /// The sampling options the image is drawn with.
+    pub sampling: SamplingOptions,
+    /// An additional transform that is applied to the image before it is fitted.
+    pub matrix: Matrix,
+    pub scaling: SizeFit,
+}
+
+impl Default for FrameData {
+    fn default() -> Self {
+        Self {
+            image: None,
+            sampling: SamplingOptions::default(),
+            matrix: Matrix::default(),
+            scaling: SizeFit::Center,
+        }
+    }
+}
+
+impl FrameData {
+    pub fn new(image: impl Into<Option<Image>>) -> Self {
+        Self {
+            image: image.into(),
+            ..Self::default()
+        }
+    }
+}
+
+/// A trait for implementing [`ImageAsset`]s in Rust, for example to supply the frames of a video
+/// decoder.
+pub trait ImageAssetImpl: Send {
+    /// Returns `true` if the asset may return different frames for different times. Single frame
+    /// assets may be asked for their frame only once.
+    fn is_multi_frame(&mut self) -> bool;
+
+    /// Returns the frame to draw at time `t`, in seconds.
+    fn frame_data(&mut self, t: f32) -> FrameData;
+}
+
 // TODO: Wrap ExternalTrackAsset
 
 pub trait ResourceProvider {
@@ -363,3 +472,59 @@ pub mod helpers {
         assert_eq!(0, decode_base64("$GVsbG8h").len());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{FrameData, ImageAsset, ImageAssetImpl, SizeFit};
+    use crate::{surfaces, Color, EncodedImageFormat, Image, Matrix};
+
+    fn image(color: Color) -> Image {
+        let mut surface = surfaces::raster_n32_premul((4, 2)).unwrap();
+        surface.canvas().clear(color);
+        surface.image_snapshot()
+    }
+
+    struct Frames(Vec<Image>);
+
+    impl ImageAssetImpl for Frames {
+        fn is_multi_frame(&mut self) -> bool {
+            self.0.len() > 1
+        }
+
+        fn frame_data(&mut self, t: f32) -> FrameData {
+            let index = (t.max(0.0) as usize).min(self.0.len() - 1);
+            FrameData {
+                matrix: Matrix::translate((index as f32, 0.0)),
+                scaling: SizeFit::Fill,
+                ..FrameData::new(self.0[index].clone())
+            }
+        }
+    }
+
+    #[test]
+    fn frames_of_rust_image_assets() {
+        let asset = ImageAsset::new(Frames(vec![image(Color::RED), image(Color::BLUE)]));
+        assert!(asset.is_multi_frame());
+
+        let frame = asset.frame_data(1.5);
+        let image = frame.image.unwrap();
+        let pixmap = image.peek_pixels().unwrap();
+        assert_eq!(pixmap.get_color((0, 0)), Color::BLUE);
+        assert_eq!(frame.matrix, Matrix::translate((1.0, 0.0)));
+        assert_eq!(frame.scaling, SizeFit::Fill);
+    }
+
+    #[test]
+    fn frame_of_encoded_image_asset() {
+        let data = image(Color::GREEN)
+            .encode(None, EncodedImageFormat::PNG, None)
+            .unwrap();
+        let asset = ImageAsset::from_data(data, None).unwrap();
+        assert!(!asset.is_multi_frame());
+
+        let frame = asset.frame_data(0.0);
+        assert_eq!(frame.image.unwrap().dimensions(), (4, 2).into());
+        assert_eq!(frame.matrix, Matrix::default());
+        assert_eq!(frame.scaling, SizeFit::Center);
+    }
+}
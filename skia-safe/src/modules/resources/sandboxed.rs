@@ -0,0 +1,506 @@
+use std::{
+    fmt,
+    fs::{self, File},
+    io::Read,
+    path::{Component, Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use super::{
+    helpers::{self, ResourceKind},
+    ImageAsset, ResourceProvider,
+};
+use crate::{codec::Codec, Data, FontMgr, ISize, Typeface};
+
+type RejectionHandler = Box<dyn Fn(&Rejection) + Send + Sync>;
+
+/// A resource provider for untrusted documents that loads resources only from a root directory.
+///
+/// Relative paths and `file://` URLs with relative paths are resolved against the root directory,
+/// absolute ones must point into it. Inline base64 data is accepted, too. Everything else, like
+/// `http://` URLs or paths that escape the root directory, is rejected.
+///
+/// The size of each resource and the sum of the sizes of all resources loaded over the lifetime
+/// of the provider can be limited, and so can the dimensions of images. Rejected requests are
+/// ignored unless a rejection handler is set.
+pub struct SandboxedResourceProvider {
+    font_mgr: FontMgr,
+    root: PathBuf,
+    max_resource_bytes: Option<usize>,
+    max_total_bytes: Option<usize>,
+    max_image_dimensions: Option<ISize>,
+    on_rejection: Option<RejectionHandler>,
+    loaded_bytes: AtomicUsize,
+}
+
+impl fmt::Debug for SandboxedResourceProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SandboxedResourceProvider")
+            .field("font_mgr", &self.font_mgr)
+            .field("root", &self.root)
+            .field("max_resource_bytes", &self.max_resource_bytes)
+            .field("max_total_bytes", &self.max_total_bytes)
+            .field("max_image_dimensions", &self.max_image_dimensions)
+            .field("loaded_bytes", &self.loaded_bytes())
+            .finish()
+    }
+}
+
+impl SandboxedResourceProvider {
+    /// Creates a provider that loads resources from the directory `root` without any size limits.
+    ///
+    /// Returns `None` if `root` is not an existing directory.
+    pub fn new(root: impl AsRef<Path>, font_mgr: impl Into<FontMgr>) -> Option<Self> {
+        let root = fs::canonicalize(root).ok()?;
+        if !root.is_dir() {
+            return None;
+        }
+        Some(Self {
+            font_mgr: font_mgr.into(),
+            root,
+            max_resource_bytes: None,
+            max_total_bytes: None,
+            max_image_dimensions: None,
+            on_rejection: None,
+            loaded_bytes: AtomicUsize::new(0),
+        })
+    }
+
+    /// Limits the size of a single resource.
+    pub fn with_max_resource_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_resource_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Limits the sum of the sizes of all resources this provider loads.
+    pub fn with_max_total_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_total_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Limits the width and the height of images. The dimensions are read from the encoded data,
+    /// images that exceed them are never decoded.
+    pub fn with_max_image_dimensions(mut self, max_dimensions: impl Into<ISize>) -> Self {
+        self.max_image_dimensions = Some(max_dimensions.into());
+        self
+    }
+
+    /// Sets the function that is invoked for every rejected request.
+    pub fn with_rejection_handler(
+        mut self,
+        on_rejection: impl Fn(&Rejection) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_rejection = Some(Box::new(on_rejection));
+        self
+    }
+
+    /// The canonical root directory resources are loaded from.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// The sum of the sizes of the resources loaded so far.
+    pub fn loaded_bytes(&self) -> usize {
+        self.loaded_bytes.load(Ordering::Relaxed)
+    }
+
+    /// Reads a resource without charging it to the total byte budget.
+    fn read(&self, kind: ResourceKind) -> Result<Data, RejectionReason> {
+        match kind {
+            ResourceKind::Base64(data) => {
+                self.check_resource_size(data.size())?;
+                Ok(data)
+            }
+            ResourceKind::DownloadFromUrl(url) => {
+                let path = self.resolve(&url)?;
+                self.read_file(&path)
+            }
+        }
+    }
+
+    /// Resolves a resource location to a file inside the root directory.
+    fn resolve(&self, location: &str) -> Result<PathBuf, RejectionReason> {
+        let location = match location.split_once("://") {
+            Some(("file", path)) => path,
+            Some((scheme, _)) => {
+                return Err(RejectionReason::UnsupportedScheme(scheme.to_string()));
+            }
+            None => location,
+        };
+        let location = percent_encoding::percent_decode_str(location).decode_utf8_lossy();
+        let location = Path::new(location.as_ref());
+
+        let mut path = if location.is_absolute() {
+            PathBuf::new()
+        } else {
+            self.root.clone()
+        };
+        for component in location.components() {
+            match component {
+                Component::Prefix(_) | Component::RootDir => path.push(component),
+                Component::CurDir => {}
+                Component::ParentDir => {
+                    if !path.pop() {
+                        return Err(RejectionReason::OutsideRoot);
+                    }
+                }
+                Component::Normal(name) => path.push(name),
+            }
+        }
+        if !path.starts_with(&self.root) {
+            return Err(RejectionReason::OutsideRoot);
+        }
+
+        // Symbolic links may point outside of the root directory.
+        let path = fs::canonicalize(&path).map_err(|_| RejectionReason::NotFound)?;
+        if !path.starts_with(&self.root) {
+            return Err(RejectionReason::OutsideRoot);
+        }
+        Ok(path)
+    }
+
+    fn read_file(&self, path: &Path) -> Result<Data, RejectionReason> {
+        let file = File::open(path).map_err(|_| RejectionReason::NotFound)?;
+        let metadata = file.metadata().map_err(|_| RejectionReason::NotFound)?;
+        if !metadata.is_file() {
+            return Err(RejectionReason::NotFound);
+        }
+        let size = metadata.len().try_into().unwrap_or(usize::MAX);
+        self.check_resource_size(size)?;
+        self.check_remaining_bytes(size)?;
+
+        // The file may grow after its size was checked.
+        let limit = [self.max_resource_bytes, self.remaining_bytes()]
+            .into_iter()
+            .flatten()
+            .min()
+            .map_or(u64::MAX, |max| max as u64 + 1);
+        let mut bytes = Vec::new();
+        file.take(limit)
+            .read_to_end(&mut bytes)
+            .map_err(|_| RejectionReason::NotFound)?;
+        self.check_resource_size(bytes.len())?;
+        self.check_remaining_bytes(bytes.len())?;
+        Ok(Data::new_copy(&bytes))
+    }
+
+    fn check_resource_size(&self, size: usize) -> Result<(), RejectionReason> {
+        match self.max_resource_bytes {
+            Some(limit) if size > limit => Err(RejectionReason::ResourceTooLarge { size, limit }),
+            _ => Ok(()),
+        }
+    }
+
+    /// The bytes left in the total budget, `None` if there is no total budget.
+    fn remaining_bytes(&self) -> Option<usize> {
+        self.max_total_bytes
+            .map(|limit| limit.saturating_sub(self.loaded_bytes()))
+    }
+
+    /// Checks `size` against the total budget without charging it, see [`Self::reserve()`].
+    fn check_remaining_bytes(&self, size: usize) -> Result<(), RejectionReason> {
+        match self.remaining_bytes() {
+            Some(remaining) if size > remaining => {
+                Err(RejectionReason::BudgetExceeded { size, remaining })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Adds `size` to the loaded bytes if the total budget allows it.
+    fn reserve(&self, size: usize) -> Result<(), RejectionReason> {
+        let Some(limit) = self.max_total_bytes else {
+            self.loaded_bytes.fetch_add(size, Ordering::Relaxed);
+            return Ok(());
+        };
+        self.loaded_bytes
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |loaded| {
+                loaded.checked_add(size).filter(|total| *total <= limit)
+            })
+            .map(|_| ())
+            .map_err(|loaded| RejectionReason::BudgetExceeded {
+                size,
+                remaining: limit.saturating_sub(loaded),
+            })
+    }
+
+    fn check_image_dimensions(&self, data: &Data) -> Result<(), RejectionReason> {
+        let Some(limit) = self.max_image_dimensions else {
+            return Ok(());
+        };
+        let codec = Codec::from_data(data.clone()).ok_or(RejectionReason::UnsupportedImage)?;
+        let dimensions = codec.dimensions();
+        if dimensions.width > limit.width || dimensions.height > limit.height {
+            return Err(RejectionReason::ImageTooLarge { dimensions, limit });
+        }
+        Ok(())
+    }
+
+    fn reject(&self, resource: String, reason: RejectionReason) {
+        if let Some(on_rejection) = &self.on_rejection {
+            on_rejection(&Rejection { resource, reason });
+        }
+    }
+}
+
+impl ResourceProvider for SandboxedResourceProvider {
+    fn load(&self, resource_path: &str, resource_name: &str) -> Option<Data> {
+        let kind = resource_kind(resource_path, resource_name);
+        let resource = resource_description(&kind);
+        self.read(kind)
+            .and_then(|data| {
+                self.reserve(data.size())?;
+                Ok(data)
+            })
+            .map_err(|reason| self.reject(resource, reason))
+            .ok()
+    }
+
+    fn load_image_asset(
+        &self,
+        resource_path: &str,
+        resource_name: &str,
+        _resource_id: &str,
+    ) -> Option<ImageAsset> {
+        let kind = resource_kind(resource_path, resource_name);
+        let resource = resource_description(&kind);
+        self.read(kind)
+            .and_then(|data| {
+                self.check_image_dimensions(&data)?;
+                let size = data.size();
+                let asset =
+                    ImageAsset::from_data(data, None).ok_or(RejectionReason::UnsupportedImage)?;
+                // Rejected images are not charged to the budget.
+                self.reserve(size)?;
+                Ok(asset)
+            })
+            .map_err(|reason| self.reject(resource, reason))
+            .ok()
+    }
+
+    fn load_typeface(&self, name: &str, url: &str) -> Option<Typeface> {
+        helpers::load_typeface(self, &self.font_mgr, name, url)
+    }
+
+    fn font_mgr(&self) -> FontMgr {
+        self.font_mgr.clone()
+    }
+}
+
+/// Unlike [`helpers::identify_resource_kind()`], this treats a resource with an empty path as a
+/// file relative to the root directory unless it is a `data:` URL.
+fn resource_kind(resource_path: &str, resource_name: &str) -> ResourceKind {
+    if resource_name.starts_with("data:") {
+        return helpers::identify_resource_kind("", resource_name);
+    }
+    ResourceKind::DownloadFromUrl(if resource_path.is_empty() {
+        resource_name.to_string()
+    } else {
+        format!("{resource_path}/{resource_name}")
+    })
+}
+
+fn resource_description(kind: &ResourceKind) -> String {
+    match kind {
+        ResourceKind::Base64(data) => format!("inline data ({} bytes)", data.size()),
+        ResourceKind::DownloadFromUrl(url) => url.clone(),
+    }
+}
+
+/// A request a [`SandboxedResourceProvider`] refused to serve.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Rejection {
+    /// The requested resource.
+    pub resource: String,
+    pub reason: RejectionReason,
+}
+
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "rejected resource {:?}: {}", self.resource, self.reason)
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum RejectionReason {
+    /// The resource uses a URL scheme other than `file`.
+    UnsupportedScheme(String),
+    /// The resource is located outside of the root directory.
+    OutsideRoot,
+    /// The resource does not exist or can not be read.
+    NotFound,
+    /// The resource is larger than the per resource limit.
+    ResourceTooLarge { size: usize, limit: usize },
+    /// Loading the resource would exceed the total byte budget.
+    BudgetExceeded { size: usize, remaining: usize },
+    /// The image is larger than the configured maximum dimensions.
+    ImageTooLarge { dimensions: ISize, limit: ISize },
+    /// The resource is not an image that can be decoded.
+    UnsupportedImage,
+}
+
+impl fmt::Display for RejectionReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnsupportedScheme(scheme) => write!(f, "unsupported scheme {scheme:?}"),
+            Self::OutsideRoot => write!(f, "outside of the root directory"),
+            Self::NotFound => write!(f, "not found"),
+            Self::ResourceTooLarge { size, limit } => {
+                write!(f, "{size} bytes exceed the limit of {limit} bytes")
+            }
+            Self::BudgetExceeded { size, remaining } => write!(
+                f,
+                "{size} bytes exceed the remaining budget of {remaining} bytes"
+            ),
+            Self::ImageTooLarge { dimensions, limit } => write!(
+                f,
+                "image of {}x{} exceeds the limit of {}x{}",
+                dimensions.width, dimensions.height, limit.width, limit.height
+            ),
+            Self::UnsupportedImage => write!(f, "not a supported image"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        sync::{Arc, Mutex},
+    };
+
+    use tempfile::TempDir;
+
+    use super::{Rejection, RejectionReason, SandboxedResourceProvider};
+    use crate::{resources::ResourceProvider, surfaces, Color, EncodedImageFormat, FontMgr, ISize};
+
+    /// Creates a directory with the resource root `root` and a file outside of it.
+    fn resource_dir() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path();
+        fs::create_dir_all(path.join("root/images")).unwrap();
+        fs::write(path.join("secret.txt"), b"secret").unwrap();
+        fs::write(path.join("root/hello.txt"), b"hello").unwrap();
+        fs::write(path.join("root/images/small.png"), png((4, 4))).unwrap();
+        fs::write(path.join("root/images/large.png"), png((64, 32))).unwrap();
+        dir
+    }
+
+    fn logging_provider(dir: &TempDir) -> (SandboxedResourceProvider, Arc<Mutex<Vec<Rejection>>>) {
+        let rejections = Arc::new(Mutex::new(Vec::new()));
+        let log = rejections.clone();
+        let provider =
+            SandboxedResourceProvider::new(dir.path().join("root"), FontMgr::new_empty())
+                .unwrap()
+                .with_rejection_handler(move |rejection| {
+                    log.lock().unwrap().push(rejection.clone())
+                });
+        (provider, rejections)
+    }
+
+    fn png(size: impl Into<ISize>) -> Vec<u8> {
+        let mut surface = surfaces::raster_n32_premul(size).unwrap();
+        surface.canvas().clear(Color::RED);
+        let image = surface.image_snapshot();
+        let data = image.encode(None, EncodedImageFormat::PNG, None).unwrap();
+        data.as_bytes().to_vec()
+    }
+
+    fn reasons(rejections: &Mutex<Vec<Rejection>>) -> Vec<RejectionReason> {
+        rejections
+            .lock()
+            .unwrap()
+            .drain(..)
+            .map(|rejection| rejection.reason)
+            .collect()
+    }
+
+    #[test]
+    fn resolves_paths_inside_of_the_root_only() {
+        let dir = resource_dir();
+        let (provider, rejections) = logging_provider(&dir);
+
+        let hello = provider.load("", "hello.txt").unwrap();
+        assert_eq!(hello.as_bytes(), b"hello");
+        assert!(provider.load("images", "../hello.txt").is_some());
+        let url = format!("file://{}", provider.root().join("hello.txt").display());
+        assert!(provider.load("", &url).is_some());
+        assert!(reasons(&rejections).is_empty());
+
+        assert!(provider.load("", "../secret.txt").is_none());
+        assert!(provider.load("images", "../../secret.txt").is_none());
+        let url = format!("file://{}", dir.path().join("secret.txt").display());
+        assert!(provider.load("", &url).is_none());
+        assert!(provider.load("", "missing.txt").is_none());
+        assert!(provider.load("http://example.com", "image.png").is_none());
+
+        assert_eq!(
+            reasons(&rejections),
+            [
+                RejectionReason::OutsideRoot,
+                RejectionReason::OutsideRoot,
+                RejectionReason::OutsideRoot,
+                RejectionReason::NotFound,
+                RejectionReason::UnsupportedScheme("http".into()),
+            ]
+        );
+        assert_eq!(provider.loaded_bytes(), 3 * b"hello".len());
+    }
+
+    #[test]
+    fn enforces_byte_budgets() {
+        let dir = resource_dir();
+        let (provider, rejections) = logging_provider(&dir);
+        let provider = provider.with_max_resource_bytes(8).with_max_total_bytes(12);
+
+        assert!(provider.load("images", "small.png").is_none());
+        assert!(provider.load("", "hello.txt").is_some());
+        assert!(provider.load("", "hello.txt").is_some());
+        assert!(provider.load("", "hello.txt").is_none());
+        assert_eq!(provider.loaded_bytes(), 10);
+
+        let reasons = reasons(&rejections);
+        assert!(matches!(
+            reasons[0],
+            RejectionReason::ResourceTooLarge { limit: 8, .. }
+        ));
+        assert_eq!(
+            reasons[1],
+            RejectionReason::BudgetExceeded {
+                size: 5,
+                remaining: 2
+            }
+        );
+    }
+
+    #[test]
+    fn caps_image_dimensions() {
+        let dir = resource_dir();
+        let (provider, rejections) = logging_provider(&dir);
+        let provider = provider.with_max_image_dimensions((32, 32));
+
+        let small = provider
+            .load_image_asset("images", "small.png", "")
+            .unwrap();
+        assert!(!small.is_multi_frame());
+        assert!(provider
+            .load_image_asset("images", "large.png", "")
+            .is_none());
+        assert!(provider.load_image_asset("", "hello.txt", "").is_none());
+        let small_size = fs::metadata(provider.root().join("images/small.png"))
+            .unwrap()
+            .len();
+        assert_eq!(provider.loaded_bytes() as u64, small_size);
+
+        assert_eq!(
+            reasons(&rejections),
+            [
+                RejectionReason::ImageTooLarge {
+                    dimensions: ISize::new(64, 32),
+                    limit: ISize::new(32, 32)
+                },
+                RejectionReason::UnsupportedImage,
+            ]
+        );
+    }
+}
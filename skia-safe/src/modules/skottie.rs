@@ -0,0 +1,274 @@
+use std::{error::Error, fmt, io};
+
+use skia_bindings::{self as sb, skottie_Animation};
+
+use crate::{
+    interop::{self, MemoryStream, NativeStreamBase, RustStream},
+    prelude::*,
+    resources::NativeResourceProvider,
+    Canvas, Rect, Size,
+};
+
+/// A Lottie animation that can be seeked and rendered.
+///
+/// Images and fonts referenced by the animation are loaded through a [`NativeResourceProvider`],
+/// see [`crate::svg::Dom`] for how to create one.
+pub type Animation = RCHandle<skottie_Animation>;
+
+impl NativeRefCounted for skottie_Animation {
+    fn _ref(&self) {
+        unsafe { sb::C_skottie_Animation_ref(self) }
+    }
+
+    fn _unref(&self) {
+        unsafe { sb::C_skottie_Animation_unref(self) }
+    }
+
+    fn unique(&self) -> bool {
+        unsafe { sb::C_skottie_Animation_unique(self) }
+    }
+}
+
+/// Error when something goes wrong when loading a Lottie animation. Skia does not report further
+/// details.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LoadError;
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Failed to load animation (reason unknown)")
+    }
+}
+
+impl Error for LoadError {}
+
+impl From<LoadError> for io::Error {
+    fn from(other: LoadError) -> Self {
+        io::Error::new(io::ErrorKind::Other, other)
+    }
+}
+
+bitflags! {
+    /// Flags that control how the top level of an [`Animation`] is rendered.
+    #[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+    pub struct RenderFlags: u32 {
+        /// When rendering into a known transparent buffer, clients can pass this flag to avoid
+        /// some unnecessary compositing overhead for animations using layer blend modes.
+        const SKIP_TOP_LEVEL_ISOLATION = 0x01;
+        /// By default, content is clipped to the intrinsic animation bounds (as determined by its
+        /// size). If this flag is set, then the animation can draw outside of the bounds.
+        const DISABLE_TOP_LEVEL_CLIPPING = 0x02;
+    }
+}
+
+impl Default for RenderFlags {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl fmt::Debug for Animation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Animation")
+            .field("version", &self.version())
+            .field("size", &self.size())
+            .field("duration", &self.duration())
+            .field("fps", &self.fps())
+            .field("in_point", &self.in_point())
+            .field("out_point", &self.out_point())
+            .finish()
+    }
+}
+
+impl Animation {
+    pub fn read<R: io::Read>(
+        mut reader: R,
+        resource_provider: impl Into<NativeResourceProvider>,
+    ) -> Result<Self, LoadError> {
+        let mut reader = RustStream::new(&mut reader);
+        let stream = reader.stream_mut();
+        let resource_provider = resource_provider.into();
+
+        Self::from_loaded(unsafe {
+            sb::C_skottie_Animation_MakeFromStream(stream, resource_provider.into_ptr())
+        })
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(
+        json: impl AsRef<str>,
+        resource_provider: impl Into<NativeResourceProvider>,
+    ) -> Result<Self, LoadError> {
+        Self::from_bytes(json.as_ref().as_bytes(), resource_provider)
+    }
+
+    pub fn from_bytes(
+        json: &[u8],
+        resource_provider: impl Into<NativeResourceProvider>,
+    ) -> Result<Self, LoadError> {
+        let mut ms = MemoryStream::from_bytes(json);
+        let resource_provider = resource_provider.into();
+
+        Self::from_loaded(unsafe {
+            sb::C_skottie_Animation_MakeFromStream(
+                ms.native_mut().as_stream_mut(),
+                resource_provider.into_ptr(),
+            )
+        })
+    }
+
+    // Skottie requires a frame to be selected before the animation is rendered.
+    fn from_loaded(animation: *mut skottie_Animation) -> Result<Self, LoadError> {
+        let mut animation = Self::from_ptr(animation).ok_or(LoadError)?;
+        animation.seek_frame(animation.in_point());
+        Ok(animation)
+    }
+
+    /// The Lottie format version the animation was exported with.
+    pub fn version(&self) -> String {
+        let mut version = interop::String::default();
+        unsafe { sb::C_skottie_Animation_version(self.native(), version.native_mut()) };
+        version.as_str().into()
+    }
+
+    /// The intrinsic size of the animation.
+    pub fn size(&self) -> Size {
+        Size::construct(|s| unsafe { sb::C_skottie_Animation_size(self.native(), s) })
+    }
+
+    /// The animation duration in seconds.
+    pub fn duration(&self) -> f64 {
+        unsafe { sb::C_skottie_Animation_duration(self.native()) }
+    }
+
+    /// The animation frame rate (frames / second).
+    pub fn fps(&self) -> f64 {
+        unsafe { sb::C_skottie_Animation_fps(self.native()) }
+    }
+
+    /// Animation in point, in frame index units.
+    pub fn in_point(&self) -> f64 {
+        unsafe { sb::C_skottie_Animation_inPoint(self.native()) }
+    }
+
+    /// Animation out point, in frame index units.
+    pub fn out_point(&self) -> f64 {
+        unsafe { sb::C_skottie_Animation_outPoint(self.native()) }
+    }
+
+    /// Update the animation state to match `t`, specified as a normalized value in the range of
+    /// `[0..1]`.
+    pub fn seek(&mut self, t: f32) {
+        unsafe { sb::C_skottie_Animation_seek(self.native_mut(), t) }
+    }
+
+    /// Update the animation state to match `t`, specified as a frame index, i.e. relative to
+    /// [`Self::duration()`] * [`Self::fps()`].
+    ///
+    /// Fractional values are allowed and meaningful - e.g. `0.0 -> 0.5 -> 1.0 -> 1.5 -> 2.0` would
+    /// render "half frames".
+    pub fn seek_frame(&mut self, t: f64) {
+        unsafe { sb::C_skottie_Animation_seekFrame(self.native_mut(), t) }
+    }
+
+    /// Update the animation state to match `t`, specified in seconds in the range of
+    /// `[0..duration]`.
+    pub fn seek_frame_time(&mut self, t: f64) {
+        unsafe { sb::C_skottie_Animation_seekFrameTime(self.native_mut(), t) }
+    }
+
+    /// Draws the current animation frame. A newly loaded animation shows the frame at
+    /// [`Self::in_point()`].
+    ///
+    /// If `dst` is specified, the animation is scaled to fit the destination rectangle.
+    pub fn render(&self, canvas: &Canvas, dst: impl Into<Option<Rect>>) {
+        self.render_with_flags(canvas, dst, RenderFlags::default())
+    }
+
+    pub fn render_with_flags(
+        &self,
+        canvas: &Canvas,
+        dst: impl Into<Option<Rect>>,
+        flags: RenderFlags,
+    ) {
+        #[cfg(feature = "embed-icudtl")]
+        crate::icu::init();
+
+        let dst = dst.into();
+        unsafe {
+            sb::C_skottie_Animation_render(
+                self.native(),
+                canvas.native_mut(),
+                dst.native().as_ptr_or_null(),
+                flags.bits(),
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Animation;
+    use crate::{surfaces, Color, FontMgr, Rect};
+
+    // A single red square that moves from left to right in one second. Layers are only active
+    // before their out point, so the layer ends after the last frame of the animation.
+    const ANIMATION: &str = r##"{
+        "v": "5.7.4", "fr": 30, "ip": 0, "op": 30, "w": 100, "h": 100, "layers": [{
+            "ty": 1, "ind": 1, "ip": 0, "op": 31, "st": 0,
+            "sw": 50, "sh": 50, "sc": "#ff0000",
+            "ks": {
+                "p": { "a": 1, "k": [
+                    { "t": 0, "s": [0, 25, 0] },
+                    { "t": 30, "s": [50, 25, 0] }
+                ]}
+            }
+        }]
+    }"##;
+
+    #[test]
+    fn load_animation_properties() {
+        let animation = Animation::from_str(ANIMATION, FontMgr::new()).unwrap();
+        assert_eq!(animation.version(), "5.7.4");
+        assert_eq!(animation.size(), (100.0, 100.0).into());
+        assert_eq!(animation.fps(), 30.0);
+        assert_eq!(animation.duration(), 1.0);
+        assert_eq!(animation.in_point(), 0.0);
+        assert_eq!(animation.out_point(), 30.0);
+    }
+
+    #[test]
+    fn invalid_animation_fails_to_load() {
+        assert!(Animation::from_str("{}", FontMgr::new()).is_err());
+    }
+
+    #[test]
+    fn seek_and_render_animation() {
+        let mut animation = Animation::from_str(ANIMATION, FontMgr::new()).unwrap();
+
+        animation.seek_frame(0.0);
+        let (left, right) = render_and_sample(&animation);
+        assert_eq!(left, Color::RED);
+        assert_eq!(right, Color::TRANSPARENT);
+
+        animation.seek_frame(30.0);
+        let (left, right) = render_and_sample(&animation);
+        assert_eq!(left, Color::TRANSPARENT);
+        assert_eq!(right, Color::RED);
+    }
+
+    #[test]
+    fn render_loaded_animation() {
+        let animation = Animation::from_str(ANIMATION, FontMgr::new()).unwrap();
+        let (left, right) = render_and_sample(&animation);
+        assert_eq!(left, Color::RED);
+        assert_eq!(right, Color::TRANSPARENT);
+    }
+
+    fn render_and_sample(animation: &Animation) -> (Color, Color) {
+        let mut surface = surfaces::raster_n32_premul((200, 200)).unwrap();
+        animation.render(surface.canvas(), Rect::from_wh(200.0, 200.0));
+        let pixmap = surface.peek_pixels().unwrap();
+        (pixmap.get_color((10, 60)), pixmap.get_color((190, 60)))
+    }
+}
@@ -9,7 +9,9 @@ mod image;
 mod mask;
 mod node;
 mod node_hierarchy;
+mod open_type_decoder;
 mod pattern;
+mod render_context;
 mod shape;
 mod stop;
 mod svg_;
@@ -29,26 +31,31 @@ pub use self::{
     mask::Mask,
     node::*,
     node_hierarchy::*,
+    open_type_decoder::OpenTypeDecoder,
     r#use::Use,
+    render_context::RenderContext,
     shape::*,
     stop::Stop,
     svg_::*,
-    text::{TSpan, Text, TextLiteral, TextPath},
+    text::{TSpan, Text, TextLiteral, TextPath, TextRun},
     transformable_node::TransformableNode,
     types::*,
 };
 
 use std::{
+    collections::HashMap,
     error::Error,
+    ffi::{c_void, CString},
     fmt,
     io::{self},
 };
 
 use super::resources::NativeResourceProvider;
 use crate::{
+    interop,
     interop::{MemoryStream, NativeStreamBase, RustStream},
     prelude::*,
-    Canvas, Size,
+    Canvas, Matrix, Point, Size,
 };
 use skia_bindings::{self as sb, SkRefCntBase};
 
@@ -150,6 +157,51 @@ impl Dom {
         Self::from_ptr(out).ok_or(LoadError)
     }
 
+    /// Creates a `Dom` like [`Self::from_bytes()`], and returns the attributes of its elements as
+    /// they are written in `svg`.
+    ///
+    /// This parses `svg` a second time to read the attributes.
+    pub fn from_bytes_with_attributes(
+        svg: &[u8],
+        resource_provider: impl Into<NativeResourceProvider>,
+    ) -> Result<(Self, RawAttributes), LoadError> {
+        let dom = Self::from_bytes(svg, resource_provider)?;
+        let mut attributes = RawAttributes::default();
+        let mut ms = MemoryStream::from_bytes(svg);
+        unsafe {
+            sb::C_SkSVGDOM_visitAttributes(
+                dom.native(),
+                ms.native_mut().as_stream_mut(),
+                &mut attributes as *mut _ as *mut c_void,
+                Some(visit_element),
+            )
+        };
+        return Ok((dom, attributes));
+
+        extern "C" fn visit_element(
+            context: *mut c_void,
+            node: *mut sb::SkSVGNode,
+            names: *const sb::SkString,
+            values: *const sb::SkString,
+            count: usize,
+        ) {
+            let attributes = unsafe { &mut *(context as *mut RawAttributes) };
+            let names = unsafe { safer::from_raw_parts(names, count) };
+            let values = unsafe { safer::from_raw_parts(values, count) };
+            let node_attributes = names
+                .iter()
+                .zip(values)
+                .map(|(name, value)| {
+                    (
+                        interop::String::from_native_ref(name).as_str().into(),
+                        interop::String::from_native_ref(value).as_str().into(),
+                    )
+                })
+                .collect();
+            attributes.0.insert(node as *const _, node_attributes);
+        }
+    }
+
     pub fn root(&self) -> Svg {
         unsafe {
             Svg::from_unshared_ptr(sb::C_SkSVGDOM_getRoot(self.native()) as *mut _)
@@ -157,6 +209,60 @@ impl Dom {
         }
     }
 
+    /// Returns the node with the given `id`.
+    ///
+    /// Note that ids are only known for nodes that were parsed from the SVG document, not for
+    /// nodes that were added later on.
+    pub fn find_node_by_id(&self, id: impl AsRef<str>) -> Option<Node> {
+        let id = CString::new(id.as_ref()).ok()?;
+        Node::from_ptr(unsafe { sb::C_SkSVGDOM_findNodeById(self.native(), id.as_ptr()) })
+    }
+
+    /// Returns all nodes of the document in depth-first order, starting with the root.
+    ///
+    /// The nodes are visited lazily, changes to the children of nodes that were not returned yet
+    /// are reflected. The children of text nodes are not visited.
+    pub fn nodes(&self) -> impl Iterator<Item = ResolvedNode> {
+        NodeIter::from_ptr(unsafe { sb::C_RustSVGNodeIter_new(self.native()) }).unwrap()
+    }
+
+    /// Shapes `text` with the fonts and the text shaper of this document, and returns the
+    /// resulting glyph runs in logical order.
+    ///
+    /// Returns no runs if `text` is not part of this document, or if it is not drawn, for example
+    /// because it has neither a fill nor a stroke.
+    pub fn shape_text(&self, text: &Text) -> Vec<TextRun> {
+        let mut runs: Vec<TextRun> = Vec::new();
+        unsafe {
+            sb::C_RustSVGRenderContext_shapeText(
+                self.render_context().native(),
+                text.native(),
+                &mut runs as *mut _ as *mut c_void,
+                Some(visit_run),
+            )
+        };
+        return runs;
+
+        extern "C" fn visit_run(
+            context: *mut c_void,
+            font: *const sb::SkFont,
+            glyphs: *const GlyphId,
+            xforms: *const sb::SkRSXform,
+            count: usize,
+            transform: *const sb::SkMatrix,
+        ) {
+            let runs = unsafe { &mut *(context as *mut Vec<TextRun>) };
+            runs.push(unsafe {
+                TextRun {
+                    font: Font::from_native_ref(&*font).clone(),
+                    glyphs: safer::from_raw_parts(glyphs, count).to_vec(),
+                    xforms: safer::from_raw_parts(RSXform::from_native_ptr(xforms), count).to_vec(),
+                    transform: *Matrix::from_native_ref(&*transform),
+                }
+            });
+        }
+    }
+
     pub fn render(&self, canvas: &Canvas) {
         // TODO: may be we should init ICU whenever we expose a Canvas?
         #[cfg(all(feature = "embed-icudtl", feature = "textlayout"))]
@@ -165,12 +271,127 @@ impl Dom {
         unsafe { sb::SkSVGDOM::render(self.native() as &_, canvas.native_mut()) }
     }
 
+    /// Renders only the node with the given `id` and its children.
+    ///
+    /// Returns `false` if there is no node with that id. Note that ids are only known for nodes
+    /// that were parsed from the SVG document, not for nodes that were added later on.
+    pub fn render_node(&self, canvas: &Canvas, id: impl AsRef<str>) -> bool {
+        #[cfg(all(feature = "embed-icudtl", feature = "textlayout"))]
+        crate::icu::init();
+
+        let Ok(id) = CString::new(id.as_ref()) else {
+            return false;
+        };
+        unsafe { sb::C_SkSVGDOM_renderNode(self.native(), canvas.native_mut(), id.as_ptr()) }
+    }
+
+    /// Returns the object bounding box of `node` in document coordinates, which are the
+    /// coordinates [`Self::render()`] draws in.
+    ///
+    /// The bounds of a container are the union of the bounds of its rendered descendants. Returns
+    /// `None` if `node` is not rendered as part of this document, for example because it is not
+    /// in the tree, or is the child of a `defs` element.
+    pub fn object_bounding_box(&self, node: &Node) -> Option<crate::Rect> {
+        let mut bounds = crate::Rect::default();
+        unsafe {
+            sb::C_RustSVGRenderContext_objectBoundingBox(
+                self.render_context().native(),
+                node.native(),
+                bounds.native_mut(),
+            )
+        }
+        .then_some(bounds)
+    }
+
+    /// Returns the topmost shape or `use` element whose geometry contains `point`, which is in
+    /// document coordinates.
+    ///
+    /// Only the fill area of the geometry returned by [`Node::as_path()`] is considered. Strokes,
+    /// and nodes without geometry like images, can not be hit.
+    pub fn hit_test(&self, point: impl Into<Point>) -> Option<Node> {
+        let point = point.into();
+        Node::from_ptr(unsafe {
+            sb::C_RustSVGRenderContext_hitTest(self.render_context().native(), point.native())
+        })
+    }
+
+    /// Creates a [`RenderContext`] for geometry queries on the nodes of this document.
+    pub fn render_context(&self) -> RenderContext {
+        RenderContext::new(self)
+    }
+
     pub fn set_container_size(&mut self, size: impl Into<Size>) {
         let size = size.into();
         unsafe { sb::C_SkSVGDOM_setContainerSize(self.native_mut(), size.native()) }
     }
 }
 
+/// The attributes of the elements of an SVG document as they are written in the document,
+/// including the ones Skia does not support, like `class` or `data-*` attributes.
+///
+/// Returned by [`Dom::from_bytes_with_attributes()`]. The attributes are a snapshot taken when the
+/// document was parsed: changes to the nodes are not reflected, and nodes that were added later on
+/// have no attributes.
+///
+/// The snapshot does not keep the nodes alive, so only nodes that are still part of the document
+/// should be looked up: a node that is created after another one was dropped may take its place.
+#[derive(Clone, Debug, Default)]
+pub struct RawAttributes(HashMap<*const sb::SkSVGNode, Vec<(String, String)>>);
+
+impl RawAttributes {
+    /// Returns the attributes of `node` in the order they are written in the document.
+    pub fn attributes(&self, node: &Node) -> &[(String, String)] {
+        self.0
+            .get(&(node.native() as *const _))
+            .map(|attributes| attributes.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Returns the value of the attribute `name` of `node`.
+    pub fn attribute(&self, node: &Node, name: impl AsRef<str>) -> Option<&str> {
+        let name = name.as_ref();
+        self.attributes(node)
+            .iter()
+            .find_map(|(n, value)| (n == name).then_some(value.as_str()))
+    }
+}
+
+type NodeIter = RefHandle<sb::RustSVGNodeIter>;
+
+impl NativeDrop for sb::RustSVGNodeIter {
+    fn drop(&mut self) {
+        unsafe { sb::C_RustSVGNodeIter_delete(self) }
+    }
+}
+
+impl Iterator for NodeIter {
+    type Item = ResolvedNode;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut transform = Matrix::default();
+        let mut depth = 0;
+        let node = Node::from_ptr(unsafe {
+            sb::C_RustSVGNodeIter_next(self.native_mut(), transform.native_mut(), &mut depth)
+        })?;
+        Some(ResolvedNode {
+            node,
+            transform,
+            depth,
+        })
+    }
+}
+
+/// A node of a [`Dom`], returned by [`Dom::nodes()`].
+#[derive(Clone, Debug)]
+pub struct ResolvedNode {
+    pub node: Node,
+    /// Maps the coordinates of the node to document coordinates. This includes the transforms
+    /// of the node and of all its ancestors, and the view box mapping of `svg` elements.
+    pub transform: Matrix,
+    /// The depth of the node in the tree. The root has depth `0`.
+    pub depth: usize,
+}
+
 // TODO: Prelude candidate.
 #[macro_export]
 macro_rules! impl_default_make {
@@ -191,8 +412,8 @@ mod tests {
         prelude::{NativeAccess, NativeRefCounted},
         resources::NativeResourceProvider,
         surfaces,
-        svg::{Length, LengthUnit},
-        FontMgr, Surface,
+        svg::{Length, LengthUnit, NodeTag, Paint, Rect, Text, TextLiteral, TypedNode},
+        Color, FontMgr, Surface,
     };
 
     #[test]
@@ -308,6 +529,323 @@ mod tests {
         save_to_tmp(&mut surface, "svg-with-base64-image2");
     }
 
+    #[test]
+    fn build_and_edit_a_dom() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="10"/>"#;
+        let dom = Dom::from_str(svg, FontMgr::new_empty()).unwrap();
+        let mut root = dom.root();
+
+        let mut left = Rect::default();
+        assert!(left.set_attribute("width", "10"));
+        assert!(left.set_attribute("height", "10"));
+        assert!(left.set_attribute("fill", "red"));
+        assert!(!left.set_attribute("fill", "not a color"));
+        assert!(!left.set_attribute("unknown", "10"));
+        root.append_child(left);
+
+        let mut right = Rect::default();
+        right.set_x(Length::new(10., LengthUnit::Number));
+        right.set_width(Length::new(10., LengthUnit::Number));
+        right.set_height(Length::new(10., LengthUnit::Number));
+        right.set_fill(Paint::from_color(Color::BLUE));
+        root.insert_child(0, right);
+        assert_eq!(root.children_typed().len(), 2);
+
+        let pixel = |x, y| {
+            let mut surface = surfaces::raster_n32_premul((20, 10)).unwrap();
+            dom.render(surface.canvas());
+            surface.peek_pixels().unwrap().get_color((x, y))
+        };
+        assert_eq!(pixel(5, 5), Color::RED);
+        assert_eq!(pixel(15, 5), Color::BLUE);
+
+        let mut root = dom.root();
+        let children = root.children();
+        root.remove_child(1);
+        // Returned children stay valid when the container changes.
+        assert_eq!(children.len(), 2);
+        assert_eq!(children[1].tag(), NodeTag::Rect);
+        assert_eq!(pixel(5, 5), Color::TRANSPARENT);
+        assert_eq!(pixel(15, 5), Color::BLUE);
+
+        root.clear_children();
+        assert!(root.children().is_empty());
+        assert_eq!(pixel(15, 5), Color::TRANSPARENT);
+    }
+
+    #[test]
+    fn render_node_by_id() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="10">
+            <rect id="left" width="10" height="10" fill="red"/>
+            <rect id="right" x="10" width="10" height="10" fill="blue"/>
+            </svg>"#;
+        let dom = Dom::from_str(svg, FontMgr::new_empty()).unwrap();
+        let mut surface = surfaces::raster_n32_premul((20, 10)).unwrap();
+        assert!(dom.render_node(surface.canvas(), "right"));
+        assert!(!dom.render_node(surface.canvas(), "missing"));
+        let pixmap = surface.peek_pixels().unwrap();
+        assert_eq!(pixmap.get_color((5, 5)), Color::TRANSPARENT);
+        assert_eq!(pixmap.get_color((15, 5)), Color::BLUE);
+    }
+
+    #[test]
+    fn geometry_queries() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="200" height="100" viewBox="0 0 100 50">
+            <defs><rect id="hidden" width="100" height="50"/></defs>
+            <rect width="100" height="50" fill="white"/>
+            <g transform="translate(10, 10)">
+                <circle id="circle" cx="10" cy="10" r="10"/>
+                <rect id="square" x="15" width="20" height="20"/>
+            </g>
+            </svg>"#;
+        let dom = Dom::from_str(svg, FontMgr::new_empty()).unwrap();
+        let root = dom.root();
+        // Whitespace between elements may be kept as text literals.
+        let children: Vec<_> = root
+            .children()
+            .into_iter()
+            .filter(|node| node.tag() != NodeTag::TextLiteral)
+            .collect();
+        let TypedNode::Defs(defs) = children[0].clone().typed() else {
+            panic!("expected defs");
+        };
+        let background = &children[1];
+        let TypedNode::G(group) = children[2].clone().typed() else {
+            panic!("expected a group");
+        };
+        let group_children: Vec<_> = group
+            .children()
+            .into_iter()
+            .filter(|node| node.tag() != NodeTag::TextLiteral)
+            .collect();
+        let circle = &group_children[0];
+        let square = &group_children[1];
+
+        let ctx = dom.render_context();
+        let path = circle.as_path(&ctx).unwrap();
+        assert_eq!(path.bounds(), &crate::Rect::new(0., 0., 20., 20.));
+        assert_eq!(
+            square.object_bounding_box(&ctx),
+            crate::Rect::new(15., 0., 35., 20.)
+        );
+
+        // Document coordinates include the view box scale and the group's transform.
+        assert_eq!(
+            dom.object_bounding_box(circle),
+            Some(crate::Rect::new(20., 20., 60., 60.))
+        );
+        assert_eq!(
+            dom.object_bounding_box(&group),
+            Some(crate::Rect::new(20., 20., 90., 60.))
+        );
+        let hidden = defs
+            .children()
+            .into_iter()
+            .find(|node| node.tag() == NodeTag::Rect)
+            .unwrap();
+        assert_eq!(dom.object_bounding_box(&hidden), None);
+
+        let hit = |x: f32, y: f32| dom.hit_test((x, y)).map(|node| node.native() as *const _);
+        assert_eq!(hit(40., 40.), Some(circle.native() as *const _));
+        assert_eq!(hit(80., 30.), Some(square.native() as *const _));
+        assert_eq!(hit(150., 80.), Some(background.native() as *const _));
+        assert_eq!(hit(250., 80.), None);
+    }
+
+    #[test]
+    fn geometry_queries_resolve_references() {
+        let svg = r##"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" width="100" height="100">
+            <defs><rect id="box" x="50" y="50" width="10" height="10"/></defs>
+            <use id="copy" xlink:href="#box"/>
+            </svg>"##;
+        let dom = Dom::from_str(svg, FontMgr::new_empty()).unwrap();
+        let copy = dom.find_node_by_id("copy").unwrap();
+        assert_eq!(
+            dom.object_bounding_box(&copy),
+            Some(crate::Rect::new(50., 50., 60., 60.))
+        );
+        let hit = dom.hit_test((55., 55.)).unwrap();
+        assert_eq!(hit.native() as *const _, copy.native() as *const _);
+    }
+
+    #[test]
+    fn find_nodes_and_read_raw_attributes() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="200" height="100" viewBox="0 0 100 50">
+            <title>Template</title>
+            <g transform="translate(10, 20)" class="slots">
+                <rect id="photo-slot" class="slot" data-kind="photo" width="20" height="10"/>
+                <text x="5" y="5">Caption</text>
+            </g>
+            </svg>"#;
+        let (dom, attributes) =
+            Dom::from_bytes_with_attributes(svg.as_bytes(), FontMgr::new_empty()).unwrap();
+
+        let slot = dom.find_node_by_id("photo-slot").unwrap();
+        assert_eq!(slot.tag(), NodeTag::Rect);
+        assert!(dom.find_node_by_id("missing").is_none());
+
+        assert_eq!(attributes.attribute(&slot, "data-kind"), Some("photo"));
+        assert_eq!(attributes.attribute(&slot, "class"), Some("slot"));
+        assert_eq!(attributes.attribute(&slot, "fill"), None);
+        assert_eq!(attributes.attributes(&slot).len(), 5);
+        assert!(attributes.attributes(&Rect::default()).is_empty());
+
+        // The children of text nodes are not visited.
+        let nodes: Vec<_> = dom.nodes().collect();
+        let tags: Vec<_> = nodes.iter().map(|n| (n.node.tag(), n.depth)).collect();
+        assert_eq!(
+            tags,
+            [
+                (NodeTag::Svg, 0),
+                (NodeTag::G, 1),
+                (NodeTag::Rect, 2),
+                (NodeTag::Text, 2)
+            ]
+        );
+        assert_eq!(attributes.attribute(&nodes[1].node, "class"), Some("slots"));
+        assert_eq!(attributes.attribute(&nodes[3].node, "x"), Some("5"));
+
+        let slot = &nodes[2];
+        assert_eq!(
+            slot.transform.map_point((0., 0.)),
+            crate::Point::new(20., 40.)
+        );
+        assert_eq!(
+            slot.transform.map_point((20., 10.)),
+            crate::Point::new(60., 60.)
+        );
+    }
+
+    #[test]
+    fn raw_attributes_skip_unsupported_elements() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" xmlns:svg="http://www.w3.org/2000/svg">
+            <g class="group">
+                <rect class="first" width="10" height="10"/>
+                <unknown class="unknown"><rect class="in-unknown"/></unknown>
+                <svg:rect class="prefixed"/>
+                <a class="link"><circle class="in-link" r="5"/></a>
+                <ellipse class="last" rx="5" ry="5"/>
+            </g>
+            <filter id="filter">
+                <rect class="dropped"/>
+                <feFlood class="flood"/>
+            </filter>
+            </svg>"#;
+        let (dom, attributes) =
+            Dom::from_bytes_with_attributes(svg.as_bytes(), FontMgr::new_empty()).unwrap();
+
+        let nodes: Vec<_> = dom.nodes().map(|n| n.node).collect();
+        let classes: Vec<_> = nodes
+            .iter()
+            .map(|node| (node.tag(), attributes.attribute(node, "class")))
+            .collect();
+        // The rect in the filter is dropped by Skia, so the elements after it can not be paired
+        // with nodes.
+        assert_eq!(
+            classes,
+            [
+                (NodeTag::Svg, None),
+                (NodeTag::G, Some("group")),
+                (NodeTag::Rect, Some("first")),
+                (NodeTag::G, Some("link")),
+                (NodeTag::Circle, Some("in-link")),
+                (NodeTag::Ellipse, Some("last")),
+                (NodeTag::Filter, None),
+                (NodeTag::FeFlood, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn extract_shape_and_edit_text() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="200" height="100">
+            <text id="title" x="10" y="50" font-size="20">Hello <tspan font-size="40">big</tspan> world</text>
+            </svg>"#;
+        let dom = Dom::from_str(svg, FontMgr::new()).unwrap();
+        let TypedNode::Text(text) = dom.find_node_by_id("title").unwrap().typed() else {
+            panic!("expected text");
+        };
+        assert_eq!(text.text_content(), "Hello big world");
+        assert_eq!(Text::default().text_content(), "");
+
+        let runs = dom.shape_text(&text);
+        assert!(runs.iter().any(|run| run.font.size() == 20.));
+        assert!(runs.iter().any(|run| run.font.size() == 40.));
+        let first = &runs[0];
+        assert_eq!(first.glyphs.len(), first.xforms.len());
+        assert_eq!((first.xforms[0].tx, first.xforms[0].ty), (10., 50.));
+        assert!(dom.shape_text(&Text::default()).is_empty());
+
+        let mut literal = TextLiteral::default();
+        literal.set_text("Hallo Welt");
+        let mut edited = Text::default();
+        assert!(edited.set_attribute("x", "10"));
+        assert!(edited.set_attribute("y", "80"));
+        assert!(edited.set_attribute("font-size", "20"));
+        edited.append_child(literal);
+        assert_eq!(edited.text_content(), "Hallo Welt");
+        dom.root().append_child(edited.clone());
+
+        let runs = dom.shape_text(&edited);
+        assert!(runs.iter().all(|run| run.font.size() == 20.));
+        assert!(runs.iter().all(|run| run.glyphs.len() == run.xforms.len()));
+        let glyphs: usize = runs.iter().map(|run| run.glyphs.len()).sum();
+        assert!(glyphs > 0);
+        let xforms: Vec<_> = runs.iter().flat_map(|run| &run.xforms).collect();
+        assert_eq!((xforms[0].tx, xforms[0].ty), (10., 80.));
+        assert!(xforms.last().unwrap().tx > xforms[0].tx);
+    }
+
+    #[test]
+    fn build_image_filters_from_filter_elements() {
+        let svg = r##"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100">
+            <defs>
+                <filter id="shift" filterUnits="userSpaceOnUse" x="0" y="0" width="100" height="100">
+                    <feOffset dx="10" dy="0"/>
+                </filter>
+                <filter id="glow">
+                    <feFlood flood-color="#00ff00"/>
+                </filter>
+                <filter id="empty"/>
+            </defs>
+            </svg>"##;
+        let dom = Dom::from_str(svg, FontMgr::new_empty()).unwrap();
+        let ctx = dom.render_context();
+        let filter = |id| {
+            let TypedNode::Filter(filter) = dom.find_node_by_id(id).unwrap().typed() else {
+                panic!("expected filter");
+            };
+            filter
+        };
+        let draw = |image_filter, rect: crate::Rect| {
+            let mut surface = surfaces::raster_n32_premul((100, 100)).unwrap();
+            surface.canvas().clear(Color::WHITE);
+            let mut paint = crate::Paint::default();
+            paint.set_color(Color::BLUE).set_image_filter(image_filter);
+            surface.canvas().draw_rect(rect, &paint);
+            surface
+        };
+
+        let shift = filter("shift").build_image_filter(&ctx).unwrap();
+        let mut surface = draw(shift, crate::Rect::new(0., 0., 10., 10.));
+        let pixmap = surface.peek_pixels().unwrap();
+        assert_eq!(pixmap.get_color((15, 5)), Color::BLUE);
+        assert_eq!(pixmap.get_color((5, 5)), Color::WHITE);
+
+        // The filter region of `glow` extends 10% beyond the bounds of the content.
+        let bounds = crate::Rect::new(20., 20., 60., 60.);
+        let glow = filter("glow")
+            .build_image_filter_for_bounds(&ctx, bounds)
+            .unwrap();
+        let mut surface = draw(glow, bounds);
+        let pixmap = surface.peek_pixels().unwrap();
+        assert_eq!(pixmap.get_color((17, 17)), Color::GREEN);
+        assert_eq!(pixmap.get_color((40, 40)), Color::GREEN);
+        assert_eq!(pixmap.get_color((10, 10)), Color::WHITE);
+
+        assert!(filter("empty").build_image_filter(&ctx).is_none());
+    }
+
     #[cfg(feature = "save-svg-images")]
     fn save_to_tmp(surface: &mut Surface, name: &str) {
         use crate::EncodedImageFormat;
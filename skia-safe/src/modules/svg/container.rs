@@ -22,19 +22,56 @@ impl Container {
         unsafe { sb::C_SkSVGContainer_appendChild(self.native_mut(), node.into().into_ptr()) }
     }
 
-    pub fn children(&self) -> &[Node] {
+    /// Inserts `node` as the child at `index`, shifting all children after it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > children().len()`.
+    pub fn insert_child(&mut self, index: usize, node: impl Into<Node>) {
+        assert!(index <= self.children_count());
+        unsafe {
+            sb::C_SkSVGContainer_insertChild(
+                self.native_mut(),
+                index.try_into().unwrap(),
+                node.into().into_ptr(),
+            )
+        }
+    }
+
+    /// Removes and returns the child at `index`, shifting all children after it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index >= children().len()`.
+    pub fn remove_child(&mut self, index: usize) -> Node {
+        assert!(index < self.children_count());
+        Node::from_ptr(unsafe {
+            sb::C_SkSVGContainer_removeChild(self.native_mut(), index.try_into().unwrap())
+        })
+        .unwrap()
+    }
+
+    pub fn clear_children(&mut self) {
+        unsafe { sb::C_SkSVGContainer_clearChildren(self.native_mut()) }
+    }
+
+    /// Returns the current children.
+    ///
+    /// The children are returned as new references, because other handles to this container may
+    /// change its children while they are in use.
+    pub fn children(&self) -> Vec<Node> {
         unsafe {
             let sp_slice = safer::from_raw_parts(
                 sb::C_SkSVGContainer_children(self.native()),
                 self.children_count(),
             );
 
-            RCHandle::from_non_null_sp_slice(sp_slice)
+            RCHandle::from_non_null_sp_slice(sp_slice).to_vec()
         }
     }
 
     pub fn children_typed(&self) -> Vec<TypedNode> {
-        self.children().iter().cloned().map(|n| n.typed()).collect()
+        self.children().into_iter().map(|n| n.typed()).collect()
     }
 
     pub(crate) fn children_count(&self) -> usize {
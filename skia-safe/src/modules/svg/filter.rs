@@ -1,6 +1,7 @@
-use super::{BoundingBoxUnits, DebugAttributes, Length, NodeSubtype};
-use crate::{impl_default_make, prelude::*};
+use super::{BoundingBoxUnits, DebugAttributes, Length, NodeSubtype, RenderContext};
+use crate::{impl_default_make, prelude::*, ImageFilter, Rect as SkRect};
 use skia_bindings as sb;
+use std::ptr;
 
 pub type Filter = RCHandle<sb::SkSVGFilter>;
 
@@ -28,7 +29,40 @@ impl_default_make!(Filter, sb::C_SkSVGFilter_Make);
 
 impl Filter {
     // TODO: wrap applyProperties()
-    // TODO: wrap buildFilterDAG
+
+    /// Builds the [`ImageFilter`] the filter effects of this `filter` element describe, so that it
+    /// can be used with any [`crate::Paint`].
+    ///
+    /// Lengths in object bounding box units are resolved against the unit square. Use
+    /// [`Self::build_image_filter_for_bounds()`] when the filter region or the primitives are
+    /// specified relative to the bounding box of the filtered content, which is the default for
+    /// `filterUnits`.
+    ///
+    /// Returns `None` if the element does not contain any filter effects.
+    pub fn build_image_filter(&self, context: &RenderContext) -> Option<ImageFilter> {
+        ImageFilter::from_ptr(unsafe {
+            sb::C_SkSVGFilter_buildFilterDAG(self.native(), context.native(), ptr::null())
+        })
+    }
+
+    /// Builds the [`ImageFilter`] of this `filter` element for content with the bounding box
+    /// `bounds`.
+    ///
+    /// The filter region and the primitive subregions are resolved against `bounds`, which must be
+    /// in the coordinate space the content is drawn in.
+    pub fn build_image_filter_for_bounds(
+        &self,
+        context: &RenderContext,
+        bounds: impl AsRef<SkRect>,
+    ) -> Option<ImageFilter> {
+        ImageFilter::from_ptr(unsafe {
+            sb::C_SkSVGFilter_buildFilterDAG(
+                self.native(),
+                context.native(),
+                bounds.as_ref().native(),
+            )
+        })
+    }
 
     skia_svg_macros::attrs! {
         SkSVGFilter => {
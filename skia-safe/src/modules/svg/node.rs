@@ -2,10 +2,11 @@ use super::{
     fe, pattern::Pattern, svg_::Svg, Circle, ClipPath, ColorSpace, DebugAttributes, Defs, Display,
     Ellipse, Fill, FillRule, Filter, FontFamily, FontSize, FontStyle, FontWeight, Image, IriFunc,
     Length, Line, LineCap, LineJoin, LinearGradient, Mask, Paint, Path, Poly, RadialGradient, Rect,
-    Stop, TSpan, Text, TextAnchor, TextLiteral, TextPath, Use, Visibility, G,
+    RenderContext, Stop, TSpan, Text, TextAnchor, TextLiteral, TextPath, Use, Visibility, G,
 };
-use crate::{prelude::*, scalar, Color};
+use crate::{prelude::*, scalar, Color, Path as SkPath, Rect as SkRect};
 use skia_bindings as sb;
+use std::ffi::CString;
 
 pub type NodeTag = sb::SkSVGTag;
 
@@ -66,10 +67,46 @@ impl Node {
         unsafe { sb::C_SkSVGNode_tag(self.native()) }
     }
 
-    // TODO: wrap appendChild()
-    // TODO: wrap render(), asPaint(), asPath(), objectBoundingBox()
-    // TODO: wrap setAttribute().
-    // TODO: wrap parseAndSetAttribute()
+    /// Appends `node` as the last child. Has no effect if this node can not contain children.
+    pub fn append_child(&mut self, node: impl Into<Node>) {
+        unsafe { sb::C_SkSVGNode_appendChild(self.native_mut(), node.into().into_ptr()) }
+    }
+
+    /// Parses `value` and sets the attribute `name`, as if it were specified in the SVG document.
+    ///
+    /// Returns `false` if the node does not support the attribute or the value could not be
+    /// parsed. Typed values can be set with the attribute setters, like [`Self::set_fill()`].
+    pub fn set_attribute(&mut self, name: impl AsRef<str>, value: impl AsRef<str>) -> bool {
+        let Ok(name) = CString::new(name.as_ref()) else {
+            return false;
+        };
+        let Ok(value) = CString::new(value.as_ref()) else {
+            return false;
+        };
+        unsafe {
+            sb::C_SkSVGNode_parseAndSetAttribute(self.native_mut(), name.as_ptr(), value.as_ptr())
+        }
+    }
+
+    /// Returns the geometry of this node in the coordinates of its parent, clipped by its
+    /// `clip-path`, or `None` if the node has no geometry or is not displayed.
+    pub fn as_path(&self, context: &RenderContext) -> Option<SkPath> {
+        let mut path = SkPath::default();
+        unsafe { sb::C_SkSVGNode_asPath(self.native(), context.native(), path.native_mut()) };
+        (!path.is_empty()).then_some(path)
+    }
+
+    /// Returns the bounding box of this node's geometry in its own coordinates, that is, before
+    /// its `transform` is applied.
+    pub fn object_bounding_box(&self, context: &RenderContext) -> SkRect {
+        let mut rect = SkRect::default();
+        unsafe {
+            sb::C_SkSVGNode_objectBoundingBox(self.native(), context.native(), rect.native_mut())
+        };
+        rect
+    }
+
+    // TODO: wrap render(), asPaint()
 
     pub fn typed(self) -> TypedNode {
         TypedNode::from_ptr(self.into_ptr())
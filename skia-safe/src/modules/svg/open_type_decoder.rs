@@ -0,0 +1,89 @@
+use std::fmt;
+
+use crate::{prelude::*, Canvas, Color, GlyphId};
+use skia_bindings as sb;
+
+/// Skia's [`crate::OpenTypeSVGDecoder`], which renders the glyphs of an OpenType-SVG document with
+/// an SVG DOM.
+///
+/// The glyph with the id `n` is the element with the id `glyph{n}`. Only `data:` URLs are
+/// resolved.
+///
+/// See [`crate::graphics::set_default_open_type_svg_decoder()`].
+pub type OpenTypeDecoder = RefHandle<sb::SkOpenTypeSVGDecoder>;
+
+// The decoder owns its DOM, which is never shared.
+unsafe impl Send for OpenTypeDecoder {}
+
+impl NativeDrop for sb::SkOpenTypeSVGDecoder {
+    fn drop(&mut self) {
+        unsafe { sb::C_SkOpenTypeSVGDecoder_delete(self) }
+    }
+}
+
+impl fmt::Debug for OpenTypeDecoder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OpenTypeDecoder").finish()
+    }
+}
+
+impl OpenTypeDecoder {
+    /// Parses the SVG document of a font. Returns `None` if the document can not be parsed.
+    pub fn new(svg: &[u8]) -> Option<Self> {
+        Self::from_ptr(unsafe { sb::C_SkSVGOpenTypeSVGDecoder_Make(svg.as_ptr(), svg.len()) })
+    }
+}
+
+impl crate::OpenTypeSVGDecoder for OpenTypeDecoder {
+    fn approximate_size(&self) -> usize {
+        unsafe { sb::C_SkOpenTypeSVGDecoder_approximateSize(self.native_mut_force()) }
+    }
+
+    fn render(
+        &mut self,
+        canvas: &Canvas,
+        upem: i32,
+        glyph_id: GlyphId,
+        foreground_color: Color,
+        palette: &[Color],
+    ) -> bool {
+        unsafe {
+            sb::C_SkOpenTypeSVGDecoder_render(
+                self.native_mut(),
+                canvas.native_mut(),
+                upem,
+                glyph_id,
+                foreground_color.into_native(),
+                palette.native().as_ptr(),
+                palette.len(),
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OpenTypeDecoder;
+    use crate::{surfaces, Color, OpenTypeSVGDecoder};
+
+    #[test]
+    fn renders_glyph_with_foreground_color() {
+        // OpenType-SVG glyphs are drawn above the baseline at negative y coordinates.
+        let svg = br#"<svg xmlns="http://www.w3.org/2000/svg">
+            <g id="glyph3"><rect x="0" y="-10" width="10" height="10" fill="currentColor"/></g>
+            <g id="glyph4"><rect x="0" y="-10" width="10" height="10" fill="blue"/></g>
+            </svg>"#;
+        let mut decoder = OpenTypeDecoder::new(svg).unwrap();
+        assert!(decoder.approximate_size() > 0);
+
+        let mut surface = surfaces::raster_n32_premul((10, 10)).unwrap();
+        let canvas = surface.canvas();
+        canvas.clear(Color::WHITE);
+        canvas.translate((0.0, 10.0));
+        assert!(decoder.render(canvas, 1000, 3, Color::RED, &[]));
+        assert!(!decoder.render(canvas, 1000, 5, Color::RED, &[]));
+
+        let pixmap = surface.peek_pixels().unwrap();
+        assert_eq!(pixmap.get_color((5, 5)), Color::RED);
+    }
+}
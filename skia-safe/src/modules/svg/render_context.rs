@@ -0,0 +1,35 @@
+use super::Dom;
+use crate::prelude::*;
+use skia_bindings as sb;
+use std::fmt;
+
+/// The context the root of a [`Dom`] is rendered in.
+///
+/// It resolves lengths relative to the container size of the [`Dom`], references like the ones
+/// of `use` elements to the nodes of the [`Dom`], and fonts with the font manager of the [`Dom`].
+///
+/// References are resolved to the nodes that have the referenced ids when the context is created.
+///
+/// A [`Dom`] that was not created by this crate, for example with [`Dom::from_ptr()`], is resolved
+/// without fonts and resources.
+pub type RenderContext = RefHandle<sb::RustSVGRenderContext>;
+
+impl NativeDrop for sb::RustSVGRenderContext {
+    fn drop(&mut self) {
+        unsafe { sb::C_RustSVGRenderContext_delete(self) }
+    }
+}
+
+impl fmt::Debug for RenderContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RenderContext").finish()
+    }
+}
+
+impl RenderContext {
+    /// Creates a render context for `dom`. Changes to the container size of `dom` after the
+    /// context was created are not reflected.
+    pub fn new(dom: &Dom) -> Self {
+        Self::from_ptr(unsafe { sb::C_RustSVGRenderContext_New(dom.native()) }).unwrap()
+    }
+}
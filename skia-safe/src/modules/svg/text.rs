@@ -1,11 +1,11 @@
 use super::{DebugAttributes, Iri, Length, NodeSubtype, XmlSpace};
-use crate::{impl_default_make, interop, prelude::*, scalar};
+use crate::{impl_default_make, interop, prelude::*, scalar, Font, GlyphId, Matrix, RSXform};
 use skia_bindings as sb;
 
 type TextContainer = RCHandle<sb::SkSVGTextContainer>;
 
 impl NodeSubtype for sb::SkSVGTextContainer {
-    type Base = sb::SkSVGContainer;
+    type Base = sb::SkSVGTransformableNode;
 }
 
 impl DebugAttributes for TextContainer {
@@ -69,6 +69,18 @@ impl TextContainer {
         }
     }
 
+    /// Returns the text of this node and of all its `tspan` and `textPath` descendants, as it
+    /// is rendered, i.e. after white space is processed according to
+    /// [`xml_space()`](Self::xml_space).
+    ///
+    /// The children of text nodes can not be enumerated, text is added with
+    /// [`append_child()`](super::Node::append_child).
+    pub fn text_content(&self) -> String {
+        let mut text = interop::String::default();
+        unsafe { sb::C_SkSVGTextContainer_textContent(self.native(), text.native_mut()) };
+        text.as_str().to_owned()
+    }
+
     skia_svg_macros::attrs! {
         SkSVGTextContainer => {
             xml_space: XmlSpace [get(value) => value, set(value) => value]
@@ -76,6 +88,20 @@ impl TextContainer {
     }
 }
 
+/// A run of glyphs that are drawn with the same font, returned by
+/// [`crate::svg::Dom::shape_text()`].
+#[derive(Clone, Debug)]
+pub struct TextRun {
+    /// The resolved font of the run.
+    pub font: Font,
+    pub glyphs: Vec<GlyphId>,
+    /// The placement of each glyph in the coordinates of the `text` element. The translation of
+    /// an xform is the position of the glyph's origin.
+    pub xforms: Vec<RSXform>,
+    /// Maps the coordinates of the `text` element to document coordinates.
+    pub transform: Matrix,
+}
+
 pub type Text = RCHandle<sb::SkSVGText>;
 
 impl NodeSubtype for sb::SkSVGText {
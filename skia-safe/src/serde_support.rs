@@ -0,0 +1,566 @@
+//! [`serde`] support.
+//!
+//! With the `serde` feature enabled, the following types implement [`Serialize`] and
+//! [`Deserialize`]:
+//!
+//! - [`Point`], [`Rect`], [`Color4f`] and [`CubicResampler`] as structs of their fields.
+//! - [`Color`] as a `"#rrggbbaa"` string. `"#rrggbb"` is read as an opaque color. Formats that
+//!   are not human-readable store the 32 bit ARGB value.
+//! - [`RRect`] as its `rect` and the `radii` of the upper-left, upper-right, lower-right and
+//!   lower-left corners.
+//! - [`Matrix`] as an array of its 9 and [`M44`] as an array of its 16 values in row-major order.
+//! - [`FontStyle`] as its `weight`, `width` and `slant`.
+//! - [`SamplingOptions`] as one of `{"filter": {"mode": .., "mipmap": ..}}`,
+//!   `{"cubic": {"b": .., "c": ..}}` or `{"anisotropic": max_aniso}`.
+//! - [`Path`] as SVG path data, see [`crate::utils::parse_path`]. The fill type is not preserved.
+//! - [`crate::textlayout::TextStyle`] and [`crate::textlayout::ParagraphStyle`] if the
+//!   `textlayout` feature is enabled, see [below](#text-and-paragraph-styles).
+//!
+//! Enums are represented by the kebab-cased names of their variants, for example
+//! `"src-over"` or `"clamp"`. Because they are defined in `skia-bindings`, they can not implement
+//! the serde traits. Use the modules of this module instead:
+//!
+//! ```
+//! use serde::{Deserialize, Serialize};
+//! use skia_safe::{serde_support, BlendMode, TileMode};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Layer {
+//!     #[serde(with = "serde_support::blend_mode")]
+//!     blend_mode: BlendMode,
+//!     #[serde(with = "serde_support::tile_mode")]
+//!     tile_mode: TileMode,
+//! }
+//! ```
+//!
+//! # Text and paragraph styles
+//!
+//! A `TextStyle` is represented by its color, decoration, font style, shadows, font features,
+//! font size and families, baseline shift, height, spacing, locale and text baseline. Foreground
+//! and background paints, the typeface and font arguments are not serialized. Missing fields
+//! are read as the values of a default constructed style.
+//!
+//! A `ParagraphStyle` is represented by its strut style, text style, alignment, direction, maximum
+//! number of lines, ellipsis, height, text height behavior, hinting, tab replacement and rounding
+//! hack settings.
+
+use std::fmt;
+
+use serde::{
+    de::{self, SeqAccess, Visitor},
+    ser::SerializeTuple,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::{
+    font_style::{Slant, Weight, Width},
+    prelude::NativeTransmutable,
+    BlendMode, Color, CubicResampler, FilterMode, FontStyle, Matrix, MipmapMode, Path, Point,
+    RRect, Rect, SamplingOptions, TileMode, M44,
+};
+
+#[cfg(feature = "textlayout")]
+mod paragraph;
+#[cfg(feature = "textlayout")]
+pub use paragraph::*;
+
+/// Defines a module with `serialize` and `deserialize` functions for an enum defined in
+/// `skia-bindings` that can be used with `#[serde(with = "..")]`.
+macro_rules! enum_module {
+    ($module:ident, $ty:ident, { $($variant:ident => $name:literal),+ $(,)? }) => {
+        #[doc = concat!("Serializes [`", stringify!($ty), "`] by the names of its variants.")]
+        pub mod $module {
+            use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+            use super::$ty;
+
+            const NAMES: &[&str] = &[$($name),+];
+
+            pub fn serialize<S: Serializer>(
+                value: &$ty,
+                serializer: S,
+            ) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(match value {
+                    $($ty::$variant => $name,)+
+                })
+            }
+
+            pub fn deserialize<'de, D: Deserializer<'de>>(
+                deserializer: D,
+            ) -> Result<$ty, D::Error> {
+                let name = String::deserialize(deserializer)?;
+                match name.as_str() {
+                    $($name => Ok($ty::$variant),)+
+                    _ => Err(D::Error::unknown_variant(&name, NAMES)),
+                }
+            }
+        }
+    };
+}
+
+pub(crate) use enum_module;
+
+enum_module!(blend_mode, BlendMode, {
+    Clear => "clear",
+    Src => "src",
+    Dst => "dst",
+    SrcOver => "src-over",
+    DstOver => "dst-over",
+    SrcIn => "src-in",
+    DstIn => "dst-in",
+    SrcOut => "src-out",
+    DstOut => "dst-out",
+    SrcATop => "src-atop",
+    DstATop => "dst-atop",
+    Xor => "xor",
+    Plus => "plus",
+    Modulate => "modulate",
+    Screen => "screen",
+    Overlay => "overlay",
+    Darken => "darken",
+    Lighten => "lighten",
+    ColorDodge => "color-dodge",
+    ColorBurn => "color-burn",
+    HardLight => "hard-light",
+    SoftLight => "soft-light",
+    Difference => "difference",
+    Exclusion => "exclusion",
+    Multiply => "multiply",
+    Hue => "hue",
+    Saturation => "saturation",
+    Color => "color",
+    Luminosity => "luminosity",
+});
+
+enum_module!(tile_mode, TileMode, {
+    Clamp => "clamp",
+    Repeat => "repeat",
+    Mirror => "mirror",
+    Decal => "decal",
+});
+
+enum_module!(filter_mode, FilterMode, {
+    Nearest => "nearest",
+    Linear => "linear",
+});
+
+enum_module!(mipmap_mode, MipmapMode, {
+    None => "none",
+    Nearest => "nearest",
+    Linear => "linear",
+});
+
+enum_module!(slant, Slant, {
+    Upright => "upright",
+    Italic => "italic",
+    Oblique => "oblique",
+});
+
+impl Serialize for Color {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(&format_args!(
+                "#{:02x}{:02x}{:02x}{:02x}",
+                self.r(),
+                self.g(),
+                self.b(),
+                self.a()
+            ))
+        } else {
+            serializer.serialize_u32(self.into_native())
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Color {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ColorVisitor;
+
+        impl Visitor<'_> for ColorVisitor {
+            type Value = Color;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a \"#rrggbb\" or \"#rrggbbaa\" color or a 32 bit ARGB value")
+            }
+
+            fn visit_u32<E: de::Error>(self, argb: u32) -> Result<Color, E> {
+                Ok(Color::new(argb))
+            }
+
+            fn visit_u64<E: de::Error>(self, argb: u64) -> Result<Color, E> {
+                u32::try_from(argb)
+                    .map(Color::new)
+                    .map_err(|_| E::invalid_value(de::Unexpected::Unsigned(argb), &self))
+            }
+
+            fn visit_str<E: de::Error>(self, s: &str) -> Result<Color, E> {
+                parse_hex_color(s).ok_or_else(|| E::invalid_value(de::Unexpected::Str(s), &self))
+            }
+        }
+
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(ColorVisitor)
+        } else {
+            deserializer.deserialize_u32(ColorVisitor)
+        }
+    }
+}
+
+fn parse_hex_color(s: &str) -> Option<Color> {
+    let hex = s.strip_prefix('#')?;
+    if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).ok();
+    let a = match hex.len() {
+        6 => 0xff,
+        8 => channel(3)?,
+        _ => return None,
+    };
+    Some(Color::from_argb(a, channel(0)?, channel(1)?, channel(2)?))
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "RRect")]
+struct RRectRepr {
+    rect: Rect,
+    radii: [Point; 4],
+}
+
+impl Serialize for RRect {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        RRectRepr {
+            rect: *self.rect(),
+            radii: *self.radii_ref(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for RRect {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = RRectRepr::deserialize(deserializer)?;
+        Ok(RRect::new_rect_radii(repr.rect, &repr.radii))
+    }
+}
+
+impl Serialize for Matrix {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut values = [0.0; 9];
+        self.get_9(&mut values);
+        serialize_array(&values, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Matrix {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let [scale_x, skew_x, trans_x, skew_y, scale_y, trans_y, pers_0, pers_1, pers_2] =
+            deserialize_array::<_, 9>(deserializer)?;
+        Ok(Matrix::new_all(
+            scale_x, skew_x, trans_x, skew_y, scale_y, trans_y, pers_0, pers_1, pers_2,
+        ))
+    }
+}
+
+impl Serialize for M44 {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut values = [0.0; 16];
+        self.get_row_major(&mut values);
+        serialize_array(&values, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for M44 {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(M44::row_major(&deserialize_array(deserializer)?))
+    }
+}
+
+fn serialize_array<S: Serializer, const N: usize>(
+    values: &[f32; N],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let mut tuple = serializer.serialize_tuple(N)?;
+    for value in values {
+        tuple.serialize_element(value)?;
+    }
+    tuple.end()
+}
+
+fn deserialize_array<'de, D: Deserializer<'de>, const N: usize>(
+    deserializer: D,
+) -> Result<[f32; N], D::Error> {
+    struct ArrayVisitor<const N: usize>;
+
+    impl<'de, const N: usize> Visitor<'de> for ArrayVisitor<N> {
+        type Value = [f32; N];
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "an array of {N} numbers")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let mut values = [0.0; N];
+            for (i, value) in values.iter_mut().enumerate() {
+                *value = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+            }
+            if seq.next_element::<de::IgnoredAny>()?.is_some() {
+                return Err(de::Error::invalid_length(N + 1, &self));
+            }
+            Ok(values)
+        }
+    }
+
+    deserializer.deserialize_tuple(N, ArrayVisitor)
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "FontStyle")]
+struct FontStyleRepr {
+    weight: i32,
+    width: i32,
+    #[serde(with = "slant")]
+    slant: Slant,
+}
+
+impl Serialize for FontStyle {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        FontStyleRepr {
+            weight: *self.weight(),
+            width: *self.width(),
+            slant: self.slant(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for FontStyle {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = FontStyleRepr::deserialize(deserializer)?;
+        Ok(FontStyle::new(
+            Weight::from(repr.weight),
+            Width::from(repr.width),
+            repr.slant,
+        ))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "SamplingOptions", rename_all = "kebab-case")]
+enum SamplingOptionsRepr {
+    Filter {
+        #[serde(with = "filter_mode")]
+        mode: FilterMode,
+        #[serde(with = "mipmap_mode")]
+        mipmap: MipmapMode,
+    },
+    Cubic(CubicResampler),
+    Anisotropic(i32),
+}
+
+impl Serialize for SamplingOptions {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let repr = if self.is_aniso() {
+            SamplingOptionsRepr::Anisotropic(self.max_aniso)
+        } else if self.use_cubic {
+            SamplingOptionsRepr::Cubic(self.cubic)
+        } else {
+            SamplingOptionsRepr::Filter {
+                mode: self.filter,
+                mipmap: self.mipmap,
+            }
+        };
+        repr.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for SamplingOptions {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match SamplingOptionsRepr::deserialize(deserializer)? {
+            SamplingOptionsRepr::Filter { mode, mipmap } => SamplingOptions::new(mode, mipmap),
+            SamplingOptionsRepr::Cubic(cubic) => cubic.into(),
+            SamplingOptionsRepr::Anisotropic(max_aniso) => SamplingOptions::from_aniso(max_aniso),
+        })
+    }
+}
+
+impl Serialize for Path {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_svg())
+    }
+}
+
+impl<'de> Deserialize<'de> for Path {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let svg = String::deserialize(deserializer)?;
+        Path::from_svg(&svg)
+            .ok_or_else(|| de::Error::invalid_value(de::Unexpected::Str(&svg), &"SVG path data"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+    use serde_json::json;
+
+    use crate::{
+        font_style::{Slant, Weight, Width},
+        BlendMode, Color, Color4f, CubicResampler, FilterMode, FontStyle, Matrix, MipmapMode, Path,
+        Point, RRect, Rect, SamplingOptions, TileMode, M44,
+    };
+
+    fn round_trip<T>(value: &T, expected: serde_json::Value) -> T
+    where
+        T: Serialize + for<'de> Deserialize<'de>,
+    {
+        let json = serde_json::to_value(value).unwrap();
+        assert_eq!(json, expected);
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn colors() {
+        let color = Color::from_argb(0x80, 0x12, 0xab, 0xff);
+        assert_eq!(round_trip(&color, json!("#12abff80")), color);
+        assert_eq!(
+            serde_json::from_value::<Color>(json!("#FF0000")).unwrap(),
+            Color::RED
+        );
+        assert!(serde_json::from_value::<Color>(json!("#ff00")).is_err());
+        assert!(serde_json::from_value::<Color>(json!("red")).is_err());
+
+        let color = Color4f::new(0.25, 0.5, 0.75, 1.0);
+        assert_eq!(
+            round_trip(&color, json!({"r": 0.25, "g": 0.5, "b": 0.75, "a": 1.0})),
+            color
+        );
+    }
+
+    #[test]
+    fn geometry() {
+        let point = Point::new(1.0, 2.0);
+        assert_eq!(round_trip(&point, json!({"x": 1.0, "y": 2.0})), point);
+
+        let rect = Rect::new(1.0, 2.0, 3.0, 4.0);
+        assert_eq!(
+            round_trip(
+                &rect,
+                json!({"left": 1.0, "top": 2.0, "right": 3.0, "bottom": 4.0})
+            ),
+            rect
+        );
+
+        let rrect = RRect::new_rect_xy(Rect::new(0.0, 0.0, 10.0, 20.0), 2.0, 3.0);
+        let corner = json!({"x": 2.0, "y": 3.0});
+        assert_eq!(
+            round_trip(
+                &rrect,
+                json!({
+                    "rect": {"left": 0.0, "top": 0.0, "right": 10.0, "bottom": 20.0},
+                    "radii": [corner, corner, corner, corner]
+                })
+            ),
+            rrect
+        );
+    }
+
+    #[test]
+    fn matrices_are_row_major() {
+        let matrix = Matrix::translate((10.0, 20.0));
+        assert_eq!(
+            round_trip(
+                &matrix,
+                json!([1.0, 0.0, 10.0, 0.0, 1.0, 20.0, 0.0, 0.0, 1.0])
+            ),
+            matrix
+        );
+        assert!(serde_json::from_value::<Matrix>(json!([1.0, 0.0])).is_err());
+
+        let m44 = M44::translate(1.0, 2.0, 3.0);
+        assert_eq!(
+            round_trip(
+                &m44,
+                json!([
+                    1.0, 0.0, 0.0, 1.0, 0.0, 1.0, 0.0, 2.0, 0.0, 0.0, 1.0, 3.0, 0.0, 0.0, 0.0, 1.0
+                ])
+            ),
+            m44
+        );
+    }
+
+    #[test]
+    fn font_style() {
+        let style = FontStyle::new(Weight::BOLD, Width::CONDENSED, Slant::Italic);
+        assert_eq!(
+            round_trip(
+                &style,
+                json!({"weight": 700, "width": 3, "slant": "italic"})
+            ),
+            style
+        );
+    }
+
+    #[test]
+    fn sampling_options() {
+        let sampling = SamplingOptions::new(FilterMode::Linear, MipmapMode::Nearest);
+        assert_eq!(
+            round_trip(
+                &sampling,
+                json!({"filter": {"mode": "linear", "mipmap": "nearest"}})
+            ),
+            sampling
+        );
+
+        let sampling = SamplingOptions::from(CubicResampler::catmull_rom());
+        assert_eq!(
+            round_trip(&sampling, json!({"cubic": {"b": 0.0, "c": 0.5}})),
+            sampling
+        );
+
+        let sampling = SamplingOptions::from_aniso(4);
+        assert_eq!(round_trip(&sampling, json!({"anisotropic": 4})), sampling);
+    }
+
+    #[test]
+    fn enums() {
+        #[derive(Serialize, Deserialize)]
+        struct Modes {
+            #[serde(with = "crate::serde_support::blend_mode")]
+            blend_mode: BlendMode,
+            #[serde(with = "crate::serde_support::tile_mode")]
+            tile_mode: TileMode,
+        }
+
+        let modes = round_trip(
+            &Modes {
+                blend_mode: BlendMode::SrcOver,
+                tile_mode: TileMode::Mirror,
+            },
+            json!({"blend_mode": "src-over", "tile_mode": "mirror"}),
+        );
+        assert_eq!(modes.blend_mode, BlendMode::SrcOver);
+        assert_eq!(modes.tile_mode, TileMode::Mirror);
+
+        assert!(serde_json::from_value::<Modes>(
+            json!({"blend_mode": "SrcOver", "tile_mode": "mirror"})
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn path_round_trips_through_svg() {
+        let mut path = Path::default();
+        path.move_to((0, 0));
+        path.line_to((100, 100));
+        path.quad_to((50, 0), (0, 100));
+        path.close();
+
+        let svg = path.to_svg();
+        let restored: Path = round_trip(&path, json!(svg));
+        assert_eq!(restored.to_svg(), svg);
+
+        assert!(serde_json::from_value::<Path>(json!("not a path")).is_err());
+    }
+}
@@ -0,0 +1,511 @@
+use serde::{
+    de::{self, SeqAccess, Visitor},
+    ser::SerializeSeq,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::fmt;
+
+use super::enum_module;
+use crate::{
+    prelude::*,
+    scalar,
+    textlayout::{
+        Decoration, ParagraphStyle, StrutStyle, TextAlign, TextBaseline, TextDecoration,
+        TextDecorationMode, TextDecorationStyle, TextDirection, TextHeightBehavior, TextShadow,
+        TextStyle,
+    },
+    Color, FontStyle, Point,
+};
+
+enum_module!(text_align, TextAlign, {
+    Left => "left",
+    Right => "right",
+    Center => "center",
+    Justify => "justify",
+    Start => "start",
+    End => "end",
+});
+
+enum_module!(text_direction, TextDirection, {
+    RTL => "rtl",
+    LTR => "ltr",
+});
+
+enum_module!(text_baseline, TextBaseline, {
+    Alphabetic => "alphabetic",
+    Ideographic => "ideographic",
+});
+
+enum_module!(text_height_behavior, TextHeightBehavior, {
+    All => "all",
+    DisableFirstAscent => "disable-first-ascent",
+    DisableLastDescent => "disable-last-descent",
+    DisableAll => "disable-all",
+});
+
+enum_module!(text_decoration_style, TextDecorationStyle, {
+    Solid => "solid",
+    Double => "double",
+    Dotted => "dotted",
+    Dashed => "dashed",
+    Wavy => "wavy",
+});
+
+enum_module!(text_decoration_mode, TextDecorationMode, {
+    Gaps => "gaps",
+    Through => "through",
+});
+
+const TEXT_DECORATIONS: [(TextDecoration, &str); 3] = [
+    (TextDecoration::UNDERLINE, "underline"),
+    (TextDecoration::OVERLINE, "overline"),
+    (TextDecoration::LINE_THROUGH, "line-through"),
+];
+
+/// Serialized as an array of the names of the decorations, for example
+/// `["underline", "line-through"]`.
+impl Serialize for TextDecoration {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let names = TEXT_DECORATIONS
+            .iter()
+            .filter(|(decoration, _)| self.contains(*decoration));
+        let mut seq = serializer.serialize_seq(None)?;
+        for (_, name) in names {
+            seq.serialize_element(name)?;
+        }
+        seq.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for TextDecoration {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct TextDecorationVisitor;
+
+        impl<'de> Visitor<'de> for TextDecorationVisitor {
+            type Value = TextDecoration;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("an array of text decoration names")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut decorations = TextDecoration::NO_DECORATION;
+                while let Some(name) = seq.next_element::<String>()? {
+                    let (decoration, _) = TEXT_DECORATIONS
+                        .iter()
+                        .find(|(_, n)| *n == name)
+                        .ok_or_else(|| {
+                            de::Error::unknown_variant(
+                                &name,
+                                &["underline", "overline", "line-through"],
+                            )
+                        })?;
+                    decorations |= *decoration;
+                }
+                Ok(decorations)
+            }
+        }
+
+        deserializer.deserialize_seq(TextDecorationVisitor)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Decoration")]
+struct DecorationRepr {
+    #[serde(rename = "type")]
+    ty: TextDecoration,
+    #[serde(with = "text_decoration_mode")]
+    mode: TextDecorationMode,
+    color: Color,
+    #[serde(with = "text_decoration_style")]
+    style: TextDecorationStyle,
+    thickness_multiplier: scalar,
+}
+
+impl Serialize for Decoration {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        DecorationRepr {
+            ty: self.ty,
+            mode: self.mode,
+            color: self.color,
+            style: self.style,
+            thickness_multiplier: self.thickness_multiplier,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Decoration {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = DecorationRepr::deserialize(deserializer)?;
+        Ok(Decoration {
+            ty: repr.ty,
+            mode: repr.mode,
+            color: repr.color,
+            style: repr.style,
+            thickness_multiplier: repr.thickness_multiplier,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "TextShadow")]
+struct TextShadowRepr {
+    color: Color,
+    offset: Point,
+    blur_sigma: f64,
+}
+
+impl Serialize for TextShadow {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        TextShadowRepr {
+            color: self.color,
+            offset: self.offset,
+            blur_sigma: self.blur_sigma,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for TextShadow {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = TextShadowRepr::deserialize(deserializer)?;
+        Ok(TextShadow::new(repr.color, repr.offset, repr.blur_sigma))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "FontFeature")]
+struct FontFeatureRepr {
+    name: String,
+    value: i32,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "TextStyle", default)]
+struct TextStyleRepr {
+    color: Color,
+    decoration: Decoration,
+    font_style: FontStyle,
+    shadows: Vec<TextShadow>,
+    font_features: Vec<FontFeatureRepr>,
+    font_size: scalar,
+    font_families: Vec<String>,
+    baseline_shift: scalar,
+    height: scalar,
+    height_override: bool,
+    half_leading: bool,
+    letter_spacing: scalar,
+    word_spacing: scalar,
+    locale: String,
+    #[serde(with = "text_baseline")]
+    text_baseline: TextBaseline,
+}
+
+impl Default for TextStyleRepr {
+    fn default() -> Self {
+        (&TextStyle::new()).into()
+    }
+}
+
+impl From<&TextStyle> for TextStyleRepr {
+    fn from(style: &TextStyle) -> Self {
+        Self {
+            color: style.color(),
+            decoration: *style.decoration(),
+            font_style: style.font_style(),
+            shadows: style.shadows().to_vec(),
+            font_features: style
+                .font_features()
+                .iter()
+                .map(|feature| FontFeatureRepr {
+                    name: feature.name().into(),
+                    value: feature.value(),
+                })
+                .collect(),
+            font_size: style.font_size(),
+            font_families: style.font_families().iter().map(Into::into).collect(),
+            baseline_shift: style.baseline_shift(),
+            // `height()` returns 0 if the height is not overridden.
+            height: style.native().fHeight,
+            height_override: style.height_override(),
+            half_leading: style.half_leading(),
+            letter_spacing: style.letter_spacing(),
+            word_spacing: style.word_spacing(),
+            locale: style.locale().into(),
+            text_baseline: style.text_baseline(),
+        }
+    }
+}
+
+impl From<TextStyleRepr> for TextStyle {
+    fn from(repr: TextStyleRepr) -> Self {
+        let mut style = TextStyle::new();
+        style
+            .set_color(repr.color)
+            .set_font_style(repr.font_style)
+            .set_font_size(repr.font_size)
+            .set_font_families(&repr.font_families)
+            .set_baseline_shift(repr.baseline_shift)
+            .set_height(repr.height)
+            .set_height_override(repr.height_override)
+            .set_half_leading(repr.half_leading)
+            .set_letter_spacing(repr.letter_spacing)
+            .set_word_spacing(repr.word_spacing)
+            .set_locale(repr.locale)
+            .set_text_baseline(repr.text_baseline);
+        style.set_decoration(&repr.decoration);
+        for shadow in repr.shadows {
+            style.add_shadow(shadow);
+        }
+        for feature in repr.font_features {
+            style.add_font_feature(feature.name, feature.value);
+        }
+        style
+    }
+}
+
+impl Serialize for TextStyle {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        TextStyleRepr::from(self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for TextStyle {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        TextStyleRepr::deserialize(deserializer).map(Into::into)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "StrutStyle", default)]
+struct StrutStyleRepr {
+    font_families: Vec<String>,
+    font_style: FontStyle,
+    font_size: scalar,
+    height: scalar,
+    leading: scalar,
+    enabled: bool,
+    force_height: bool,
+    height_override: bool,
+    half_leading: bool,
+}
+
+impl Default for StrutStyleRepr {
+    fn default() -> Self {
+        (&StrutStyle::new()).into()
+    }
+}
+
+impl From<&StrutStyle> for StrutStyleRepr {
+    fn from(style: &StrutStyle) -> Self {
+        Self {
+            font_families: style.font_families().iter().map(Into::into).collect(),
+            font_style: style.font_style(),
+            font_size: style.font_size(),
+            height: style.height(),
+            leading: style.leading(),
+            enabled: style.strut_enabled(),
+            force_height: style.force_strut_height(),
+            height_override: style.height_override(),
+            half_leading: style.half_leading(),
+        }
+    }
+}
+
+impl From<StrutStyleRepr> for StrutStyle {
+    fn from(repr: StrutStyleRepr) -> Self {
+        let mut style = StrutStyle::new();
+        style
+            .set_font_families(&repr.font_families)
+            .set_font_style(repr.font_style)
+            .set_font_size(repr.font_size)
+            .set_height(repr.height)
+            .set_leading(repr.leading)
+            .set_strut_enabled(repr.enabled)
+            .set_force_strut_height(repr.force_height)
+            .set_height_override(repr.height_override)
+            .set_half_leading(repr.half_leading);
+        style
+    }
+}
+
+impl Serialize for StrutStyle {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        StrutStyleRepr::from(self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for StrutStyle {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        StrutStyleRepr::deserialize(deserializer).map(Into::into)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "ParagraphStyle", default)]
+struct ParagraphStyleRepr {
+    strut_style: StrutStyle,
+    text_style: TextStyle,
+    #[serde(with = "text_align")]
+    text_align: TextAlign,
+    #[serde(with = "text_direction")]
+    text_direction: TextDirection,
+    max_lines: Option<usize>,
+    ellipsis: String,
+    height: scalar,
+    #[serde(with = "text_height_behavior")]
+    text_height_behavior: TextHeightBehavior,
+    hinting: bool,
+    replace_tab_characters: bool,
+    apply_rounding_hack: bool,
+}
+
+impl Default for ParagraphStyleRepr {
+    fn default() -> Self {
+        (&ParagraphStyle::new()).into()
+    }
+}
+
+impl From<&ParagraphStyle> for ParagraphStyleRepr {
+    fn from(style: &ParagraphStyle) -> Self {
+        Self {
+            strut_style: style.strut_style().clone(),
+            text_style: style.text_style().clone(),
+            text_align: style.text_align(),
+            text_direction: style.text_direction(),
+            max_lines: style.max_lines(),
+            ellipsis: style.ellipsis().into(),
+            height: style.height(),
+            text_height_behavior: style.text_height_behavior(),
+            hinting: style.hinting_is_on(),
+            replace_tab_characters: style.replace_tab_characters(),
+            apply_rounding_hack: style.apply_rounding_hack(),
+        }
+    }
+}
+
+impl From<ParagraphStyleRepr> for ParagraphStyle {
+    fn from(repr: ParagraphStyleRepr) -> Self {
+        let mut style = ParagraphStyle::new();
+        style
+            .set_strut_style(repr.strut_style)
+            .set_text_style(&repr.text_style)
+            .set_text_align(repr.text_align)
+            .set_text_direction(repr.text_direction)
+            .set_max_lines(repr.max_lines)
+            .set_ellipsis(repr.ellipsis)
+            .set_height(repr.height)
+            .set_text_height_behavior(repr.text_height_behavior)
+            .set_replace_tab_characters(repr.replace_tab_characters)
+            .set_apply_rounding_hack(repr.apply_rounding_hack);
+        if !repr.hinting {
+            style.turn_hinting_off();
+        }
+        style
+    }
+}
+
+impl Serialize for ParagraphStyle {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ParagraphStyleRepr::from(self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ParagraphStyle {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        ParagraphStyleRepr::deserialize(deserializer).map(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::{
+        font_style::{Slant, Weight, Width},
+        textlayout::{
+            ParagraphStyle, StrutStyle, TextAlign, TextDecoration, TextDecorationStyle,
+            TextDirection, TextShadow, TextStyle,
+        },
+        Color, FontStyle,
+    };
+
+    fn text_style() -> TextStyle {
+        let mut style = TextStyle::new();
+        style
+            .set_color(Color::BLUE)
+            .set_font_size(18.0)
+            .set_font_families(&["Roboto", "Noto Sans"])
+            .set_font_style(FontStyle::new(Weight::BOLD, Width::NORMAL, Slant::Upright))
+            .set_letter_spacing(0.5)
+            .set_height(1.5)
+            .set_height_override(true)
+            .set_locale("de-DE")
+            .add_shadow(TextShadow::new(Color::BLACK, (1.0, 2.0), 3.0));
+        style.set_decoration_type(TextDecoration::UNDERLINE | TextDecoration::LINE_THROUGH);
+        style.set_decoration_style(TextDecorationStyle::Wavy);
+        style.add_font_feature("tnum", 1);
+        style
+    }
+
+    #[test]
+    fn text_style_round_trip() {
+        let style = text_style();
+        let json = serde_json::to_value(&style).unwrap();
+        assert_eq!(json["color"], json!("#0000ffff"));
+        assert_eq!(json["font_families"], json!(["Roboto", "Noto Sans"]));
+        assert_eq!(
+            json["decoration"]["type"],
+            json!(["underline", "line-through"])
+        );
+        assert_eq!(json["decoration"]["style"], json!("wavy"));
+        assert_eq!(json["font_features"], json!([{"name": "tnum", "value": 1}]));
+        assert_eq!(json["text_baseline"], json!("alphabetic"));
+
+        let restored: TextStyle = serde_json::from_value(json).unwrap();
+        assert_eq!(restored, style);
+    }
+
+    #[test]
+    fn missing_text_style_fields_are_defaults() {
+        let style: TextStyle = serde_json::from_value(json!({"font_size": 20.0})).unwrap();
+        let mut expected = TextStyle::new();
+        expected.set_font_size(20.0);
+        assert_eq!(style, expected);
+    }
+
+    #[test]
+    fn paragraph_style_round_trip() {
+        let mut strut_style = StrutStyle::new();
+        strut_style
+            .set_font_families(&["Roboto"])
+            .set_strut_enabled(true)
+            .set_leading(2.0);
+
+        let mut style = ParagraphStyle::new();
+        style
+            .set_strut_style(strut_style)
+            .set_text_style(&text_style())
+            .set_text_align(TextAlign::Justify)
+            .set_text_direction(TextDirection::RTL)
+            .set_max_lines(Some(3))
+            .set_ellipsis("…")
+            .turn_hinting_off();
+
+        let json = serde_json::to_value(&style).unwrap();
+        assert_eq!(json["text_align"], json!("justify"));
+        assert_eq!(json["text_direction"], json!("rtl"));
+        assert_eq!(json["max_lines"], json!(3));
+        assert_eq!(json["hinting"], json!(false));
+        assert_eq!(json["strut_style"]["enabled"], json!(true));
+
+        let restored: ParagraphStyle = serde_json::from_value(json).unwrap();
+        assert_eq!(restored, style);
+        assert_eq!(
+            serde_json::from_value::<ParagraphStyle>(json!({})).unwrap(),
+            ParagraphStyle::new()
+        );
+    }
+}
@@ -0,0 +1,291 @@
+//! Wrapper for include/sksl/
+
+use std::fmt;
+
+use skia_bindings::{
+    self as sb, DebugTraceNumberKind, DebugTraceOp, SkRefCntBase, SkSL_DebugTrace,
+};
+
+use crate::{interop, prelude::*, IPoint};
+
+/// The trace of a single pixel's evaluation of an SkSL runtime effect, created by
+/// [`crate::RuntimeEffect::make_traced()`].
+///
+/// The trace is recorded when the traced shader is drawn with the CPU raster backend and covers
+/// the pixel at the trace coordinate.
+pub type DebugTrace = RCHandle<SkSL_DebugTrace>;
+
+impl NativeRefCountedBase for SkSL_DebugTrace {
+    type Base = SkRefCntBase;
+}
+
+impl fmt::Debug for DebugTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DebugTrace")
+            .field("trace_coord", &self.trace_coord())
+            .field("trace_len", &self.trace_len())
+            .finish()
+    }
+}
+
+/// A single event of a [`DebugTrace`].
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum TraceOp {
+    /// Execution reached a line of the source, 1-based.
+    Line(i32),
+    /// A variable slot was assigned the raw value bits.
+    Var { slot: usize, value: i32 },
+    /// A function was entered.
+    Enter(usize),
+    /// A function was exited.
+    Exit(usize),
+    /// A scope was entered (positive delta), or exited (negative delta).
+    Scope(i32),
+}
+
+/// A value assigned to a variable component.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum TraceValue {
+    Float(f32),
+    Int(i32),
+    UInt(u32),
+    Bool(bool),
+}
+
+impl fmt::Display for TraceValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TraceValue::Float(v) => write!(f, "{v}"),
+            TraceValue::Int(v) => write!(f, "{v}"),
+            TraceValue::UInt(v) => write!(f, "{v}"),
+            TraceValue::Bool(v) => write!(f, "{v}"),
+        }
+    }
+}
+
+/// A component of a variable that is tracked in a [`DebugTrace`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TraceSlot {
+    /// The name of the variable.
+    pub name: String,
+    /// The component suffix for vectors and matrices, for example `.x` or `[1][0]`, or an empty
+    /// string for scalars.
+    pub suffix: String,
+    /// The index of the component inside its variable.
+    pub component_index: usize,
+    /// The line on which the variable is declared.
+    pub line: i32,
+    number_kind: DebugTraceNumberKind,
+}
+
+impl TraceSlot {
+    /// Interprets the raw bits of a value assigned to this slot.
+    pub fn value(&self, bits: i32) -> TraceValue {
+        match self.number_kind {
+            DebugTraceNumberKind::Float => TraceValue::Float(f32::from_bits(bits as u32)),
+            DebugTraceNumberKind::Unsigned => TraceValue::UInt(bits as u32),
+            DebugTraceNumberKind::Boolean => TraceValue::Bool(bits != 0),
+            DebugTraceNumberKind::Signed | DebugTraceNumberKind::Nonnumeric => {
+                TraceValue::Int(bits)
+            }
+        }
+    }
+}
+
+/// A variable component and the value it was assigned.
+#[derive(Clone, PartialEq, Debug)]
+pub struct TraceVariable {
+    /// The name of the variable, including the component suffix.
+    pub name: String,
+    pub value: TraceValue,
+}
+
+/// A line of the source that was executed and the variables that were assigned by it.
+#[derive(Clone, PartialEq, Debug)]
+pub struct TraceLine {
+    /// The line number, 1-based.
+    pub line: i32,
+    pub variables: Vec<TraceVariable>,
+}
+
+impl DebugTrace {
+    /// Serializes the trace to the JSON format that Skia's SkSL debugger reads.
+    pub fn write_trace(&self) -> String {
+        let data =
+            crate::Data::from_ptr(unsafe { sb::C_SkSL_DebugTrace_writeTrace(self.native()) })
+                .unwrap();
+        String::from_utf8_lossy(data.as_bytes()).into_owned()
+    }
+
+    /// Returns a human readable dump of the trace.
+    pub fn dump(&self) -> String {
+        let data =
+            crate::Data::from_ptr(unsafe { sb::C_SkSL_DebugTrace_dump(self.native()) }).unwrap();
+        String::from_utf8_lossy(data.as_bytes()).into_owned()
+    }
+
+    /// The pixel coordinate that is traced.
+    pub fn trace_coord(&self) -> IPoint {
+        IPoint::construct(|p| unsafe { sb::C_SkSL_DebugTrace_traceCoord(self.native(), p) })
+    }
+
+    /// The lines of the traced SkSL source.
+    pub fn source(&self) -> Vec<String> {
+        let count = unsafe { sb::C_SkSL_DebugTrace_sourceCount(self.native()) };
+        (0..count)
+            .map(|i| {
+                let mut line = interop::String::default();
+                unsafe { sb::C_SkSL_DebugTrace_source(self.native(), i, line.native_mut()) };
+                line.as_str().into()
+            })
+            .collect()
+    }
+
+    /// The variable components that are tracked, indexed by [`TraceOp::Var`] slots.
+    pub fn slots(&self) -> Vec<TraceSlot> {
+        let count = unsafe { sb::C_SkSL_DebugTrace_slotCount(self.native()) };
+        (0..count)
+            .map(|i| {
+                let mut name = interop::String::default();
+                let mut suffix = interop::String::default();
+                let mut component_index = 0;
+                let mut number_kind = DebugTraceNumberKind::Nonnumeric;
+                let mut line = 0;
+                unsafe {
+                    sb::C_SkSL_DebugTrace_slot(
+                        self.native(),
+                        i,
+                        name.native_mut(),
+                        &mut component_index,
+                        &mut number_kind,
+                        &mut line,
+                    );
+                    sb::C_SkSL_DebugTrace_slotSuffix(self.native(), i, suffix.native_mut());
+                }
+                TraceSlot {
+                    name: name.as_str().into(),
+                    suffix: suffix.as_str().into(),
+                    component_index: component_index.try_into().unwrap(),
+                    line,
+                    number_kind,
+                }
+            })
+            .collect()
+    }
+
+    /// The names of the functions, indexed by [`TraceOp::Enter`] and [`TraceOp::Exit`].
+    pub fn functions(&self) -> Vec<String> {
+        let count = unsafe { sb::C_SkSL_DebugTrace_functionCount(self.native()) };
+        (0..count)
+            .map(|i| {
+                let mut name = interop::String::default();
+                unsafe { sb::C_SkSL_DebugTrace_function(self.native(), i, name.native_mut()) };
+                name.as_str().into()
+            })
+            .collect()
+    }
+
+    /// The number of recorded events.
+    pub fn trace_len(&self) -> usize {
+        unsafe { sb::C_SkSL_DebugTrace_traceCount(self.native()) }
+    }
+
+    /// The recorded events in execution order.
+    pub fn trace(&self) -> Vec<TraceOp> {
+        (0..self.trace_len())
+            .map(|i| {
+                let mut op = DebugTraceOp::Line;
+                let mut data = [0i32; 2];
+                unsafe {
+                    sb::C_SkSL_DebugTrace_trace(self.native(), i, &mut op, data.as_mut_ptr())
+                };
+                match op {
+                    DebugTraceOp::Line => TraceOp::Line(data[0]),
+                    DebugTraceOp::Var => TraceOp::Var {
+                        slot: data[0].try_into().unwrap(),
+                        value: data[1],
+                    },
+                    DebugTraceOp::Enter => TraceOp::Enter(data[0].try_into().unwrap()),
+                    DebugTraceOp::Exit => TraceOp::Exit(data[0].try_into().unwrap()),
+                    DebugTraceOp::Scope => TraceOp::Scope(data[0]),
+                }
+            })
+            .collect()
+    }
+
+    /// The executed lines in execution order, each with the variable values it assigned.
+    ///
+    /// Variable assignments that happen before the first line, like the initialization of
+    /// function parameters, are attributed to a line numbered `0`.
+    pub fn lines(&self) -> Vec<TraceLine> {
+        let slots = self.slots();
+        let mut lines: Vec<TraceLine> = Vec::new();
+        for op in self.trace() {
+            match op {
+                TraceOp::Line(line) => lines.push(TraceLine {
+                    line,
+                    variables: Vec::new(),
+                }),
+                TraceOp::Var { slot, value } => {
+                    if lines.is_empty() {
+                        lines.push(TraceLine {
+                            line: 0,
+                            variables: Vec::new(),
+                        });
+                    }
+                    let slot = &slots[slot];
+                    lines.last_mut().unwrap().variables.push(TraceVariable {
+                        name: format!("{}{}", slot.name, slot.suffix),
+                        value: slot.value(value),
+                    });
+                }
+                TraceOp::Enter(_) | TraceOp::Exit(_) | TraceOp::Scope(_) => {}
+            }
+        }
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{surfaces, Paint, RuntimeEffect};
+
+    #[test]
+    fn trace_a_pixel() {
+        let effect = RuntimeEffect::make_for_shader(
+            "half4 main(float2 p) {\n\
+                 float x = p.x * 2;\n\
+                 return half4(half(x), 0, 0, 1);\n\
+             }",
+            None,
+        )
+        .unwrap();
+        let shader = effect
+            .make_shader(crate::Data::new_empty(), &[], None)
+            .unwrap();
+        let traced = RuntimeEffect::make_traced(shader, (1, 0)).unwrap();
+        assert_eq!(traced.debug_trace.trace_coord(), IPoint::new(1, 0));
+
+        let mut surface = surfaces::raster_n32_premul((2, 1)).unwrap();
+        let mut paint = Paint::default();
+        paint.set_shader(traced.shader);
+        surface.canvas().draw_paint(&paint);
+
+        let trace = traced.debug_trace;
+        assert!(trace.source()[1].contains("float x"));
+        let x = trace
+            .lines()
+            .into_iter()
+            .find(|line| line.line == 2)
+            .unwrap()
+            .variables
+            .into_iter()
+            .find(|v| v.name == "x")
+            .unwrap();
+        // The center of the pixel at x = 1.
+        assert_eq!(x.value, TraceValue::Float(3.0));
+        assert!(trace.write_trace().starts_with('{'));
+        assert!(!trace.dump().is_empty());
+    }
+}
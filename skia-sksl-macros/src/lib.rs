@@ -0,0 +1,168 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, ToTokens};
+use syn::{
+    parse_macro_input, spanned::Spanned, Data, DeriveInput, Expr, Fields, LitStr, Type, TypeArray,
+};
+
+/// A field of the struct and the uniform it describes.
+struct Field {
+    ident: syn::Ident,
+    /// The name of the uniform in SkSL.
+    name: String,
+    /// The type of a single uniform value.
+    element_ty: Type,
+    /// The array length, `None` if the uniform is not an array.
+    len: Option<Expr>,
+}
+
+impl Field {
+    fn parse(field: &syn::Field) -> syn::Result<Self> {
+        let ident = field.ident.clone().unwrap();
+        let mut name = ident.to_string();
+        let mut array = false;
+
+        for attr in field.attrs.iter().filter(|a| a.path().is_ident("uniform")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("name") {
+                    name = meta.value()?.parse::<LitStr>()?.value();
+                    Ok(())
+                } else if meta.path.is_ident("array") {
+                    array = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `name = \"...\"` or `array`"))
+                }
+            })?;
+        }
+
+        let (element_ty, len) = if array {
+            match &field.ty {
+                Type::Array(TypeArray { elem, len, .. }) => ((**elem).clone(), Some(len.clone())),
+                ty => {
+                    return Err(syn::Error::new(
+                        ty.span(),
+                        "`#[uniform(array)]` fields must be of type `[T; N]`",
+                    ))
+                }
+            }
+        } else {
+            (field.ty.clone(), None)
+        };
+
+        Ok(Self {
+            ident,
+            name,
+            element_ty,
+            len,
+        })
+    }
+
+    fn descriptor(&self) -> TokenStream2 {
+        let Self {
+            name,
+            element_ty,
+            len,
+            ..
+        } = self;
+        let count = len
+            .as_ref()
+            .map(|len| len.to_token_stream())
+            .unwrap_or_else(|| quote! { 1 });
+        let value_type = element_ty.to_token_stream().to_string();
+        let is_array = len.is_some();
+
+        quote! {
+            ::skia_safe::runtime_effect::UniformDescriptor {
+                name: #name,
+                value_type: #value_type,
+                count: #count,
+                is_array: #is_array,
+                accepts: <#element_ty as ::skia_safe::runtime_effect::UniformValue>::accepts,
+            }
+        }
+    }
+
+    fn write(&self) -> TokenStream2 {
+        let ident = &self.ident;
+        if self.len.is_some() {
+            quote! {
+                for value in &self.#ident {
+                    ::skia_safe::runtime_effect::UniformValue::write_bytes(value, bytes);
+                }
+            }
+        } else {
+            quote! {
+                ::skia_safe::runtime_effect::UniformValue::write_bytes(&self.#ident, bytes);
+            }
+        }
+    }
+}
+
+fn derive_uniforms2(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new(
+                    input.ident.span(),
+                    "`Uniforms` can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                "`Uniforms` can only be derived for structs",
+            ))
+        }
+    };
+
+    let fields = fields
+        .iter()
+        .map(Field::parse)
+        .collect::<syn::Result<Vec<_>>>()?;
+    for (i, field) in fields.iter().enumerate() {
+        if fields[..i].iter().any(|f| f.name == field.name) {
+            return Err(syn::Error::new(
+                field.ident.span(),
+                format!("uniform `{}` is provided more than once", field.name),
+            ));
+        }
+    }
+    let descriptors = fields.iter().map(Field::descriptor);
+    let writes = fields.iter().map(Field::write);
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::skia_safe::runtime_effect::Uniforms for #ident #ty_generics #where_clause {
+            const DESCRIPTORS: &'static [::skia_safe::runtime_effect::UniformDescriptor] = &[
+                #(#descriptors),*
+            ];
+
+            fn write_bytes(&self, bytes: &mut Vec<u8>) {
+                #(#writes)*
+            }
+        }
+    })
+}
+
+/// Derives `skia_safe::runtime_effect::Uniforms` for a struct whose fields correspond, in
+/// declaration order, to the uniforms of an SkSL runtime effect.
+///
+/// Each field type must implement `skia_safe::runtime_effect::UniformValue`. Fields can be
+/// annotated with:
+///
+/// - `#[uniform(name = "u_name")]` to use a different uniform name than the field's name.
+/// - `#[uniform(array)]` for fields of type `[T; N]` that correspond to an SkSL array of `N`
+///   values of type `T`.
+///
+/// Each uniform can only be provided by one field.
+#[proc_macro_derive(Uniforms, attributes(uniform))]
+pub fn derive_uniforms(input: TokenStream) -> TokenStream {
+    derive_uniforms2(parse_macro_input!(input as DeriveInput))
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}